@@ -0,0 +1,84 @@
+//! Gas benchmark for batch mints and transfers of 1, 10 and 100 items, with distinct
+//! ids and with one id repeated (the case the per-run balance accessor targets).
+//!
+//! Runs against a deployed, initialized contract whose owner is the signing key:
+//!
+//! ```sh
+//! RPC_URL=... PRIVATE_KEY=... STYLUS_CONTRACT_ADDRESS=... cargo run --example batch_gas
+//! ```
+
+use std::{str::FromStr, sync::Arc};
+
+use ethers::{
+    middleware::SignerMiddleware,
+    prelude::abigen,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, Bytes, U256},
+};
+use eyre::{eyre, Result};
+
+abigen!(
+    Erc1155,
+    r#"[
+        function mintBatch(address to, uint256[] ids, uint256[] values, bytes data) external
+        function safeBatchTransferFrom(address from, address to, uint256[] ids, uint256[] values, bytes data) external
+    ]"#
+);
+
+const SIZES: [usize; 3] = [1, 10, 100];
+
+/// Fresh ids per run, so every measurement starts from zero balances.
+const ID_STRIDE: u64 = 1_000;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv::dotenv().ok();
+    let rpc_url = env("RPC_URL")?;
+    let private_key = env("PRIVATE_KEY")?;
+    let contract_address = Address::from_str(&env("STYLUS_CONTRACT_ADDRESS")?)?;
+
+    let provider = Provider::<Http>::try_from(rpc_url)?;
+    let chain_id = provider.get_chainid().await?.as_u64();
+    let wallet = LocalWallet::from_str(&private_key)?.with_chain_id(chain_id);
+    let owner = wallet.address();
+    let client = Arc::new(SignerMiddleware::new(provider, wallet));
+    let token = Erc1155::new(contract_address, client);
+    let recipient = Address::from_low_u64_be(0xbeef);
+
+    println!("{:>6} {:>10} {:>12} {:>12} {:>12}", "items", "ids", "mint gas", "xfer gas", "xfer/item");
+    for (run, &size) in SIZES.iter().enumerate() {
+        for repeated in [false, true] {
+            let base = (run as u64 * 2 + repeated as u64 + 1) * ID_STRIDE;
+            let ids: Vec<U256> = (0..size as u64)
+                .map(|i| U256::from(if repeated { base } else { base + i }))
+                .collect();
+            let values = vec![U256::from(1); size];
+
+            let mint = token.mint_batch(owner, ids.clone(), values.clone(), Bytes::new());
+            let mint_gas = gas_used(mint.send().await?.await?)?;
+            let transfer = token.safe_batch_transfer_from(owner, recipient, ids, values, Bytes::new());
+            let transfer_gas = gas_used(transfer.send().await?.await?)?;
+
+            println!(
+                "{:>6} {:>10} {:>12} {:>12} {:>12}",
+                size,
+                if repeated { "repeated" } else { "distinct" },
+                mint_gas,
+                transfer_gas,
+                transfer_gas / U256::from(size)
+            );
+        }
+    }
+    Ok(())
+}
+
+fn env(name: &str) -> Result<String> {
+    std::env::var(name).map_err(|_| eyre!("{name} not set"))
+}
+
+fn gas_used(receipt: Option<ethers::types::TransactionReceipt>) -> Result<U256> {
+    receipt
+        .and_then(|r| r.gas_used)
+        .ok_or_else(|| eyre!("transaction dropped"))
+}
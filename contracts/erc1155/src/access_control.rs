@@ -0,0 +1,369 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::{ownable::OwnershipTransferred, revert};
+
+/// Role that administers every other role. Matches OpenZeppelin's `0x00`.
+pub const DEFAULT_ADMIN_ROLE: B256 = B256::ZERO;
+
+/// Delay applied to default-admin transfers until the admin changes it (3 days).
+pub const INITIAL_DEFAULT_ADMIN_DELAY: U256 = U256::from_limbs([259_200, 0, 0, 0]);
+
+/// Longest admin delay accepted, `type(uint48).max` as in OpenZeppelin, so schedules
+/// built from it can't overflow.
+pub const MAX_DEFAULT_ADMIN_DELAY: U256 = U256::from_limbs([0xffff_ffff_ffff, 0, 0, 0]);
+
+sol_storage! {
+    pub struct AccessControl {
+        mapping(bytes32 => mapping(address => bool)) roles;
+        mapping(bytes32 => address[]) role_members;
+        mapping(bytes32 => mapping(address => uint256)) role_member_index;
+        mapping(bytes32 => bytes32) role_admin;
+        address current_default_admin;
+        address pending_default_admin;
+        uint256 pending_default_admin_schedule;
+        uint256 current_delay;
+        uint256 pending_delay;
+        uint256 pending_delay_schedule;
+        bool initialized;
+    }
+}
+
+sol! {
+    event RoleGranted(bytes32 indexed role, address indexed account, address indexed sender);
+    event RoleRevoked(bytes32 indexed role, address indexed account, address indexed sender);
+    event RoleAdminChanged(bytes32 indexed role, bytes32 indexed previousAdminRole, bytes32 indexed newAdminRole);
+    event DefaultAdminTransferScheduled(address indexed newAdmin, uint256 acceptSchedule);
+    event DefaultAdminTransferCanceled();
+    event DefaultAdminDelayChangeScheduled(uint256 newDelay, uint256 effectSchedule);
+    event DefaultAdminDelayChangeCanceled();
+
+    error AccessControlUnauthorizedAccount(address account, bytes32 neededRole);
+    error AccessControlBadConfirmation();
+    error InvalidInitialization();
+    error AccessControlOutOfBoundsIndex(bytes32 role, uint256 index);
+    error AccessControlInvalidDefaultAdmin(address defaultAdmin);
+    error AccessControlEnforcedDefaultAdminRules();
+    error AccessControlEnforcedDefaultAdminDelay(uint256 schedule);
+    error AccessControlInvalidDefaultAdminDelay(uint256 delay);
+}
+
+pub enum AccessControlError {
+    UnauthorizedAccount(AccessControlUnauthorizedAccount),
+    BadConfirmation(AccessControlBadConfirmation),
+    InvalidInitialization(InvalidInitialization),
+    OutOfBoundsIndex(AccessControlOutOfBoundsIndex),
+    InvalidDefaultAdmin(AccessControlInvalidDefaultAdmin),
+    EnforcedDefaultAdminRules(AccessControlEnforcedDefaultAdminRules),
+    EnforcedDefaultAdminDelay(AccessControlEnforcedDefaultAdminDelay),
+    InvalidDefaultAdminDelay(AccessControlInvalidDefaultAdminDelay),
+}
+
+impl From<AccessControlError> for Vec<u8> {
+    fn from(error: AccessControlError) -> Self {
+        match error {
+            AccessControlError::UnauthorizedAccount(e) => revert::encode(e),
+            AccessControlError::BadConfirmation(e) => revert::encode(e),
+            AccessControlError::InvalidInitialization(e) => revert::encode(e),
+            AccessControlError::OutOfBoundsIndex(e) => revert::encode(e),
+            AccessControlError::InvalidDefaultAdmin(e) => revert::encode(e),
+            AccessControlError::EnforcedDefaultAdminRules(e) => revert::encode(e),
+            AccessControlError::EnforcedDefaultAdminDelay(e) => revert::encode(e),
+            AccessControlError::InvalidDefaultAdminDelay(e) => revert::encode(e),
+        }
+    }
+}
+
+impl AccessControl {
+    pub fn has_role(&self, role: B256, account: Address) -> bool {
+        self.roles.get(role).get(account)
+    }
+
+    pub fn get_role_member_count(&self, role: B256) -> U256 {
+        U256::from(self.role_members.get(role).len())
+    }
+
+    /// Members are unordered; indices shift when an account is revoked.
+    pub fn get_role_member(&self, role: B256, index: U256) -> Result<Address, AccessControlError> {
+        self.role_members.get(role).get(index).ok_or(AccessControlError::OutOfBoundsIndex(
+            AccessControlOutOfBoundsIndex { role, index },
+        ))
+    }
+
+    pub fn get_role_members(&self, role: B256) -> Vec<Address> {
+        let members = self.role_members.get(role);
+        (0..members.len()).filter_map(|i| members.get(i)).collect()
+    }
+
+    /// Reverts unless the caller holds `role`.
+    pub fn only_role(&self, role: B256) -> Result<(), AccessControlError> {
+        self._check_role(role, self.vm().msg_sender())
+    }
+
+    /// Role whose members may grant and revoke `role`. Defaults to `DEFAULT_ADMIN_ROLE`.
+    pub fn get_role_admin(&self, role: B256) -> B256 {
+        self.role_admin.get(role)
+    }
+
+    /// `DEFAULT_ADMIN_ROLE` can only move through the two-step transfer below.
+    pub fn grant_role(&mut self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        if role == DEFAULT_ADMIN_ROLE {
+            return Err(AccessControlError::EnforcedDefaultAdminRules(AccessControlEnforcedDefaultAdminRules {}));
+        }
+        self.only_role(self.get_role_admin(role))?;
+        self._grant_role(role, account);
+        Ok(())
+    }
+
+    pub fn revoke_role(&mut self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        if role == DEFAULT_ADMIN_ROLE {
+            return Err(AccessControlError::EnforcedDefaultAdminRules(AccessControlEnforcedDefaultAdminRules {}));
+        }
+        self.only_role(self.get_role_admin(role))?;
+        self._revoke_role(role, account);
+        Ok(())
+    }
+
+    /// Delegates management of `role` to holders of `admin_role`, e.g. a GAME_ADMIN role
+    /// that manages GAME_ROLE without holding `DEFAULT_ADMIN_ROLE`.
+    pub fn set_role_admin(&mut self, role: B256, admin_role: B256) -> Result<(), AccessControlError> {
+        self.only_role(DEFAULT_ADMIN_ROLE)?;
+        self._set_role_admin(role, admin_role);
+        Ok(())
+    }
+
+    /// Lets an account drop one of its own roles. `confirmation` must equal the caller.
+    /// The default admin can only renounce after a transfer to the zero address has matured.
+    pub fn renounce_role(&mut self, role: B256, confirmation: Address) -> Result<(), AccessControlError> {
+        if confirmation != self.vm().msg_sender() {
+            return Err(AccessControlError::BadConfirmation(AccessControlBadConfirmation {}));
+        }
+        if role == DEFAULT_ADMIN_ROLE && confirmation == self.default_admin() {
+            let (pending, schedule) = self.pending_default_admin();
+            if !pending.is_zero() || !self._is_schedule_passed(schedule) {
+                return Err(AccessControlError::EnforcedDefaultAdminDelay(AccessControlEnforcedDefaultAdminDelay {
+                    schedule,
+                }));
+            }
+            self.pending_default_admin_schedule.erase();
+            self.current_default_admin.erase();
+            log(self.vm(), OwnershipTransferred {
+                previousOwner: confirmation,
+                newOwner: Address::ZERO,
+            });
+        }
+        self._revoke_role(role, confirmation);
+        Ok(())
+    }
+
+    /// One-shot bootstrap that makes `admin` the single default admin.
+    pub fn _initialize(&mut self, admin: Address) -> Result<(), AccessControlError> {
+        if self.initialized.get() {
+            return Err(AccessControlError::InvalidInitialization(InvalidInitialization {}));
+        }
+        self.initialized.set(true);
+        self.current_delay.set(INITIAL_DEFAULT_ADMIN_DELAY);
+        self.current_default_admin.set(admin);
+        self._grant_role(DEFAULT_ADMIN_ROLE, admin);
+        log(self.vm(), OwnershipTransferred {
+            previousOwner: Address::ZERO,
+            newOwner: admin,
+        });
+        Ok(())
+    }
+
+    pub fn default_admin(&self) -> Address {
+        self.current_default_admin.get()
+    }
+
+    /// Returns `(new_admin, accept_schedule)` of the in-flight transfer, if any.
+    pub fn pending_default_admin(&self) -> (Address, U256) {
+        (self.pending_default_admin.get(), self.pending_default_admin_schedule.get())
+    }
+
+    /// The delay in force now, taking a matured pending change into account.
+    pub fn default_admin_delay(&self) -> U256 {
+        let schedule = self.pending_delay_schedule.get();
+        if self._is_schedule_set(schedule) && self._is_schedule_passed(schedule) {
+            self.pending_delay.get()
+        } else {
+            self.current_delay.get()
+        }
+    }
+
+    /// Returns `(new_delay, effect_schedule)` of a delay change that hasn't taken effect yet.
+    pub fn pending_default_admin_delay(&self) -> (U256, U256) {
+        let schedule = self.pending_delay_schedule.get();
+        if self._is_schedule_set(schedule) && !self._is_schedule_passed(schedule) {
+            (self.pending_delay.get(), schedule)
+        } else {
+            (U256::ZERO, U256::ZERO)
+        }
+    }
+
+    /// Starts a transfer that `new_admin` can accept once the current delay has elapsed.
+    /// Scheduling again replaces the previous pending admin.
+    pub fn begin_default_admin_transfer(&mut self, new_admin: Address) -> Result<(), AccessControlError> {
+        self.only_role(DEFAULT_ADMIN_ROLE)?;
+        let schedule = U256::from(self.vm().block_timestamp()).saturating_add(self.default_admin_delay());
+        self.pending_default_admin.set(new_admin);
+        self.pending_default_admin_schedule.set(schedule);
+        log(self.vm(), DefaultAdminTransferScheduled {
+            newAdmin: new_admin,
+            acceptSchedule: schedule,
+        });
+        Ok(())
+    }
+
+    pub fn cancel_default_admin_transfer(&mut self) -> Result<(), AccessControlError> {
+        self.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.pending_default_admin.erase();
+        self.pending_default_admin_schedule.erase();
+        log(self.vm(), DefaultAdminTransferCanceled {});
+        Ok(())
+    }
+
+    pub fn accept_default_admin_transfer(&mut self) -> Result<(), AccessControlError> {
+        let (new_admin, schedule) = self.pending_default_admin();
+        if self.vm().msg_sender() != new_admin || new_admin.is_zero() {
+            return Err(AccessControlError::InvalidDefaultAdmin(AccessControlInvalidDefaultAdmin {
+                defaultAdmin: self.vm().msg_sender(),
+            }));
+        }
+        if !self._is_schedule_passed(schedule) {
+            return Err(AccessControlError::EnforcedDefaultAdminDelay(AccessControlEnforcedDefaultAdminDelay {
+                schedule,
+            }));
+        }
+
+        let previous = self.default_admin();
+        self._revoke_role(DEFAULT_ADMIN_ROLE, previous);
+        self._grant_role(DEFAULT_ADMIN_ROLE, new_admin);
+        self.current_default_admin.set(new_admin);
+        self.pending_default_admin.erase();
+        self.pending_default_admin_schedule.erase();
+        log(self.vm(), OwnershipTransferred {
+            previousOwner: previous,
+            newOwner: new_admin,
+        });
+        Ok(())
+    }
+
+    /// Schedules a new delay, at most [`MAX_DEFAULT_ADMIN_DELAY`]. The change itself waits
+    /// out the current delay, so a compromised admin can't shorten the window before a
+    /// hostile transfer.
+    pub fn change_default_admin_delay(&mut self, new_delay: U256) -> Result<(), AccessControlError> {
+        self.only_role(DEFAULT_ADMIN_ROLE)?;
+        if new_delay > MAX_DEFAULT_ADMIN_DELAY {
+            return Err(AccessControlError::InvalidDefaultAdminDelay(AccessControlInvalidDefaultAdminDelay {
+                delay: new_delay,
+            }));
+        }
+        let current = self.default_admin_delay();
+        let schedule = U256::from(self.vm().block_timestamp()).saturating_add(current);
+        self.current_delay.set(current);
+        self.pending_delay.set(new_delay);
+        self.pending_delay_schedule.set(schedule);
+        log(self.vm(), DefaultAdminDelayChangeScheduled {
+            newDelay: new_delay,
+            effectSchedule: schedule,
+        });
+        Ok(())
+    }
+
+    pub fn rollback_default_admin_delay(&mut self) -> Result<(), AccessControlError> {
+        self.only_role(DEFAULT_ADMIN_ROLE)?;
+        let current = self.default_admin_delay();
+        self.current_delay.set(current);
+        self.pending_delay.erase();
+        self.pending_delay_schedule.erase();
+        log(self.vm(), DefaultAdminDelayChangeCanceled {});
+        Ok(())
+    }
+
+    pub fn _check_role(&self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        if !self.has_role(role, account) {
+            return Err(AccessControlError::UnauthorizedAccount(AccessControlUnauthorizedAccount {
+                account,
+                neededRole: role,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Grants `role` to `account`, returning whether anything changed.
+    pub fn _grant_role(&mut self, role: B256, account: Address) -> bool {
+        if self.has_role(role, account) {
+            return false;
+        }
+        self.roles.setter(role).insert(account, true);
+        let mut members = self.role_members.setter(role);
+        members.push(account);
+        let position = U256::from(members.len());
+        self.role_member_index.setter(role).insert(account, position);
+        log(self.vm(), RoleGranted {
+            role,
+            account,
+            sender: self.vm().msg_sender(),
+        });
+        true
+    }
+
+    /// Revokes `role` from `account`, returning whether anything changed.
+    pub fn _revoke_role(&mut self, role: B256, account: Address) -> bool {
+        if !self.has_role(role, account) {
+            return false;
+        }
+        self.roles.setter(role).delete(account);
+        self._remove_member(role, account);
+        log(self.vm(), RoleRevoked {
+            role,
+            account,
+            sender: self.vm().msg_sender(),
+        });
+        true
+    }
+
+    pub fn _set_role_admin(&mut self, role: B256, admin_role: B256) {
+        let previous = self.get_role_admin(role);
+        self.role_admin.insert(role, admin_role);
+        log(self.vm(), RoleAdminChanged {
+            role,
+            previousAdminRole: previous,
+            newAdminRole: admin_role,
+        });
+    }
+
+    fn _is_schedule_set(&self, schedule: U256) -> bool {
+        !schedule.is_zero()
+    }
+
+    fn _is_schedule_passed(&self, schedule: U256) -> bool {
+        self._is_schedule_set(schedule) && schedule <= U256::from(self.vm().block_timestamp())
+    }
+
+    /// Swap-and-pop removal from the role's member list.
+    fn _remove_member(&mut self, role: B256, account: Address) {
+        let position = self.role_member_index.get(role).get(account);
+        if position.is_zero() {
+            return;
+        }
+        let mut members = self.role_members.setter(role);
+        let index = position.to::<usize>() - 1;
+        let last = members.len() - 1;
+        if index != last {
+            let moved = members.get(last).unwrap_or_default();
+            if let Some(mut slot) = members.setter(index) {
+                slot.set(moved);
+            }
+            self.role_member_index.setter(role).insert(moved, position);
+        }
+        members.pop();
+        self.role_member_index.setter(role).delete(account);
+    }
+}
@@ -0,0 +1,141 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::U256,
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct IdAliases {
+        mapping(uint256 => bool) aliased;
+        mapping(uint256 => uint256) canonical;
+        // Number of aliases pointing at each canonical id.
+        mapping(uint256 => uint256) alias_count;
+    }
+}
+
+sol! {
+    event IdAliased(uint256 indexed aliasId, uint256 indexed canonicalId);
+    event IdAliasRemoved(uint256 indexed aliasId);
+
+    error InvalidIdAlias(uint256 aliasId, uint256 canonicalId);
+    error IdAliasHasSupply(uint256 aliasId, uint256 supply);
+}
+
+pub enum AliasError {
+    InvalidAlias(InvalidIdAlias),
+    HasSupply(IdAliasHasSupply),
+}
+
+impl From<AliasError> for Vec<u8> {
+    fn from(error: AliasError) -> Self {
+        match error {
+            AliasError::InvalidAlias(e) => revert::encode(e),
+            AliasError::HasSupply(e) => revert::encode(e),
+        }
+    }
+}
+
+impl IdAliases {
+    /// Maps a deprecated id to its canonical id; unaliased ids resolve to themselves.
+    pub fn resolve(&self, id: U256) -> U256 {
+        if self.aliased.get(id) {
+            self.canonical.get(id)
+        } else {
+            id
+        }
+    }
+
+    pub fn resolve_all(&self, ids: Vec<U256>) -> Vec<U256> {
+        ids.into_iter().map(|id| self.resolve(id)).collect()
+    }
+
+    /// Aliases are a single hop: `canonical_id` may not itself be an alias, and
+    /// `alias_id` may not be the target of one. `alias_supply` is `alias_id`'s current
+    /// supply, which must be zero: once aliased, its balances could no longer be reached.
+    pub fn set_alias(&mut self, alias_id: U256, canonical_id: U256, alias_supply: U256) -> Result<(), AliasError> {
+        if alias_id == canonical_id || self.aliased.get(canonical_id) || !self.alias_count.get(alias_id).is_zero() {
+            return Err(AliasError::InvalidAlias(InvalidIdAlias {
+                aliasId: alias_id,
+                canonicalId: canonical_id,
+            }));
+        }
+        if !alias_supply.is_zero() {
+            return Err(AliasError::HasSupply(IdAliasHasSupply {
+                aliasId: alias_id,
+                supply: alias_supply,
+            }));
+        }
+        if self.aliased.get(alias_id) {
+            self._release_target(alias_id);
+        }
+        self.aliased.insert(alias_id, true);
+        self.canonical.insert(alias_id, canonical_id);
+        self.alias_count.insert(canonical_id, self.alias_count.get(canonical_id) + U256::from(1));
+        log(self.vm(), IdAliased {
+            aliasId: alias_id,
+            canonicalId: canonical_id,
+        });
+        Ok(())
+    }
+
+    pub fn remove_alias(&mut self, alias_id: U256) {
+        if self.aliased.get(alias_id) {
+            self._release_target(alias_id);
+        }
+        self.aliased.delete(alias_id);
+        self.canonical.delete(alias_id);
+        log(self.vm(), IdAliasRemoved { aliasId: alias_id });
+    }
+
+    fn _release_target(&mut self, alias_id: U256) {
+        let target = self.canonical.get(alias_id);
+        self.alias_count.insert(target, self.alias_count.get(target) - U256::from(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{alloy_primitives::uint, testing::*};
+
+    const OLD: U256 = uint!(1_U256);
+    const NEW: U256 = uint!(2_U256);
+    const NEWER: U256 = uint!(3_U256);
+
+    #[test]
+    fn alias_targets_cannot_become_aliases() {
+        let vm = TestVM::default();
+        let mut aliases = IdAliases::from(&vm);
+        assert!(aliases.set_alias(OLD, NEW, U256::ZERO).is_ok());
+        assert!(matches!(aliases.set_alias(NEW, NEWER, U256::ZERO), Err(AliasError::InvalidAlias(_))));
+        assert!(matches!(aliases.set_alias(NEWER, OLD, U256::ZERO), Err(AliasError::InvalidAlias(_))));
+
+        aliases.remove_alias(OLD);
+        assert!(aliases.set_alias(NEW, NEWER, U256::ZERO).is_ok());
+        assert_eq!(aliases.resolve(NEW), NEWER);
+        assert_eq!(aliases.resolve(OLD), OLD);
+    }
+
+    #[test]
+    fn repointing_an_alias_frees_its_old_target() {
+        let vm = TestVM::default();
+        let mut aliases = IdAliases::from(&vm);
+        assert!(aliases.set_alias(OLD, NEW, U256::ZERO).is_ok());
+        assert!(aliases.set_alias(OLD, NEWER, U256::ZERO).is_ok());
+        assert_eq!(aliases.resolve(OLD), NEWER);
+        assert!(aliases.set_alias(NEW, NEWER, U256::ZERO).is_ok());
+    }
+
+    #[test]
+    fn ids_with_supply_cannot_become_aliases() {
+        let vm = TestVM::default();
+        let mut aliases = IdAliases::from(&vm);
+        let result = aliases.set_alias(OLD, NEW, U256::from(5));
+        assert!(matches!(result, Err(AliasError::HasSupply(e)) if e.supply == U256::from(5)));
+        assert_eq!(aliases.resolve(OLD), OLD);
+    }
+}
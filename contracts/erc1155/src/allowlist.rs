@@ -0,0 +1,71 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{b256, Address, B256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+/// `keccak256("ALLOWLIST_ADMIN_ROLE")`
+pub const ALLOWLIST_ADMIN_ROLE: B256 =
+    b256!("e9ea3f660aa5a8eccd1bf9d16e6cdf3c1cf9a2b284b830f15bda4493942cb68f");
+
+sol_storage! {
+    pub struct ReceiverAllowlist {
+        bool enabled;
+        mapping(address => bool) allowed;
+    }
+}
+
+sol! {
+    event AllowlistModeSet(bool enabled);
+    event AllowlistUpdated(address indexed account, bool allowed);
+
+    error ERC1155ReceiverNotAllowlisted(address receiver);
+}
+
+pub enum AllowlistError {
+    ReceiverNotAllowlisted(ERC1155ReceiverNotAllowlisted),
+}
+
+impl From<AllowlistError> for Vec<u8> {
+    fn from(error: AllowlistError) -> Self {
+        match error {
+            AllowlistError::ReceiverNotAllowlisted(e) => revert::encode(e),
+        }
+    }
+}
+
+impl ReceiverAllowlist {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    pub fn is_allowlisted(&self, account: Address) -> bool {
+        self.allowed.get(account)
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled.set(enabled);
+        log(self.vm(), AllowlistModeSet { enabled });
+    }
+
+    pub fn set_allowlisted(&mut self, account: Address, allowed: bool) {
+        if allowed {
+            self.allowed.insert(account, true);
+        } else {
+            self.allowed.delete(account);
+        }
+        log(self.vm(), AllowlistUpdated { account, allowed });
+    }
+
+    /// Burns (`to == 0`) are never blocked; everything else must be listed while the mode is on.
+    pub fn check_receiver(&self, to: Address) -> Result<(), AllowlistError> {
+        if !self.is_enabled() || to.is_zero() || self.is_allowlisted(to) {
+            return Ok(());
+        }
+        Err(AllowlistError::ReceiverNotAllowlisted(ERC1155ReceiverNotAllowlisted { receiver: to }))
+    }
+}
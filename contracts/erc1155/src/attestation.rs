@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    call::Call,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::{interfaces::IAttester, revert};
+
+sol_storage! {
+    pub struct AttestationGate {
+        address attester;
+        mapping(uint256 => bytes32) required_schema;
+    }
+}
+
+sol! {
+    event AttesterSet(address attester);
+    event AttestationRequirementSet(uint256 indexed id, bytes32 schema);
+
+    error ERC1155MissingAttestation(address account, uint256 id, bytes32 schema);
+}
+
+pub enum AttestationError {
+    MissingAttestation(ERC1155MissingAttestation),
+}
+
+impl From<AttestationError> for Vec<u8> {
+    fn from(error: AttestationError) -> Self {
+        match error {
+            AttestationError::MissingAttestation(e) => revert::encode(e),
+        }
+    }
+}
+
+impl AttestationGate {
+    pub fn attester(&self) -> Address {
+        self.attester.get()
+    }
+
+    pub fn required_schema(&self, id: U256) -> B256 {
+        self.required_schema.get(id)
+    }
+
+    pub fn set_attester(&mut self, attester: Address) {
+        self.attester.set(attester);
+        log(self.vm(), AttesterSet { attester });
+    }
+
+    /// A zero `schema` removes the requirement for `id`.
+    pub fn set_required_schema(&mut self, id: U256, schema: B256) {
+        self.required_schema.insert(id, schema);
+        log(self.vm(), AttestationRequirementSet { id, schema });
+    }
+
+    /// Queries the attester for every gated id. A failing or missing attester counts as "not attested".
+    pub fn check_receiver(&self, to: Address, ids: &[U256]) -> Result<(), AttestationError> {
+        if to.is_zero() {
+            return Ok(());
+        }
+        let attester = IAttester::new(self.attester.get());
+        for &id in ids {
+            let schema = self.required_schema.get(id);
+            if schema.is_zero() {
+                continue;
+            }
+            let attested = !attester.address.is_zero()
+                && attester.has_attestation(Call::new(), to, schema).unwrap_or(false);
+            if !attested {
+                return Err(AttestationError::MissingAttestation(ERC1155MissingAttestation {
+                    account: to,
+                    id,
+                    schema,
+                }));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+//! Per-id bonding curves. A curve can only be configured on an id with no supply, and
+//! the contract's other mint paths refuse curve ids, so every unit in circulation was
+//! paid for along the curve and its reserve always covers burning it back.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{uint, U256, U8},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+pub const CURVE_NONE: u8 = 0;
+/// `price(s) = base + factor * s`
+pub const CURVE_LINEAR: u8 = 1;
+/// `price(s) = base * (1 + factor / 1e18)^s`
+pub const CURVE_EXPONENTIAL: u8 = 2;
+
+pub(crate) const WAD: U256 = uint!(1_000_000_000_000_000_000_U256);
+
+sol_storage! {
+    pub struct BondingCurves {
+        mapping(uint256 => uint8) kind;
+        mapping(uint256 => uint256) base_price;
+        mapping(uint256 => uint256) factor;
+        mapping(uint256 => uint256) supply;
+        mapping(uint256 => uint256) reserve;
+    }
+}
+
+sol! {
+    event CurveConfigured(uint256 indexed id, uint8 kind, uint256 basePrice, uint256 factor);
+    event CurveMinted(address indexed account, uint256 indexed id, uint256 amount, uint256 cost);
+    event CurveBurned(address indexed account, uint256 indexed id, uint256 amount, uint256 proceeds);
+
+    error CurveNotConfigured(uint256 id);
+    error CurveInvalidConfig(uint8 kind, uint256 factor);
+    error CurveActive(uint256 id, uint256 supply);
+    error CurveManagedId(uint256 id);
+    error CurveOverflow(uint256 id);
+    error CurveSlippage(uint256 price, uint256 limit);
+    error CurveInsufficientSupply(uint256 id, uint256 requested, uint256 supply);
+    error CurvePaymentFailed(address to, uint256 amount);
+}
+
+pub enum CurveError {
+    NotConfigured(CurveNotConfigured),
+    InvalidConfig(CurveInvalidConfig),
+    Active(CurveActive),
+    Managed(CurveManagedId),
+    Overflow(CurveOverflow),
+    Slippage(CurveSlippage),
+    InsufficientSupply(CurveInsufficientSupply),
+    PaymentFailed(CurvePaymentFailed),
+}
+
+impl From<CurveError> for Vec<u8> {
+    fn from(error: CurveError) -> Self {
+        match error {
+            CurveError::NotConfigured(e) => revert::encode(e),
+            CurveError::InvalidConfig(e) => revert::encode(e),
+            CurveError::Active(e) => revert::encode(e),
+            CurveError::Managed(e) => revert::encode(e),
+            CurveError::Overflow(e) => revert::encode(e),
+            CurveError::Slippage(e) => revert::encode(e),
+            CurveError::InsufficientSupply(e) => revert::encode(e),
+            CurveError::PaymentFailed(e) => revert::encode(e),
+        }
+    }
+}
+
+/// `x^n` for a WAD-scaled `x`, by squaring.
+pub(crate) fn wad_pow(mut x: U256, mut n: U256) -> Option<U256> {
+    let mut result = WAD;
+    while !n.is_zero() {
+        if n.bit(0) {
+            result = result.checked_mul(x)? / WAD;
+        }
+        n >>= 1;
+        if !n.is_zero() {
+            x = x.checked_mul(x)? / WAD;
+        }
+    }
+    Some(result)
+}
+
+impl BondingCurves {
+    /// Returns `(kind, base_price, factor, supply, reserve)` for `id`.
+    pub fn config(&self, id: U256) -> (u8, U256, U256, U256, U256) {
+        (
+            self.kind.get(id).to::<u8>(),
+            self.base_price.get(id),
+            self.factor.get(id),
+            self.supply.get(id),
+            self.reserve.get(id),
+        )
+    }
+
+    /// Changing the curve would strand the reserve, and units minted elsewhere would draw
+    /// on it, so this is only allowed while `id` has no supply at all.
+    pub fn configure(
+        &mut self,
+        id: U256,
+        kind: u8,
+        base_price: U256,
+        factor: U256,
+        total_supply: U256,
+    ) -> Result<(), CurveError> {
+        let supply = self.supply.get(id).max(total_supply);
+        if !supply.is_zero() {
+            return Err(CurveError::Active(CurveActive { id, supply }));
+        }
+        if kind > CURVE_EXPONENTIAL || (kind == CURVE_EXPONENTIAL && factor.is_zero()) {
+            return Err(CurveError::InvalidConfig(CurveInvalidConfig { kind, factor }));
+        }
+        self.kind.insert(id, U8::from(kind));
+        self.base_price.insert(id, base_price);
+        self.factor.insert(id, factor);
+        log(self.vm(), CurveConfigured {
+            id,
+            kind,
+            basePrice: base_price,
+            factor,
+        });
+        Ok(())
+    }
+
+    /// Curve ids are minted only through the curve; other mint paths check here.
+    pub fn check_external_mint(&self, id: U256) -> Result<(), CurveError> {
+        if self.kind.get(id).to::<u8>() != CURVE_NONE {
+            return Err(CurveError::Managed(CurveManagedId { id }));
+        }
+        Ok(())
+    }
+
+    /// Price of the next unit.
+    pub fn spot_price(&self, id: U256) -> Result<U256, CurveError> {
+        let supply = self.supply.get(id);
+        self._cost(id, supply, U256::from(1))
+    }
+
+    pub fn quote_mint(&self, id: U256, amount: U256) -> Result<U256, CurveError> {
+        self._cost(id, self.supply.get(id), amount)
+    }
+
+    pub fn quote_burn(&self, id: U256, amount: U256) -> Result<U256, CurveError> {
+        let supply = self.supply.get(id);
+        if amount > supply {
+            return Err(CurveError::InsufficientSupply(CurveInsufficientSupply {
+                id,
+                requested: amount,
+                supply,
+            }));
+        }
+        let proceeds = self._cost(id, supply - amount, amount)?;
+        // Rounding on the way in never lets the reserve fall short, but stay defensive.
+        Ok(proceeds.min(self.reserve.get(id)))
+    }
+
+    /// Books a mint paid with `value` and returns the cost; the caller refunds the rest.
+    pub fn _mint(&mut self, id: U256, amount: U256, value: U256) -> Result<U256, CurveError> {
+        let cost = self.quote_mint(id, amount)?;
+        if cost > value {
+            return Err(CurveError::Slippage(CurveSlippage { price: cost, limit: value }));
+        }
+        self.supply.insert(id, self.supply.get(id) + amount);
+        self.reserve.insert(id, self.reserve.get(id) + cost);
+        log(self.vm(), CurveMinted {
+            account: self.vm().msg_sender(),
+            id,
+            amount,
+            cost,
+        });
+        Ok(cost)
+    }
+
+    /// Books a burn and returns the ETH owed to the caller.
+    pub fn _burn(&mut self, id: U256, amount: U256, min_proceeds: U256) -> Result<U256, CurveError> {
+        let proceeds = self.quote_burn(id, amount)?;
+        if proceeds < min_proceeds {
+            return Err(CurveError::Slippage(CurveSlippage {
+                price: proceeds,
+                limit: min_proceeds,
+            }));
+        }
+        self.supply.insert(id, self.supply.get(id) - amount);
+        self.reserve.insert(id, self.reserve.get(id) - proceeds);
+        log(self.vm(), CurveBurned {
+            account: self.vm().msg_sender(),
+            id,
+            amount,
+            proceeds,
+        });
+        Ok(proceeds)
+    }
+
+    /// Sum of unit prices from `supply` to `supply + amount - 1`, rounded up.
+    fn _cost(&self, id: U256, supply: U256, amount: U256) -> Result<U256, CurveError> {
+        let base = self.base_price.get(id);
+        let factor = self.factor.get(id);
+        let cost = match self.kind.get(id).to::<u8>() {
+            CURVE_LINEAR => {
+                // base * n + factor * n * (2s + n - 1) / 2; the product is always even.
+                supply
+                    .checked_mul(U256::from(2))
+                    .and_then(|s| s.checked_add(amount))
+                    .and_then(|span| amount.checked_mul(span.saturating_sub(U256::from(1))))
+                    .and_then(|steps| factor.checked_mul(steps / U256::from(2)))
+                    .and_then(|slope| base.checked_mul(amount)?.checked_add(slope))
+            }
+            CURVE_EXPONENTIAL => {
+                // base * (m^(s+n) - m^s) / (m - 1) with m = 1 + factor.
+                let m = WAD.checked_add(factor);
+                let upper = m.zip(supply.checked_add(amount)).and_then(|(m, end)| wad_pow(m, end));
+                let lower = m.and_then(|m| wad_pow(m, supply));
+                upper
+                    .zip(lower)
+                    .and_then(|(upper, lower)| base.checked_mul(upper - lower))
+                    .map(|scaled| scaled.div_ceil(factor))
+            }
+            _ => return Err(CurveError::NotConfigured(CurveNotConfigured { id })),
+        };
+        cost.ok_or(CurveError::Overflow(CurveOverflow { id }))
+    }
+}
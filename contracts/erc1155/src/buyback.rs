@@ -0,0 +1,277 @@
+//! Treasury buyback-and-burn. The budget is fed from proceeds: a configurable share of
+//! every released primary sale, plus royalties whose receiver is this contract. The
+//! treasury spends it buying an id off a trading pool and burning the units, or through
+//! a standing bid holders can sell into. Both are rate-limited per id and capped at the
+//! id's configured unit price.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{b256, uint, Address, B256, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+/// `keccak256("TREASURY_ROLE")`
+pub const TREASURY_ROLE: B256 =
+    b256!("e1dcbdb91df27212a29bc27177c840cf2f819ecf2187432e1fac86c2dd5dfca9");
+
+const BPS: U256 = uint!(10_000_U256);
+
+sol_storage! {
+    pub struct Buyback {
+        uint256 budget;
+        uint256 sale_share_bps;
+        mapping(uint256 => uint256) price;
+        mapping(uint256 => uint256) period;
+        mapping(uint256 => uint256) period_limit;
+        mapping(uint256 => uint256) period_start;
+        mapping(uint256 => uint256) period_bought;
+    }
+}
+
+sol! {
+    event TreasuryFunded(address indexed from, uint256 amount);
+    event BuybackSaleShareSet(uint256 shareBps);
+    event BuybackConfigured(uint256 indexed id, uint256 price, uint256 period, uint256 periodLimit);
+    event BuybackExecuted(address indexed seller, uint256 indexed id, uint256 amount, uint256 cost);
+    event BuybackFromPool(uint256 indexed poolId, uint256 indexed id, uint256 amount, uint256 cost);
+
+    error BuybackDisabled(uint256 id);
+    error BuybackRateLimited(uint256 id, uint256 requested, uint256 remaining);
+    error BuybackInsufficientBudget(uint256 needed, uint256 available);
+    error BuybackPriceTooHigh(uint256 id, uint256 cost, uint256 limit);
+    error BuybackInvalidShare(uint256 shareBps);
+    error BuybackPaymentFailed(address to, uint256 amount);
+}
+
+pub enum BuybackError {
+    Disabled(BuybackDisabled),
+    RateLimited(BuybackRateLimited),
+    InsufficientBudget(BuybackInsufficientBudget),
+    PriceTooHigh(BuybackPriceTooHigh),
+    InvalidShare(BuybackInvalidShare),
+    PaymentFailed(BuybackPaymentFailed),
+}
+
+impl From<BuybackError> for Vec<u8> {
+    fn from(error: BuybackError) -> Self {
+        match error {
+            BuybackError::Disabled(e) => revert::encode(e),
+            BuybackError::RateLimited(e) => revert::encode(e),
+            BuybackError::InsufficientBudget(e) => revert::encode(e),
+            BuybackError::PriceTooHigh(e) => revert::encode(e),
+            BuybackError::InvalidShare(e) => revert::encode(e),
+            BuybackError::PaymentFailed(e) => revert::encode(e),
+        }
+    }
+}
+
+/// `amount * bps / 10_000` rounded down, without overflowing for any `bps <= 10_000`.
+pub fn share_of(amount: U256, bps: U256) -> U256 {
+    amount / BPS * bps + amount % BPS * bps / BPS
+}
+
+impl Buyback {
+    pub fn budget(&self) -> U256 {
+        self.budget.get()
+    }
+
+    /// Returns `(price, period, period_limit, remaining_in_period)` for `id`.
+    pub fn config(&self, id: U256) -> (U256, U256, U256, U256) {
+        let limit = self.period_limit.get(id);
+        let bought = if self._period_elapsed(id) { U256::ZERO } else { self.period_bought.get(id) };
+        (self.price.get(id), self.period.get(id), limit, limit.saturating_sub(bought))
+    }
+
+    pub fn sale_share_bps(&self) -> U256 {
+        self.sale_share_bps.get()
+    }
+
+    /// Share of released primary-sale proceeds, in basis points, kept for the budget.
+    pub fn set_sale_share(&mut self, share_bps: U256) -> Result<(), BuybackError> {
+        if share_bps > BPS {
+            return Err(BuybackError::InvalidShare(BuybackInvalidShare { shareBps: share_bps }));
+        }
+        self.sale_share_bps.set(share_bps);
+        log(self.vm(), BuybackSaleShareSet { shareBps: share_bps });
+        Ok(())
+    }
+
+    /// The budget's cut of `proceeds`, rounded down; the rest goes to the creator.
+    pub fn sale_share(&self, proceeds: U256) -> U256 {
+        share_of(proceeds, self.sale_share_bps.get())
+    }
+
+    /// Adds `amount` the contract already holds to the budget.
+    pub fn _fund(&mut self, from: Address, amount: U256) {
+        self.budget.set(self.budget.get() + amount);
+        log(self.vm(), TreasuryFunded { from, amount });
+    }
+
+    /// `price` caps what the treasury pays per unit of `id`, on a pool or through its
+    /// standing bid; zero disables both.
+    pub fn configure(&mut self, id: U256, price: U256, period: U256, period_limit: U256) {
+        self.price.insert(id, price);
+        self.period.insert(id, period);
+        self.period_limit.insert(id, period_limit);
+        self.period_start.insert(id, U256::from(self.vm().block_timestamp()));
+        self.period_bought.insert(id, U256::ZERO);
+        log(self.vm(), BuybackConfigured {
+            id,
+            price,
+            period,
+            periodLimit: period_limit,
+        });
+    }
+
+    /// Books a sale of `amount` units into the standing bid and returns the ETH owed.
+    /// The caller burns the units and pays the seller.
+    pub fn _execute(&mut self, id: U256, amount: U256) -> Result<U256, BuybackError> {
+        let cost = amount.checked_mul(self.price.get(id)).unwrap_or(U256::MAX);
+        self._book(id, amount, cost)?;
+        log(self.vm(), BuybackExecuted {
+            seller: self.vm().msg_sender(),
+            id,
+            amount,
+            cost,
+        });
+        Ok(cost)
+    }
+
+    /// Books a buy of `amount` units off `pool_id` for `cost`, fees and royalty included.
+    /// The caller pays the pool and burns the units.
+    pub fn _execute_from_pool(&mut self, pool_id: U256, id: U256, amount: U256, cost: U256) -> Result<(), BuybackError> {
+        self._book(id, amount, cost)?;
+        log(self.vm(), BuybackFromPool {
+            poolId: pool_id,
+            id,
+            amount,
+            cost,
+        });
+        Ok(())
+    }
+
+    /// Checks the unit-price cap, the period's rate limit and the budget, then spends
+    /// `cost` from the budget.
+    fn _book(&mut self, id: U256, amount: U256, cost: U256) -> Result<(), BuybackError> {
+        let price = self.price.get(id);
+        if price.is_zero() {
+            return Err(BuybackError::Disabled(BuybackDisabled { id }));
+        }
+        let limit = amount.checked_mul(price).unwrap_or(U256::MAX);
+        if cost > limit {
+            return Err(BuybackError::PriceTooHigh(BuybackPriceTooHigh { id, cost, limit }));
+        }
+
+        if self._period_elapsed(id) {
+            self.period_start.insert(id, U256::from(self.vm().block_timestamp()));
+            self.period_bought.insert(id, U256::ZERO);
+        }
+        let bought = self.period_bought.get(id);
+        let remaining = self.period_limit.get(id).saturating_sub(bought);
+        if amount > remaining {
+            return Err(BuybackError::RateLimited(BuybackRateLimited {
+                id,
+                requested: amount,
+                remaining,
+            }));
+        }
+
+        let budget = self.budget.get();
+        if cost > budget {
+            return Err(BuybackError::InsufficientBudget(BuybackInsufficientBudget {
+                needed: cost,
+                available: budget,
+            }));
+        }
+
+        self.period_bought.insert(id, bought + amount);
+        self.budget.set(budget - cost);
+        Ok(())
+    }
+
+    fn _period_elapsed(&self, id: U256) -> bool {
+        let end = self.period_start.get(id).saturating_add(self.period.get(id));
+        U256::from(self.vm().block_timestamp()) >= end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{alloy_primitives::address, testing::*};
+
+    const FUNDER: Address = address!("f0dd000000000000000000000000000000000001");
+    const ID: U256 = uint!(1_U256);
+    const POOL: U256 = uint!(7_U256);
+
+    fn setup() -> (TestVM, Buyback) {
+        let vm = TestVM::default();
+        vm.set_block_timestamp(1_000);
+        let mut buyback = Buyback::from(&vm);
+        buyback._fund(FUNDER, U256::from(1_000));
+        // Up to 10 wei a unit, 50 units a day.
+        buyback.configure(ID, U256::from(10), U256::from(86_400), U256::from(50));
+        (vm, buyback)
+    }
+
+    #[test]
+    fn pool_buys_spend_the_budget() {
+        let (_vm, mut buyback) = setup();
+        assert!(buyback._execute_from_pool(POOL, ID, U256::from(20), U256::from(150)).is_ok());
+        assert_eq!(buyback.budget(), U256::from(850));
+        assert_eq!(buyback.config(ID).3, U256::from(30));
+    }
+
+    #[test]
+    fn pool_buys_above_the_price_cap_revert() {
+        let (_vm, mut buyback) = setup();
+        let result = buyback._execute_from_pool(POOL, ID, U256::from(20), U256::from(201));
+        assert!(matches!(result, Err(BuybackError::PriceTooHigh(e)) if e.limit == U256::from(200)));
+        assert_eq!(buyback.budget(), U256::from(1_000));
+    }
+
+    #[test]
+    fn buys_are_rate_limited_per_period() {
+        let (vm, mut buyback) = setup();
+        assert!(buyback._execute_from_pool(POOL, ID, U256::from(50), U256::from(100)).is_ok());
+        let result = buyback._execute_from_pool(POOL, ID, U256::from(1), U256::from(1));
+        assert!(matches!(result, Err(BuybackError::RateLimited(e)) if e.remaining.is_zero()));
+
+        vm.set_block_timestamp(1_000 + 86_400);
+        assert!(buyback._execute_from_pool(POOL, ID, U256::from(1), U256::from(1)).is_ok());
+    }
+
+    #[test]
+    fn buys_cannot_exceed_the_budget() {
+        let (_vm, mut buyback) = setup();
+        buyback.configure(ID, U256::from(100), U256::from(86_400), U256::from(50));
+        let result = buyback._execute_from_pool(POOL, ID, U256::from(11), U256::from(1_100));
+        assert!(matches!(result, Err(BuybackError::InsufficientBudget(_))));
+    }
+
+    #[test]
+    fn disabled_ids_are_not_bought() {
+        let (_vm, mut buyback) = setup();
+        buyback.configure(ID, U256::ZERO, U256::ZERO, U256::ZERO);
+        assert!(matches!(buyback._execute(ID, U256::from(1)), Err(BuybackError::Disabled(_))));
+    }
+
+    #[test]
+    fn sale_share_rounds_down() {
+        let (_vm, mut buyback) = setup();
+        assert_eq!(buyback.sale_share(U256::from(999)), U256::ZERO);
+        assert!(buyback.set_sale_share(U256::from(2_500)).is_ok());
+        assert_eq!(buyback.sale_share(U256::from(999)), U256::from(249));
+        assert!(matches!(buyback.set_sale_share(BPS + U256::from(1)), Err(BuybackError::InvalidShare(_))));
+    }
+
+    #[test]
+    fn share_of_does_not_overflow() {
+        assert_eq!(share_of(U256::MAX, BPS), U256::MAX);
+        assert_eq!(share_of(U256::MAX, U256::ZERO), U256::ZERO);
+    }
+}
@@ -0,0 +1,60 @@
+use stylus_sdk::{
+    alloy_primitives::U256,
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+sol_storage! {
+    pub struct CircuitBreaker {
+        mapping(uint256 => uint256) threshold;
+        mapping(uint256 => uint256) window_block;
+        mapping(uint256 => uint256) window_volume;
+    }
+}
+
+sol! {
+    event CircuitBreakerThresholdSet(uint256 indexed id, uint256 threshold);
+    event CircuitBreakerTripped(uint256 indexed id, uint256 volume, uint256 threshold);
+}
+
+impl CircuitBreaker {
+    pub fn threshold(&self, id: U256) -> U256 {
+        self.threshold.get(id)
+    }
+
+    /// Volume moved for `id` in the current block.
+    pub fn current_volume(&self, id: U256) -> U256 {
+        if self.window_block.get(id) == U256::from(self.vm().block_number()) {
+            self.window_volume.get(id)
+        } else {
+            U256::ZERO
+        }
+    }
+
+    /// A zero threshold disables the breaker for `id`.
+    pub fn set_threshold(&mut self, id: U256, threshold: U256) {
+        self.threshold.insert(id, threshold);
+        log(self.vm(), CircuitBreakerThresholdSet { id, threshold });
+    }
+
+    /// Adds `value` to this block's volume for `id` and returns true if that pushed it
+    /// over the threshold. The transfer that trips the breaker still goes through, so
+    /// the caller must persist the pause rather than revert.
+    pub fn _record(&mut self, id: U256, value: U256) -> bool {
+        let threshold = self.threshold.get(id);
+        if threshold.is_zero() {
+            return false;
+        }
+
+        let volume = self.current_volume(id).saturating_add(value);
+        self.window_block.insert(id, U256::from(self.vm().block_number()));
+        self.window_volume.insert(id, volume);
+
+        if volume <= threshold {
+            return false;
+        }
+        log(self.vm(), CircuitBreakerTripped { id, volume, threshold });
+        true
+    }
+}
@@ -0,0 +1,71 @@
+//! Signature recovery through the `ecrecover` precompile, shared by every
+//! signature-accepting entrypoint.
+
+use stylus_sdk::{
+    alloy_primitives::{address, uint, Address, B256, U256},
+    call::{static_call, Call},
+};
+
+pub const ECRECOVER: Address = address!("0000000000000000000000000000000000000001");
+
+/// secp256k1 `n / 2`. Signatures with a larger `s` are malleable twins and are rejected.
+const HALF_ORDER: U256 = uint!(0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF5D576E7357A4501DDFE92F46681B20A0_U256);
+
+/// Recovers the signer of `hash`, or `None` for malformed or non-canonical signatures.
+pub fn recover(hash: B256, v: u8, r: B256, s: B256) -> Option<Address> {
+    if U256::from_be_bytes(s.0) > HALF_ORDER || (v != 27 && v != 28) {
+        return None;
+    }
+
+    let mut input = [0u8; 128];
+    input[..32].copy_from_slice(hash.as_slice());
+    input[63] = v;
+    input[64..96].copy_from_slice(r.as_slice());
+    input[96..].copy_from_slice(s.as_slice());
+
+    let output = static_call(Call::new(), ECRECOVER, &input).ok()?;
+    if output.len() != 32 {
+        return None;
+    }
+    let signer = Address::from_slice(&output[12..]);
+    (!signer.is_zero()).then_some(signer)
+}
+
+/// Recovers from a 65-byte `r ++ s ++ v` signature or a 64-byte EIP-2098 compact
+/// `r ++ vs` signature, where the top bit of `vs` carries `v`. A 65-byte `v` may be
+/// 0/1 or 27/28.
+pub fn recover_signature(hash: B256, signature: &[u8]) -> Option<Address> {
+    let (v, r, s) = split(signature)?;
+    recover(hash, v, r, s)
+}
+
+/// Splits a 65- or 64-byte signature into `(v, r, s)` with `v` normalized to 27/28.
+pub fn split(signature: &[u8]) -> Option<(u8, B256, B256)> {
+    let r = B256::from_slice(signature.get(..32)?);
+    match signature.len() {
+        65 => {
+            let v = match signature[64] {
+                v @ 0..=1 => v + 27,
+                v => v,
+            };
+            Some((v, r, B256::from_slice(&signature[32..64])))
+        }
+        64 => {
+            let mut s = B256::from_slice(&signature[32..]);
+            let v = 27 + (s[0] >> 7);
+            s[0] &= 0x7f;
+            Some((v, r, s))
+        }
+        _ => None,
+    }
+}
+
+/// Packs split components back into the 65-byte form accepted by [`recover_signature`],
+/// for entrypoints that take `v`, `r` and `s` separately.
+pub fn join(v: u8, r: B256, s: B256) -> [u8; 65] {
+    let mut signature = [0u8; 65];
+    signature[..32].copy_from_slice(r.as_slice());
+    signature[32..64].copy_from_slice(s.as_slice());
+    signature[64] = v;
+    signature
+}
@@ -0,0 +1,56 @@
+//! EIP-712 domain shared by permits, vouchers and signed orders.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{b256, Address, B256, U256},
+    crypto::keccak,
+    prelude::*,
+    stylus_core::Host,
+};
+
+/// `keccak256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")`
+pub const DOMAIN_TYPEHASH: B256 =
+    b256!("8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f");
+/// `keccak256("My1155")`
+pub const NAME_HASH: B256 =
+    b256!("2bd6e0c58eb758ee917460c5abe359ce8129eb0ffe75b851f34a9bc2c2f8542a");
+/// `keccak256("1")`
+pub const VERSION_HASH: B256 =
+    b256!("c89efdaa54c0f20c7adf612882df0950f5a951637e0307cdcb4c672f298b8bc6");
+
+/// `keccak256` of the concatenated words, i.e. the EIP-712 `encodeData` of a struct or array.
+pub fn hash_words(words: &[B256]) -> B256 {
+    let mut buf = Vec::with_capacity(words.len() * 32);
+    for word in words {
+        buf.extend_from_slice(word.as_slice());
+    }
+    keccak(buf)
+}
+
+pub fn address_word(account: Address) -> B256 {
+    account.into_word()
+}
+
+pub fn bool_word(value: bool) -> B256 {
+    B256::from(U256::from(value as u8))
+}
+
+pub fn domain_separator(vm: &dyn Host) -> B256 {
+    hash_words(&[
+        DOMAIN_TYPEHASH,
+        NAME_HASH,
+        VERSION_HASH,
+        B256::from(U256::from(vm.chain_id())),
+        address_word(vm.contract_address()),
+    ])
+}
+
+/// `keccak256("\x19\x01" ++ domainSeparator ++ structHash)`
+pub fn hash_typed_data(vm: &dyn Host, struct_hash: B256) -> B256 {
+    let mut buf = [0u8; 66];
+    buf[0] = 0x19;
+    buf[1] = 0x01;
+    buf[2..34].copy_from_slice(domain_separator(vm).as_slice());
+    buf[34..].copy_from_slice(struct_hash.as_slice());
+    keccak(buf)
+}
@@ -0,0 +1,317 @@
+//! Per-id emission schedules. Each epoch releases a fixed amount split between the
+//! schedule's recipients by share; anyone can call `tick` on an id to mint what has
+//! accrued. Schedules leave the scheduled list once every epoch has been emitted, or
+//! when the id's supply cap leaves no room for the next mint.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct EmissionSchedules {
+        uint256[] ids;
+        // Index in `ids` plus one; zero for ids not scheduled.
+        mapping(uint256 => uint256) positions;
+        mapping(uint256 => EmissionSchedule) schedules;
+    }
+
+    pub struct EmissionSchedule {
+        uint256 start;
+        uint256 epoch_length;
+        uint256 amount_per_epoch;
+        uint256 epochs;
+        uint256 epochs_emitted;
+        address[] recipients;
+        uint256[] shares;
+    }
+}
+
+sol! {
+    event EmissionScheduled(uint256 indexed id, uint256 start, uint256 epochLength, uint256 amountPerEpoch, uint256 epochs, address[] recipients, uint256[] shares);
+    event Emitted(uint256 indexed id, uint256 epochs, uint256 amount);
+    event EmissionStopped(uint256 indexed id, uint256 epochsEmitted);
+
+    error EmissionInvalidSchedule(uint256 id);
+}
+
+pub enum EmissionError {
+    InvalidSchedule(EmissionInvalidSchedule),
+}
+
+impl From<EmissionError> for Vec<u8> {
+    fn from(error: EmissionError) -> Self {
+        match error {
+            EmissionError::InvalidSchedule(e) => revert::encode(e),
+        }
+    }
+}
+
+impl EmissionSchedules {
+    pub fn scheduled_ids(&self) -> Vec<U256> {
+        (0..self.ids.len()).filter_map(|i| self.ids.get(i)).collect()
+    }
+
+    /// Returns `(start, epoch_length, amount_per_epoch, epochs, epochs_emitted)`.
+    pub fn schedule(&self, id: U256) -> (U256, U256, U256, U256, U256) {
+        let schedule = self.schedules.get(id);
+        (
+            schedule.start.get(),
+            schedule.epoch_length.get(),
+            schedule.amount_per_epoch.get(),
+            schedule.epochs.get(),
+            schedule.epochs_emitted.get(),
+        )
+    }
+
+    /// Returns the recipients of `id`'s emissions and their shares.
+    pub fn recipients(&self, id: U256) -> (Vec<Address>, Vec<U256>) {
+        let schedule = self.schedules.get(id);
+        let recipients = (0..schedule.recipients.len()).filter_map(|i| schedule.recipients.get(i)).collect();
+        let shares = (0..schedule.shares.len()).filter_map(|i| schedule.shares.get(i)).collect();
+        (recipients, shares)
+    }
+
+    /// Epochs of `id` that have elapsed but not been minted yet.
+    pub fn pending_epochs(&self, id: U256) -> U256 {
+        let schedule = self.schedules.get(id);
+        let (start, length) = (schedule.start.get(), schedule.epoch_length.get());
+        let now = U256::from(self.vm().block_timestamp());
+        if length.is_zero() || now < start {
+            return U256::ZERO;
+        }
+        let elapsed = ((now - start) / length).min(schedule.epochs.get());
+        elapsed.saturating_sub(schedule.epochs_emitted.get())
+    }
+
+    /// Each recipient's cut of `id`'s pending epochs, without marking them emitted.
+    /// Rounding dust goes to the first recipient.
+    pub fn pending_cuts(&self, id: U256) -> Vec<(Address, U256)> {
+        self._cuts(id, self.pending_epochs(id))
+    }
+
+    fn _cuts(&self, id: U256, epochs: U256) -> Vec<(Address, U256)> {
+        if epochs.is_zero() {
+            return Vec::new();
+        }
+        let (recipients, shares) = self.recipients(id);
+        let amount = self.schedules.get(id).amount_per_epoch.get() * epochs;
+        let total_shares = shares.iter().fold(U256::ZERO, |acc, s| acc + *s);
+        let mut cuts: Vec<(Address, U256)> = recipients
+            .into_iter()
+            .zip(shares)
+            .map(|(recipient, share)| (recipient, amount * share / total_shares))
+            .collect();
+        let distributed = cuts.iter().fold(U256::ZERO, |acc, (_, cut)| acc + *cut);
+        cuts[0].1 += amount - distributed;
+        cuts
+    }
+
+    /// Replaces `id`'s schedule. Epoch 0 ends at `start + epoch_length`. Epochs already
+    /// emitted stay emitted, so `epochs` can't be lower than that count, and a schedule
+    /// with nothing left to emit isn't listed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn configure(
+        &mut self,
+        id: U256,
+        start: U256,
+        epoch_length: U256,
+        amount_per_epoch: U256,
+        epochs: U256,
+        recipients: Vec<Address>,
+        shares: Vec<U256>,
+    ) -> Result<(), EmissionError> {
+        let total_shares = shares.iter().fold(U256::ZERO, |acc, s| acc.saturating_add(*s));
+        let emitted = self.schedules.get(id).epochs_emitted.get();
+        if epoch_length.is_zero()
+            || epochs < emitted
+            || recipients.is_empty()
+            || recipients.len() != shares.len()
+            || recipients.iter().any(|r| r.is_zero())
+            || total_shares.is_zero()
+            || amount_per_epoch.checked_mul(epochs).and_then(|a| a.checked_mul(total_shares)).is_none()
+        {
+            return Err(EmissionError::InvalidSchedule(EmissionInvalidSchedule { id }));
+        }
+
+        if epochs > emitted {
+            self._schedule(id);
+        } else {
+            self._unschedule(id);
+        }
+        let mut schedule = self.schedules.setter(id);
+        schedule.start.set(start);
+        schedule.epoch_length.set(epoch_length);
+        schedule.amount_per_epoch.set(amount_per_epoch);
+        schedule.epochs.set(epochs);
+        schedule.recipients.erase();
+        schedule.shares.erase();
+        for (recipient, share) in recipients.iter().zip(shares.iter()) {
+            schedule.recipients.push(*recipient);
+            schedule.shares.push(*share);
+        }
+
+        log(self.vm(), EmissionScheduled {
+            id,
+            start,
+            epochLength: epoch_length,
+            amountPerEpoch: amount_per_epoch,
+            epochs,
+            recipients,
+            shares,
+        });
+        Ok(())
+    }
+
+    /// Marks up to `max_epochs` of `id`'s pending epochs as emitted, oldest first, and
+    /// returns each recipient's cut, as [`Self::pending_cuts`]. Unlists the schedule once
+    /// its last epoch is emitted.
+    pub fn _tick(&mut self, id: U256, max_epochs: U256) -> Vec<(Address, U256)> {
+        let pending = self.pending_epochs(id).min(max_epochs);
+        if pending.is_zero() {
+            return Vec::new();
+        }
+        let cuts = self._cuts(id, pending);
+        let (amount, finished) = {
+            let mut schedule = self.schedules.setter(id);
+            let emitted = schedule.epochs_emitted.get() + pending;
+            schedule.epochs_emitted.set(emitted);
+            (schedule.amount_per_epoch.get() * pending, emitted == schedule.epochs.get())
+        };
+        if finished {
+            self._unschedule(id);
+        }
+        log(self.vm(), Emitted { id, epochs: pending, amount });
+        cuts
+    }
+
+    /// Ends `id`'s schedule where it is, e.g. once the id's supply cap is too low for
+    /// even one epoch.
+    pub fn _stop(&mut self, id: U256) {
+        let emitted = {
+            let mut schedule = self.schedules.setter(id);
+            let emitted = schedule.epochs_emitted.get();
+            schedule.epochs.set(emitted);
+            emitted
+        };
+        self._unschedule(id);
+        log(self.vm(), EmissionStopped { id, epochsEmitted: emitted });
+    }
+
+    fn _schedule(&mut self, id: U256) {
+        if self.positions.get(id).is_zero() {
+            self.ids.push(id);
+            self.positions.insert(id, U256::from(self.ids.len()));
+        }
+    }
+
+    /// Swap-removes `id` from `ids`.
+    fn _unschedule(&mut self, id: U256) {
+        let position = self.positions.get(id);
+        if position.is_zero() {
+            return;
+        }
+        let index = position.to::<usize>() - 1;
+        let last_index = self.ids.len() - 1;
+        if index != last_index {
+            let last = self.ids.get(last_index).unwrap_or_default();
+            if let Some(mut slot) = self.ids.setter(index) {
+                slot.set(last);
+            }
+            self.positions.insert(last, position);
+        }
+        self.ids.pop();
+        self.positions.delete(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{
+        alloy_primitives::{address, uint},
+        testing::*,
+    };
+
+    const ALICE: Address = address!("a11ce00000000000000000000000000000000001");
+    const BOB: Address = address!("b0b0000000000000000000000000000000000002");
+    const ID: U256 = uint!(1_U256);
+    const OTHER_ID: U256 = uint!(2_U256);
+
+    fn configure(schedules: &mut EmissionSchedules, id: U256, epochs: u64) -> bool {
+        schedules
+            .configure(
+                id,
+                U256::ZERO,
+                U256::from(10),
+                U256::from(100),
+                U256::from(epochs),
+                vec![ALICE, BOB],
+                vec![U256::from(1), U256::from(2)],
+            )
+            .is_ok()
+    }
+
+    #[test]
+    fn cuts_split_by_share_with_dust_to_the_first() {
+        let vm = TestVM::default();
+        let mut schedules = EmissionSchedules::from(&vm);
+        assert!(configure(&mut schedules, ID, 5));
+        vm.set_block_timestamp(25);
+        assert_eq!(schedules.pending_epochs(ID), U256::from(2));
+        assert_eq!(schedules._tick(ID, U256::MAX), vec![(ALICE, U256::from(67)), (BOB, U256::from(133))]);
+        assert_eq!(schedules.pending_epochs(ID), U256::ZERO);
+    }
+
+    #[test]
+    fn partial_ticks_leave_the_rest_pending() {
+        let vm = TestVM::default();
+        let mut schedules = EmissionSchedules::from(&vm);
+        assert!(configure(&mut schedules, ID, 5));
+        vm.set_block_timestamp(30);
+        assert_eq!(schedules._tick(ID, U256::from(1)), vec![(ALICE, U256::from(34)), (BOB, U256::from(66))]);
+        assert_eq!(schedules.pending_epochs(ID), U256::from(2));
+        assert_eq!(schedules.schedule(ID).3, U256::from(5));
+        assert_eq!(schedules.scheduled_ids(), vec![ID]);
+    }
+
+    #[test]
+    fn reconfiguring_keeps_emitted_epochs() {
+        let vm = TestVM::default();
+        let mut schedules = EmissionSchedules::from(&vm);
+        assert!(configure(&mut schedules, ID, 5));
+        vm.set_block_timestamp(30);
+        assert_eq!(schedules._tick(ID, U256::MAX).len(), 2);
+
+        assert!(configure(&mut schedules, ID, 5));
+        assert_eq!(schedules.schedule(ID).4, U256::from(3));
+        assert_eq!(schedules.pending_epochs(ID), U256::ZERO);
+        assert!(!configure(&mut schedules, ID, 2));
+    }
+
+    #[test]
+    fn finished_and_stopped_schedules_are_unlisted() {
+        let vm = TestVM::default();
+        let mut schedules = EmissionSchedules::from(&vm);
+        assert!(configure(&mut schedules, ID, 2));
+        assert!(configure(&mut schedules, OTHER_ID, 10));
+        assert_eq!(schedules.scheduled_ids(), vec![ID, OTHER_ID]);
+
+        vm.set_block_timestamp(100);
+        schedules._tick(ID, U256::MAX);
+        assert_eq!(schedules.scheduled_ids(), vec![OTHER_ID]);
+
+        schedules._stop(OTHER_ID);
+        assert!(schedules.scheduled_ids().is_empty());
+        assert_eq!(schedules.pending_epochs(OTHER_ID), U256::ZERO);
+
+        // More epochs put a finished schedule back on the list.
+        assert!(configure(&mut schedules, ID, 4));
+        assert_eq!(schedules.scheduled_ids(), vec![ID]);
+    }
+}
@@ -0,0 +1,223 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+};
+
+use crate::revert;
+
+sol_storage! {
+    /// Holder/id sets kept in sync by `Erc1155::_apply_single` when the crate is built
+    /// with the `enumerable` feature. Indices are stored 1-based so zero means "absent".
+    pub struct Erc1155Enumerable {
+        mapping(uint256 => address[]) holders;
+        mapping(uint256 => mapping(address => uint256)) holder_index;
+        mapping(address => uint256[]) owned_ids;
+        mapping(address => mapping(uint256 => uint256)) owned_index;
+        uint256[] all_ids;
+        mapping(uint256 => uint256) all_ids_index;
+    }
+}
+
+sol! {
+    error ERC1155OutOfBoundsIndex(uint256 index);
+}
+
+pub enum EnumerableError {
+    OutOfBoundsIndex(ERC1155OutOfBoundsIndex),
+}
+
+impl From<EnumerableError> for Vec<u8> {
+    fn from(error: EnumerableError) -> Self {
+        match error {
+            EnumerableError::OutOfBoundsIndex(e) => revert::encode(e),
+        }
+    }
+}
+
+fn out_of_bounds(index: U256) -> EnumerableError {
+    EnumerableError::OutOfBoundsIndex(ERC1155OutOfBoundsIndex { index })
+}
+
+impl Erc1155Enumerable {
+    pub fn holder_count(&self, id: U256) -> U256 {
+        U256::from(self.holders.get(id).len())
+    }
+
+    pub fn holder_at(&self, id: U256, index: U256) -> Result<Address, EnumerableError> {
+        self.holders.get(id).get(index).ok_or_else(|| out_of_bounds(index))
+    }
+
+    pub fn id_count_of(&self, account: Address) -> U256 {
+        U256::from(self.owned_ids.get(account).len())
+    }
+
+    pub fn id_of_owner_at(&self, account: Address, index: U256) -> Result<U256, EnumerableError> {
+        self.owned_ids.get(account).get(index).ok_or_else(|| out_of_bounds(index))
+    }
+
+    pub fn total_ids(&self) -> U256 {
+        U256::from(self.all_ids.len())
+    }
+
+    pub fn id_at(&self, index: U256) -> Result<U256, EnumerableError> {
+        self.all_ids.get(index).ok_or_else(|| out_of_bounds(index))
+    }
+
+    pub fn holders(&self, id: U256) -> Vec<Address> {
+        let list = self.holders.get(id);
+        (0..list.len()).filter_map(|i| list.get(i)).collect()
+    }
+
+    pub fn ids_of(&self, account: Address) -> Vec<U256> {
+        let list = self.owned_ids.get(account);
+        (0..list.len()).filter_map(|i| list.get(i)).collect()
+    }
+
+    /// `account` went from a zero to a non-zero balance of `id`.
+    pub fn _add(&mut self, account: Address, id: U256) {
+        if !self.holder_index.get(id).get(account).is_zero() {
+            return;
+        }
+        if self.holders.get(id).is_empty() {
+            self.all_ids.push(id);
+            self.all_ids_index.insert(id, U256::from(self.all_ids.len()));
+        }
+
+        let mut holders = self.holders.setter(id);
+        holders.push(account);
+        let position = U256::from(holders.len());
+        self.holder_index.setter(id).insert(account, position);
+
+        let mut owned = self.owned_ids.setter(account);
+        owned.push(id);
+        let position = U256::from(owned.len());
+        self.owned_index.setter(account).insert(id, position);
+    }
+
+    /// `account`'s balance of `id` dropped to zero. Uses swap-and-pop, so ordering is not stable.
+    pub fn _remove(&mut self, account: Address, id: U256) {
+        let position = self.holder_index.get(id).get(account);
+        if position.is_zero() {
+            return;
+        }
+
+        {
+            let mut holders = self.holders.setter(id);
+            let index = position.to::<usize>() - 1;
+            let last = holders.len() - 1;
+            if index != last {
+                let moved = holders.get(last).unwrap_or_default();
+                if let Some(mut slot) = holders.setter(index) {
+                    slot.set(moved);
+                }
+                self.holder_index.setter(id).insert(moved, position);
+            }
+            holders.pop();
+        }
+        self.holder_index.setter(id).delete(account);
+
+        {
+            let owned_position = self.owned_index.get(account).get(id);
+            let mut owned = self.owned_ids.setter(account);
+            let index = owned_position.to::<usize>() - 1;
+            let last = owned.len() - 1;
+            if index != last {
+                let moved = owned.get(last).unwrap_or_default();
+                if let Some(mut slot) = owned.setter(index) {
+                    slot.set(moved);
+                }
+                self.owned_index.setter(account).insert(moved, owned_position);
+            }
+            owned.pop();
+        }
+        self.owned_index.setter(account).delete(id);
+
+        if self.holders.get(id).is_empty() {
+            let id_position = self.all_ids_index.get(id);
+            let index = id_position.to::<usize>() - 1;
+            let last = self.all_ids.len() - 1;
+            if index != last {
+                let moved = self.all_ids.get(last).unwrap_or_default();
+                if let Some(mut slot) = self.all_ids.setter(index) {
+                    slot.set(moved);
+                }
+                self.all_ids_index.insert(moved, id_position);
+            }
+            self.all_ids.pop();
+            self.all_ids_index.delete(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{
+        alloy_primitives::{address, uint},
+        testing::*,
+    };
+
+    const ALICE: Address = address!("a11ce00000000000000000000000000000000001");
+    const BOB: Address = address!("b0b0000000000000000000000000000000000002");
+    const CAROL: Address = address!("ca20100000000000000000000000000000000003");
+    const ID: U256 = uint!(1_U256);
+    const OTHER_ID: U256 = uint!(2_U256);
+
+    #[test]
+    fn add_tracks_holders_and_ids() {
+        let vm = TestVM::default();
+        let mut sets = Erc1155Enumerable::from(&vm);
+        sets._add(ALICE, ID);
+        sets._add(BOB, ID);
+        sets._add(ALICE, OTHER_ID);
+        // Adding an existing holder is a no-op.
+        sets._add(ALICE, ID);
+
+        assert_eq!(sets.holders(ID), vec![ALICE, BOB]);
+        assert_eq!(sets.ids_of(ALICE), vec![ID, OTHER_ID]);
+        assert_eq!(sets.total_ids(), U256::from(2));
+        assert_eq!(sets.id_at(U256::from(1)).ok(), Some(OTHER_ID));
+        assert!(sets.holder_at(ID, U256::from(2)).is_err());
+    }
+
+    #[test]
+    fn remove_swaps_last_into_place() {
+        let vm = TestVM::default();
+        let mut sets = Erc1155Enumerable::from(&vm);
+        sets._add(ALICE, ID);
+        sets._add(BOB, ID);
+        sets._add(CAROL, ID);
+        sets._remove(ALICE, ID);
+
+        assert_eq!(sets.holders(ID), vec![CAROL, BOB]);
+        assert!(sets.ids_of(ALICE).is_empty());
+        // Removing an absent holder is a no-op.
+        sets._remove(ALICE, ID);
+        assert_eq!(sets.holder_count(ID), U256::from(2));
+    }
+
+    #[test]
+    fn last_holder_removal_drops_id_and_readd_restores_it() {
+        let vm = TestVM::default();
+        let mut sets = Erc1155Enumerable::from(&vm);
+        sets._add(ALICE, ID);
+        sets._add(ALICE, OTHER_ID);
+        sets._remove(ALICE, ID);
+
+        assert_eq!(sets.total_ids(), U256::from(1));
+        assert_eq!(sets.id_at(U256::ZERO).ok(), Some(OTHER_ID));
+        assert_eq!(sets.ids_of(ALICE), vec![OTHER_ID]);
+
+        sets._add(ALICE, ID);
+        assert_eq!(sets.total_ids(), U256::from(2));
+        assert_eq!(sets.holders(ID), vec![ALICE]);
+        assert_eq!(sets.ids_of(ALICE), vec![OTHER_ID, ID]);
+
+        sets._remove(ALICE, ID);
+        sets._remove(ALICE, OTHER_ID);
+        assert_eq!(sets.total_ids(), U256::ZERO);
+        assert_eq!(sets.id_count_of(ALICE), U256::ZERO);
+    }
+}
@@ -1,15 +1,32 @@
 use alloc::vec::Vec;
 use stylus_sdk::{
-    alloy_primitives::{Address, U256},
-    alloy_sol_types::{sol, SolError},
-    evm, msg,
+    alloy_primitives::{b256, fixed_bytes, Address, FixedBytes, B256, U256},
+    alloy_sol_types::{sol, Panic, PanicKind},
+    call::Call,
     prelude::*,
+    stylus_core::log,
 };
 
+use crate::{enumerable::Erc1155Enumerable, interfaces::IERC1155Receiver};
+#[cfg(feature = "revert-strings")]
+use crate::revert;
+
+/// `keccak256("MINTER_ROLE")`
+pub const MINTER_ROLE: B256 =
+    b256!("9f2df0fed2c77648de5860a4cc508cd0818c85b8b8a1ab4ceeef8d981c8956a6");
+
+/// `IERC1155Receiver.onERC1155Received.selector`
+pub const ON_ERC1155_RECEIVED: FixedBytes<4> = fixed_bytes!("f23a6e61");
+/// `IERC1155Receiver.onERC1155BatchReceived.selector`
+pub const ON_ERC1155_BATCH_RECEIVED: FixedBytes<4> = fixed_bytes!("bc197c81");
+
 sol_storage! {
     pub struct Erc1155 {
         mapping(uint256 => mapping(address => uint256)) balances;
         mapping(address => mapping(address => bool)) operator_approvals;
+        Erc1155Enumerable enumerable;
+        mapping(uint256 => uint256) total_supply;
+        uint256 total_supply_all;
     }
 }
 
@@ -19,6 +36,7 @@ sol! {
     event ApprovalForAll(address indexed account, address indexed operator, bool approved);
 
     error ERC1155InsufficientBalance(address sender, uint256 balance, uint256 needed, uint256 id);
+    error ERC1155InvalidSender(address sender);
     error ERC1155InvalidReceiver(address receiver);
     error ERC1155InvalidApprover(address approver);
     error ERC1155InvalidOperator(address operator);
@@ -26,34 +44,59 @@ sol! {
     error ERC1155MissingApprovalForAll(address operator, address owner);
 }
 
+/// Without `revert-strings` the derive supplies the ABI encoding; with it, errors go
+/// through `revert::encode` like every other module's.
+#[cfg_attr(not(feature = "revert-strings"), derive(SolidityError))]
 pub enum Erc1155Error {
     InsufficientBalance(ERC1155InsufficientBalance),
+    InvalidSender(ERC1155InvalidSender),
     InvalidReceiver(ERC1155InvalidReceiver),
     InvalidApprover(ERC1155InvalidApprover),
     InvalidOperator(ERC1155InvalidOperator),
     InvalidArrayLength(ERC1155InvalidArrayLength),
     MissingApprovalForAll(ERC1155MissingApprovalForAll),
+    Overflow(Panic),
 }
 
+#[cfg(feature = "revert-strings")]
 impl From<Erc1155Error> for Vec<u8> {
     fn from(error: Erc1155Error) -> Self {
         match error {
-            Erc1155Error::InsufficientBalance(e) => e.abi_encode(),
-            Erc1155Error::InvalidReceiver(e) => e.abi_encode(),
-            Erc1155Error::InvalidApprover(e) => e.abi_encode(),
-            Erc1155Error::InvalidOperator(e) => e.abi_encode(),
-            Erc1155Error::InvalidArrayLength(e) => e.abi_encode(),
-            Erc1155Error::MissingApprovalForAll(e) => e.abi_encode(),
+            Erc1155Error::InsufficientBalance(e) => revert::encode(e),
+            Erc1155Error::InvalidSender(e) => revert::encode(e),
+            Erc1155Error::InvalidReceiver(e) => revert::encode(e),
+            Erc1155Error::InvalidApprover(e) => revert::encode(e),
+            Erc1155Error::InvalidOperator(e) => revert::encode(e),
+            Erc1155Error::InvalidArrayLength(e) => revert::encode(e),
+            Erc1155Error::MissingApprovalForAll(e) => revert::encode(e),
+            Erc1155Error::Overflow(e) => revert::encode(e),
         }
     }
 }
 
+/// `Result` for the token core; other error types can be named explicitly.
+pub type Result<T, E = Erc1155Error> = core::result::Result<T, E>;
+
 impl Erc1155 {
     pub fn balance_of(&self, account: Address, id: U256) -> U256 {
         self.balances.get(id).get(account)
     }
 
-    pub fn balance_of_batch(&self, accounts: Vec<Address>, ids: Vec<U256>) -> Result<Vec<U256>, Erc1155Error> {
+    /// Units of `id` minted and not burned.
+    pub fn total_supply(&self, id: U256) -> U256 {
+        self.total_supply.get(id)
+    }
+
+    /// Sum of [`Self::total_supply`] over every id.
+    pub fn total_supply_all(&self) -> U256 {
+        self.total_supply_all.get()
+    }
+
+    pub fn exists(&self, id: U256) -> bool {
+        !self.total_supply(id).is_zero()
+    }
+
+    pub fn balance_of_batch(&self, accounts: Vec<Address>, ids: Vec<U256>) -> Result<Vec<U256>> {
         if accounts.len() != ids.len() {
             return Err(Erc1155Error::InvalidArrayLength(ERC1155InvalidArrayLength {
                 idsLength: U256::from(ids.len()),
@@ -63,16 +106,23 @@ impl Erc1155 {
         Ok(accounts.iter().zip(ids.iter()).map(|(acc, id)| self.balance_of(*acc, *id)).collect())
     }
 
-    pub fn set_approval_for_all(&mut self, operator: Address, approved: bool) -> Result<(), Erc1155Error> {
-        let owner = msg::sender();
+    pub fn set_approval_for_all(&mut self, operator: Address, approved: bool) -> Result<()> {
+        self._set_approval_for_all(self.vm().msg_sender(), operator, approved)
+    }
+
+    pub fn _set_approval_for_all(&mut self, owner: Address, operator: Address, approved: bool) -> Result<()> {
         if owner == operator {
             return Err(Erc1155Error::InvalidOperator(ERC1155InvalidOperator { operator }));
         }
 
         let mut owner_approvals = self.operator_approvals.setter(owner);
-        owner_approvals.insert(operator, approved);
+        if approved {
+            owner_approvals.insert(operator, true);
+        } else {
+            owner_approvals.delete(operator);
+        }
 
-        evm::log(ApprovalForAll {
+        log(self.vm(), ApprovalForAll {
             account: owner,
             operator,
             approved,
@@ -85,119 +135,198 @@ impl Erc1155 {
         self.operator_approvals.get(account).get(operator)
     }
 
-    pub fn safe_transfer_from(
-        &mut self,
-        from: Address,
-        to: Address,
-        id: U256,
-        value: U256,
-        _data: Vec<u8>,
-    ) -> Result<(), Erc1155Error> {
-        let operator = msg::sender();
+    pub fn enumerable(&self) -> &Erc1155Enumerable {
+        &self.enumerable
+    }
+
+    /// Authorized transfer without the receiver acceptance check, which has to be made
+    /// from the top-level storage: see [`Erc1155Hooks::_safe_transfer`].
+    pub fn transfer_from(&mut self, from: Address, to: Address, id: U256, value: U256) -> Result<()> {
+        self._authorize_transfer(self.vm().msg_sender(), from, to)?;
+        self._update(from, to, vec![id], vec![value])
+    }
+
+    /// Batch form of [`Erc1155::transfer_from`].
+    pub fn batch_transfer_from(&mut self, from: Address, to: Address, ids: Vec<U256>, values: Vec<U256>) -> Result<()> {
+        self._authorize_transfer(self.vm().msg_sender(), from, to)?;
+        self._update(from, to, ids, values)
+    }
+
+    /// Operator approval and non-zero receiver, the checks a transfer makes before
+    /// touching balances.
+    pub fn _authorize_transfer(&self, operator: Address, from: Address, to: Address) -> Result<()> {
         if from != operator && !self.is_approved_for_all(from, operator) {
             return Err(Erc1155Error::MissingApprovalForAll(ERC1155MissingApprovalForAll {
                 operator,
                 owner: from,
             }));
         }
-
         if to.is_zero() {
             return Err(Erc1155Error::InvalidReceiver(ERC1155InvalidReceiver { receiver: Address::ZERO }));
         }
-
-        self._update_single(from, to, id, value)?;
-
         Ok(())
     }
 
-    pub fn safe_batch_transfer_from(
-        &mut self,
+    /// Every check `safeBatchTransferFrom` would make for `operator`, without moving
+    /// anything. Repeated ids are checked against their combined value.
+    pub fn check_transfer(
+        &self,
+        operator: Address,
         from: Address,
         to: Address,
-        ids: Vec<U256>,
-        values: Vec<U256>,
-        _data: Vec<u8>,
-    ) -> Result<(), Erc1155Error> {
-        let operator = msg::sender();
-        if from != operator && !self.is_approved_for_all(from, operator) {
-            return Err(Erc1155Error::MissingApprovalForAll(ERC1155MissingApprovalForAll {
-                operator,
-                owner: from,
-            }));
+        ids: &[U256],
+        values: &[U256],
+    ) -> Result<()> {
+        self._authorize_transfer(operator, from, to)?;
+        check_lengths(ids, values)?;
+
+        let mut needed: Vec<(U256, U256)> = Vec::new();
+        for (&id, &value) in ids.iter().zip(values) {
+            // A self-transfer credits each leg back before the next one is debited.
+            let total = match needed.iter_mut().find(|(seen, _)| *seen == id && from != to) {
+                Some((_, total)) => {
+                    *total = total.saturating_add(value);
+                    *total
+                }
+                None => {
+                    needed.push((id, value));
+                    value
+                }
+            };
+            let balance = self.balance_of(from, id);
+            if balance < total {
+                return Err(Erc1155Error::InsufficientBalance(ERC1155InsufficientBalance {
+                    sender: from,
+                    balance,
+                    needed: total,
+                    id,
+                }));
+            }
         }
+        Ok(())
+    }
 
-        if to.is_zero() {
-            return Err(Erc1155Error::InvalidReceiver(ERC1155InvalidReceiver { receiver: Address::ZERO }));
+    /// The balance-changing core: moves each `values[i]` of `ids[i]` and logs
+    /// `TransferSingle` for a single id and `TransferBatch` otherwise. The receiver
+    /// acceptance check is left to [`Erc1155Hooks::_update`].
+    pub fn _update(&mut self, from: Address, to: Address, ids: Vec<U256>, values: Vec<U256>) -> Result<()> {
+        check_lengths(&ids, &values)?;
+        let operator = self.vm().msg_sender();
+        // Consecutive repeats of an id share one balance accessor and one supply write.
+        let mut moved = U256::ZERO;
+        let mut start = 0;
+        while start < ids.len() {
+            let id = ids[start];
+            let end = ids[start..].iter().position(|&next| next != id).map_or(ids.len(), |n| start + n);
+            let run = self._apply_run(from, to, id, &values[start..end])?;
+            moved = moved.checked_add(run).ok_or_else(overflow)?;
+            start = end;
         }
+        self._apply_supply_all(from, to, moved)?;
 
-        if ids.len() != values.len() {
+        self._log_transfer(operator, from, to, ids, values);
+        Ok(())
+    }
+
+    /// Mints `amounts[i]` of `id` to each `recipients[i]`, one `TransferSingle` each.
+    pub fn _mint_to_many(&mut self, recipients: Vec<Address>, id: U256, amounts: Vec<U256>) -> Result<()> {
+        if recipients.len() != amounts.len() {
             return Err(Erc1155Error::InvalidArrayLength(ERC1155InvalidArrayLength {
-                idsLength: U256::from(ids.len()),
-                valuesLength: U256::from(values.len()),
+                idsLength: U256::from(recipients.len()),
+                valuesLength: U256::from(amounts.len()),
             }));
         }
+        check_recipients(&recipients)?;
+        self._apply_many(Address::ZERO, &recipients, id, &amounts)?;
+        let operator = self.vm().msg_sender();
+        for (&to, &value) in recipients.iter().zip(amounts.iter()) {
+            log(self.vm(), TransferSingle {
+                operator,
+                from: Address::ZERO,
+                to,
+                id,
+                value,
+            });
+        }
+        Ok(())
+    }
 
-        self._update_batch(from, to, ids, values)?;
-
+    /// Sends the same `ids`/`values` from `from` to every recipient, logged per
+    /// recipient as [`Erc1155::_update`] would. Authorization is the caller's job.
+    pub fn _air_transfer(&mut self, from: Address, recipients: Vec<Address>, ids: Vec<U256>, values: Vec<U256>) -> Result<()> {
+        check_lengths(&ids, &values)?;
+        check_recipients(&recipients)?;
+        for (&id, &value) in ids.iter().zip(values.iter()) {
+            self._apply_many(from, &recipients, id, &vec![value; recipients.len()])?;
+        }
+        let operator = self.vm().msg_sender();
+        for &to in &recipients {
+            self._log_transfer(operator, from, to, ids.clone(), values.clone());
+        }
         Ok(())
     }
 
-    pub fn _update_single(
-        &mut self,
-        from: Address,
-        to: Address,
-        id: U256,
-        value: U256,
-    ) -> Result<(), Erc1155Error> {
-        if !from.is_zero() {
-            let mut balance_map = self.balances.setter(id);
-            let mut from_balance_setter = balance_map.setter(from);
-            let from_balance = from_balance_setter.get();
-            if from_balance < value {
+    /// Balance writes for one id fanned out to many accounts: `from` is debited once for
+    /// the total and the id's balance map is fetched once for all the credits.
+    fn _apply_many(&mut self, from: Address, recipients: &[Address], id: U256, amounts: &[U256]) -> Result<()> {
+        let total = amounts
+            .iter()
+            .try_fold(U256::ZERO, |acc, a| acc.checked_add(*a))
+            .ok_or_else(overflow)?;
+        let mut balance_map = self.balances.setter(id);
+        if from.is_zero() {
+            let supply = self.total_supply.get(id).checked_add(total).ok_or_else(overflow)?;
+            self.total_supply.insert(id, supply);
+            let supply_all = self.total_supply_all.get().checked_add(total).ok_or_else(overflow)?;
+            self.total_supply_all.set(supply_all);
+        } else {
+            let from_balance = balance_map.get(from);
+            if from_balance < total {
                 return Err(Erc1155Error::InsufficientBalance(ERC1155InsufficientBalance {
                     sender: from,
                     balance: from_balance,
-                    needed: value,
+                    needed: total,
                     id,
                 }));
             }
-            from_balance_setter.set(from_balance - value);
+            if from_balance == total {
+                balance_map.delete(from);
+                if cfg!(feature = "enumerable") && !total.is_zero() {
+                    self.enumerable._remove(from, id);
+                }
+            } else {
+                balance_map.insert(from, from_balance - total);
+            }
         }
 
-        if !to.is_zero() {
-            let mut balance_map = self.balances.setter(id);
-            let mut to_balance_setter = balance_map.setter(to);
-            let to_balance = to_balance_setter.get();
-            to_balance_setter.set(to_balance + value);
-        }
-
-        evm::log(TransferSingle {
-            operator: msg::sender(),
-            from,
-            to,
-            id,
-            value,
-        });
-
+        for (&to, &value) in recipients.iter().zip(amounts) {
+            if value.is_zero() {
+                continue;
+            }
+            let to_balance = balance_map.get(to);
+            balance_map.insert(to, to_balance.checked_add(value).ok_or_else(overflow)?);
+            if cfg!(feature = "enumerable") && to_balance.is_zero() {
+                self.enumerable._add(to, id);
+            }
+        }
         Ok(())
     }
 
-    pub fn _update_batch(
-        &mut self,
-        from: Address,
-        to: Address,
-        ids: Vec<U256>,
-        values: Vec<U256>,
-    ) -> Result<(), Erc1155Error> {
-        let operator = msg::sender();
-        for i in 0..ids.len() {
-            let id = ids[i];
-            let value = values[i];
+    /// One balance change outside `_update`, for callers that log on their own.
+    pub fn _apply_single(&mut self, from: Address, to: Address, id: U256, value: U256) -> Result<()> {
+        let moved = self._apply_run(from, to, id, &[value])?;
+        self._apply_supply_all(from, to, moved)
+    }
 
+    /// Moves every value in `values`, all of the same `id`, through one balance accessor
+    /// and one per-id supply write. Returns the sum moved. Balances and supply revert on
+    /// overflow as Solidity's checked arithmetic would.
+    fn _apply_run(&mut self, from: Address, to: Address, id: U256, values: &[U256]) -> Result<U256> {
+        let mut balance_map = self.balances.setter(id);
+        let mut moved = U256::ZERO;
+        for &value in values {
             if !from.is_zero() {
-                let mut balance_map = self.balances.setter(id);
-                let mut from_balance_setter = balance_map.setter(from);
-                let from_balance = from_balance_setter.get();
+                let from_balance = balance_map.get(from);
                 if from_balance < value {
                     return Err(Erc1155Error::InsufficientBalance(ERC1155InsufficientBalance {
                         sender: from,
@@ -206,26 +335,587 @@ impl Erc1155 {
                         id,
                     }));
                 }
-                from_balance_setter.set(from_balance - value);
+                let remaining = from_balance - value;
+                if remaining.is_zero() {
+                    balance_map.delete(from);
+                    if cfg!(feature = "enumerable") && !value.is_zero() {
+                        self.enumerable._remove(from, id);
+                    }
+                } else {
+                    balance_map.insert(from, remaining);
+                }
             }
-
-            if !to.is_zero() {
-                let mut balance_map = self.balances.setter(id);
-                let mut to_balance_setter = balance_map.setter(to);
-                let to_balance = to_balance_setter.get();
-                to_balance_setter.set(to_balance + value);
+            if !to.is_zero() && !value.is_zero() {
+                let to_balance = balance_map.get(to);
+                balance_map.insert(to, to_balance.checked_add(value).ok_or_else(overflow)?);
+                if cfg!(feature = "enumerable") && to_balance.is_zero() {
+                    self.enumerable._add(to, id);
+                }
             }
+            moved = moved.checked_add(value).ok_or_else(overflow)?;
         }
 
-        evm::log(TransferBatch {
-            operator,
-            from,
-            to,
-            ids,
-            values,
-        });
+        if from.is_zero() {
+            let supply = self.total_supply.get(id).checked_add(moved).ok_or_else(overflow)?;
+            self.total_supply.insert(id, supply);
+        }
+        if to.is_zero() {
+            self.total_supply.insert(id, self.total_supply.get(id) - moved);
+        }
+        Ok(moved)
+    }
 
+    /// Mints add `moved` to the aggregate supply and burns subtract it.
+    fn _apply_supply_all(&mut self, from: Address, to: Address, moved: U256) -> Result<()> {
+        if from.is_zero() {
+            let supply = self.total_supply_all.get().checked_add(moved).ok_or_else(overflow)?;
+            self.total_supply_all.set(supply);
+        }
+        if to.is_zero() {
+            self.total_supply_all.set(self.total_supply_all.get() - moved);
+        }
+        Ok(())
+    }
+
+    /// `TransferSingle` for one id, `TransferBatch` otherwise.
+    fn _log_transfer(&self, operator: Address, from: Address, to: Address, ids: Vec<U256>, values: Vec<U256>) {
+        if ids.len() == 1 {
+            log(self.vm(), TransferSingle {
+                operator,
+                from,
+                to,
+                id: ids[0],
+                value: values[0],
+            });
+        } else {
+            log(self.vm(), TransferBatch {
+                operator,
+                from,
+                to,
+                ids,
+                values,
+            });
+        }
+    }
+}
+
+/// Extension points around every balance change. A contract embedding [`Erc1155`]
+/// implements this and routes transfers, mints and burns through the provided methods,
+/// so its extensions (pausing, supply rules, listeners, ...) layer on in the two hooks
+/// instead of patching the token logic.
+///
+/// The implementor is the contract's top-level storage, which is what the receiver
+/// acceptance calls are made from.
+pub trait Erc1155Hooks: TopLevelStorage + Sized {
+    fn erc1155_mut(&mut self) -> &mut Erc1155;
+
+    /// Runs before any balance moves; an error aborts the update.
+    fn before_token_transfer(&mut self, _from: Address, _to: Address, _ids: &[U256], _values: &[U256]) -> Result<(), Vec<u8>> {
+        Ok(())
+    }
+
+    /// Runs after balances, events and the receiver check.
+    fn after_token_transfer(&mut self, _from: Address, _to: Address, _ids: &[U256], _values: &[U256]) -> Result<(), Vec<u8>> {
         Ok(())
     }
 
+    /// [`Erc1155::_update`] wrapped in the hooks, with the receiver acceptance check
+    /// run with `data` unless this is a burn.
+    fn _update(&mut self, from: Address, to: Address, ids: Vec<U256>, values: Vec<U256>, data: Vec<u8>) -> Result<(), Vec<u8>> {
+        self.before_token_transfer(from, to, &ids, &values)?;
+        self.erc1155_mut()._update(from, to, ids.clone(), values.clone())?;
+        if !to.is_zero() {
+            self._check_on_received(from, to, ids.clone(), values.clone(), data)?;
+        }
+        self.after_token_transfer(from, to, &ids, &values)
+    }
+
+    /// [`Erc1155::_mint_to_many`] with the hooks and the acceptance check run once per
+    /// recipient.
+    fn _mint_to_many(&mut self, recipients: Vec<Address>, id: U256, amounts: Vec<U256>, data: Vec<u8>) -> Result<(), Vec<u8>> {
+        for (&to, &value) in recipients.iter().zip(amounts.iter()) {
+            self.before_token_transfer(Address::ZERO, to, &[id], &[value])?;
+        }
+        self.erc1155_mut()._mint_to_many(recipients.clone(), id, amounts.clone())?;
+        for (&to, &value) in recipients.iter().zip(amounts.iter()) {
+            self._check_on_received(Address::ZERO, to, vec![id], vec![value], data.clone())?;
+            self.after_token_transfer(Address::ZERO, to, &[id], &[value])?;
+        }
+        Ok(())
+    }
+
+    /// [`Erc1155::_air_transfer`] with the hooks and the acceptance check run once per
+    /// recipient.
+    fn _air_transfer(
+        &mut self,
+        from: Address,
+        recipients: Vec<Address>,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Vec<u8>,
+    ) -> Result<(), Vec<u8>> {
+        for &to in &recipients {
+            self.before_token_transfer(from, to, &ids, &values)?;
+        }
+        self.erc1155_mut()._air_transfer(from, recipients.clone(), ids.clone(), values.clone())?;
+        for &to in &recipients {
+            self._check_on_received(from, to, ids.clone(), values.clone(), data.clone())?;
+            self.after_token_transfer(from, to, &ids, &values)?;
+        }
+        Ok(())
+    }
+
+    /// EOAs and this contract itself (escrow, treasury) always accept; any other
+    /// recipient with code must return `onERC1155Received`'s selector for a single id
+    /// and `onERC1155BatchReceived`'s otherwise, matching the transfer log.
+    fn _check_on_received(&mut self, from: Address, to: Address, ids: Vec<U256>, values: Vec<U256>, data: Vec<u8>) -> Result<(), Vec<u8>> {
+        let (operator, code_size, this) = {
+            let vm = self.erc1155_mut().vm();
+            (vm.msg_sender(), vm.code_size(to), vm.contract_address())
+        };
+        if code_size == 0 || to == this {
+            return Ok(());
+        }
+        let receiver = IERC1155Receiver::new(to);
+        let accepted = if ids.len() == 1 {
+            receiver
+                .on_erc_1155_received(Call::new_in(self), operator, from, ids[0], values[0], data.into())
+                .is_ok_and(|selector| selector == ON_ERC1155_RECEIVED)
+        } else {
+            receiver
+                .on_erc_1155_batch_received(Call::new_in(self), operator, from, ids, values, data.into())
+                .is_ok_and(|selector| selector == ON_ERC1155_BATCH_RECEIVED)
+        };
+        if !accepted {
+            return Err(Erc1155Error::InvalidReceiver(ERC1155InvalidReceiver { receiver: to }).into());
+        }
+        Ok(())
+    }
+
+    /// `safeTransferFrom` / `safeBatchTransferFrom` with the caller as operator.
+    fn _safe_transfer(&mut self, from: Address, to: Address, ids: Vec<U256>, values: Vec<U256>, data: Vec<u8>) -> Result<(), Vec<u8>> {
+        let operator = self.erc1155_mut().vm().msg_sender();
+        self.erc1155_mut()._authorize_transfer(operator, from, to)?;
+        self._update(from, to, ids, values, data)
+    }
+
+    fn _mint(&mut self, to: Address, id: U256, value: U256, data: Vec<u8>) -> Result<(), Vec<u8>> {
+        self._mint_batch(to, vec![id], vec![value], data)
+    }
+
+    fn _mint_batch(&mut self, to: Address, ids: Vec<U256>, values: Vec<U256>, data: Vec<u8>) -> Result<(), Vec<u8>> {
+        if to.is_zero() {
+            return Err(Erc1155Error::InvalidReceiver(ERC1155InvalidReceiver { receiver: Address::ZERO }).into());
+        }
+        self._update(Address::ZERO, to, ids, values, data)
+    }
+
+    fn _burn(&mut self, from: Address, id: U256, value: U256) -> Result<(), Vec<u8>> {
+        self._burn_batch(from, vec![id], vec![value])
+    }
+
+    fn _burn_batch(&mut self, from: Address, ids: Vec<U256>, values: Vec<U256>) -> Result<(), Vec<u8>> {
+        if from.is_zero() {
+            return Err(Erc1155Error::InvalidSender(ERC1155InvalidSender { sender: Address::ZERO }).into());
+        }
+        self._update(from, Address::ZERO, ids, values, Vec::new())
+    }
+}
+
+/// The `Panic(0x11)` Solidity raises on checked-arithmetic overflow.
+fn overflow() -> Erc1155Error {
+    Erc1155Error::Overflow(Panic::from(PanicKind::UnderOverflow))
+}
+
+fn check_recipients(recipients: &[Address]) -> Result<()> {
+    if recipients.iter().any(|to| to.is_zero()) {
+        return Err(Erc1155Error::InvalidReceiver(ERC1155InvalidReceiver { receiver: Address::ZERO }));
+    }
+    Ok(())
+}
+
+fn check_lengths(ids: &[U256], values: &[U256]) -> Result<()> {
+    if ids.len() != values.len() {
+        return Err(Erc1155Error::InvalidArrayLength(ERC1155InvalidArrayLength {
+            idsLength: U256::from(ids.len()),
+            valuesLength: U256::from(values.len()),
+        }));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{
+        alloy_primitives::{address, uint},
+        alloy_sol_types::SolEvent,
+        testing::*,
+    };
+
+    const ALICE: Address = address!("a11ce00000000000000000000000000000000001");
+    const BOB: Address = address!("b0b0000000000000000000000000000000000002");
+    const CAROL: Address = address!("ca20100000000000000000000000000000000003");
+    const ID: U256 = uint!(1_U256);
+    const OTHER_ID: U256 = uint!(2_U256);
+
+    fn setup(sender: Address) -> (TestVM, Erc1155) {
+        let vm = TestVM::default();
+        vm.set_sender(sender);
+        let token = Erc1155::from(&vm);
+        (vm, token)
+    }
+
+    fn mint(token: &mut Erc1155, to: Address, id: U256, value: U256) {
+        assert!(token._update(Address::ZERO, to, vec![id], vec![value]).is_ok());
+    }
+
+    #[test]
+    fn mint_credits_balance_and_supply() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        mint(&mut token, BOB, OTHER_ID, U256::from(5));
+
+        assert_eq!(token.balance_of(ALICE, ID), U256::from(10));
+        assert_eq!(token.total_supply(ID), U256::from(10));
+        assert_eq!(token.total_supply_all(), U256::from(15));
+        assert!(token.exists(OTHER_ID));
+        assert!(!token.exists(uint!(3_U256)));
+    }
+
+    #[test]
+    fn burn_debits_balance_and_supply() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        assert!(token._update(ALICE, Address::ZERO, vec![ID], vec![U256::from(4)]).is_ok());
+
+        assert_eq!(token.balance_of(ALICE, ID), U256::from(6));
+        assert_eq!(token.total_supply(ID), U256::from(6));
+        assert_eq!(token.total_supply_all(), U256::from(6));
+    }
+
+    #[test]
+    fn owner_transfers_single() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        assert!(token.transfer_from(ALICE, BOB, ID, U256::from(3)).is_ok());
+
+        assert_eq!(token.balance_of(ALICE, ID), U256::from(7));
+        assert_eq!(token.balance_of(BOB, ID), U256::from(3));
+        assert_eq!(token.total_supply(ID), U256::from(10));
+    }
+
+    #[test]
+    fn owner_transfers_batch() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        mint(&mut token, ALICE, OTHER_ID, U256::from(20));
+        let result = token.batch_transfer_from(
+            ALICE,
+            BOB,
+            vec![ID, OTHER_ID],
+            vec![U256::from(1), U256::from(2)],
+        );
+        assert!(result.is_ok());
+
+        let balances = token.balance_of_batch(vec![ALICE, BOB, ALICE, BOB], vec![ID, ID, OTHER_ID, OTHER_ID]);
+        assert_eq!(
+            balances.ok(),
+            Some(vec![U256::from(9), U256::from(1), U256::from(18), U256::from(2)])
+        );
+    }
+
+    #[test]
+    fn self_transfer_keeps_balance() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        assert!(token.transfer_from(ALICE, ALICE, ID, U256::from(10)).is_ok());
+        assert_eq!(token.balance_of(ALICE, ID), U256::from(10));
+    }
+
+    #[test]
+    fn unapproved_operator_cannot_transfer() {
+        let (_vm, mut token) = setup(BOB);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        let result = token.transfer_from(ALICE, BOB, ID, U256::from(1));
+        assert!(matches!(
+            result,
+            Err(Erc1155Error::MissingApprovalForAll(e)) if e.operator == BOB && e.owner == ALICE
+        ));
+        assert_eq!(token.balance_of(ALICE, ID), U256::from(10));
+    }
+
+    #[test]
+    fn approved_operator_transfers() {
+        let (vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        assert!(token.set_approval_for_all(BOB, true).is_ok());
+        assert!(token.is_approved_for_all(ALICE, BOB));
+
+        vm.set_sender(BOB);
+        assert!(token.transfer_from(ALICE, CAROL, ID, U256::from(4)).is_ok());
+        assert_eq!(token.balance_of(CAROL, ID), U256::from(4));
+    }
+
+    #[test]
+    fn revoked_operator_cannot_transfer() {
+        let (vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        assert!(token.set_approval_for_all(BOB, true).is_ok());
+        assert!(token.set_approval_for_all(BOB, false).is_ok());
+        assert!(!token.is_approved_for_all(ALICE, BOB));
+
+        vm.set_sender(BOB);
+        let result = token.transfer_from(ALICE, CAROL, ID, U256::from(1));
+        assert!(matches!(result, Err(Erc1155Error::MissingApprovalForAll(_))));
+    }
+
+    #[test]
+    fn approving_self_reverts() {
+        let (_vm, mut token) = setup(ALICE);
+        let result = token.set_approval_for_all(ALICE, true);
+        assert!(matches!(result, Err(Erc1155Error::InvalidOperator(e)) if e.operator == ALICE));
+    }
+
+    #[test]
+    fn insufficient_balance_reverts() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(2));
+        let result = token.transfer_from(ALICE, BOB, ID, U256::from(3));
+        assert!(matches!(
+            result,
+            Err(Erc1155Error::InsufficientBalance(e))
+                if e.sender == ALICE && e.balance == U256::from(2) && e.needed == U256::from(3) && e.id == ID
+        ));
+    }
+
+    #[test]
+    fn insufficient_balance_in_batch_reverts() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(5));
+        let result = token.batch_transfer_from(
+            ALICE,
+            BOB,
+            vec![ID, OTHER_ID],
+            vec![U256::from(1), U256::from(1)],
+        );
+        assert!(matches!(result, Err(Erc1155Error::InsufficientBalance(e)) if e.id == OTHER_ID));
+    }
+
+    #[test]
+    fn transfer_to_zero_reverts() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(1));
+        let result = token.transfer_from(ALICE, Address::ZERO, ID, U256::from(1));
+        assert!(matches!(result, Err(Erc1155Error::InvalidReceiver(e)) if e.receiver.is_zero()));
+    }
+
+    #[test]
+    fn batch_length_mismatch_reverts() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(5));
+        let result = token.batch_transfer_from(ALICE, BOB, vec![ID, OTHER_ID], vec![U256::from(1)]);
+        assert!(matches!(
+            result,
+            Err(Erc1155Error::InvalidArrayLength(e))
+                if e.idsLength == U256::from(2) && e.valuesLength == U256::from(1)
+        ));
+
+        let result = token.balance_of_batch(vec![ALICE], vec![ID, OTHER_ID]);
+        assert!(matches!(result, Err(Erc1155Error::InvalidArrayLength(_))));
+    }
+
+    #[test]
+    fn repeated_ids_in_batch_are_applied_in_order() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(5));
+        mint(&mut token, ALICE, OTHER_ID, U256::from(5));
+        let ids = vec![ID, ID, OTHER_ID, ID];
+        let values = vec![U256::from(2), U256::from(2), U256::from(5), U256::from(1)];
+        assert!(token.batch_transfer_from(ALICE, BOB, ids.clone(), values.clone()).is_ok());
+        assert_eq!(token.balance_of(ALICE, ID), U256::ZERO);
+        assert_eq!(token.balance_of(BOB, ID), U256::from(5));
+        assert_eq!(token.balance_of(BOB, OTHER_ID), U256::from(5));
+
+        let result = token.batch_transfer_from(BOB, ALICE, vec![ID, ID], vec![U256::from(3), U256::from(3)]);
+        assert!(matches!(
+            result,
+            Err(Erc1155Error::InsufficientBalance(e)) if e.balance == U256::from(2) && e.needed == U256::from(3)
+        ));
+    }
+
+    #[test]
+    fn mint_overflow_reverts() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::MAX);
+        let result = token._update(Address::ZERO, BOB, vec![ID], vec![U256::from(1)]);
+        assert!(matches!(result, Err(Erc1155Error::Overflow(e)) if e.code == U256::from(0x11)));
+
+        let result = token._update(Address::ZERO, BOB, vec![OTHER_ID, OTHER_ID], vec![U256::MAX, U256::from(1)]);
+        assert!(matches!(result, Err(Erc1155Error::Overflow(_))));
+        assert_eq!(token.total_supply(ID), U256::MAX);
+    }
+
+    #[test]
+    fn check_transfer_sums_repeated_ids() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(5));
+        let values = [U256::from(3), U256::from(3)];
+
+        let result = token.check_transfer(ALICE, ALICE, BOB, &[ID, ID], &values);
+        assert!(matches!(result, Err(Erc1155Error::InsufficientBalance(e)) if e.needed == U256::from(6)));
+        // A self-transfer never needs more than each leg.
+        assert!(token.check_transfer(ALICE, ALICE, ALICE, &[ID, ID], &values).is_ok());
+        assert!(token.check_transfer(ALICE, ALICE, BOB, &[ID], &values[..1]).is_ok());
+    }
+
+    #[test]
+    fn single_transfer_logs_transfer_single() {
+        let (vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        assert!(token.transfer_from(ALICE, BOB, ID, U256::from(3)).is_ok());
+
+        let logs = vm.get_emitted_logs();
+        let (topics, data) = logs.last().expect("no log emitted");
+        assert_eq!(topics[0], TransferSingle::SIGNATURE_HASH);
+        let event = TransferSingle::decode_raw_log(topics.clone(), data, true).expect("undecodable TransferSingle");
+        assert_eq!(event.operator, ALICE);
+        assert_eq!(event.from, ALICE);
+        assert_eq!(event.to, BOB);
+        assert_eq!(event.id, ID);
+        assert_eq!(event.value, U256::from(3));
+    }
+
+    #[test]
+    fn batch_transfer_logs_transfer_batch() {
+        let (vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        mint(&mut token, ALICE, OTHER_ID, U256::from(10));
+        let values = vec![U256::from(1), U256::from(2)];
+        assert!(token
+            .batch_transfer_from(ALICE, BOB, vec![ID, OTHER_ID], values.clone())
+            .is_ok());
+
+        let logs = vm.get_emitted_logs();
+        let (topics, data) = logs.last().expect("no log emitted");
+        let event = TransferBatch::decode_raw_log(topics.clone(), data, true).expect("undecodable TransferBatch");
+        assert_eq!(event.from, ALICE);
+        assert_eq!(event.to, BOB);
+        assert_eq!(event.ids, vec![ID, OTHER_ID]);
+        assert_eq!(event.values, values);
+    }
+
+    #[test]
+    fn mint_logs_from_zero() {
+        let (vm, mut token) = setup(ALICE);
+        mint(&mut token, BOB, ID, U256::from(7));
+
+        let logs = vm.get_emitted_logs();
+        let (topics, data) = logs.last().expect("no log emitted");
+        let event = TransferSingle::decode_raw_log(topics.clone(), data, true).expect("undecodable TransferSingle");
+        assert!(event.from.is_zero());
+        assert_eq!(event.to, BOB);
+        assert_eq!(event.value, U256::from(7));
+    }
+
+    #[test]
+    fn approval_logs_approval_for_all() {
+        let (vm, mut token) = setup(ALICE);
+        assert!(token.set_approval_for_all(BOB, true).is_ok());
+
+        let logs = vm.get_emitted_logs();
+        let (topics, data) = logs.last().expect("no log emitted");
+        let event = ApprovalForAll::decode_raw_log(topics.clone(), data, true).expect("undecodable ApprovalForAll");
+        assert_eq!(event.account, ALICE);
+        assert_eq!(event.operator, BOB);
+        assert!(event.approved);
+    }
+
+    #[test]
+    fn failed_transfer_logs_nothing() {
+        let (vm, mut token) = setup(ALICE);
+        let before = vm.get_emitted_logs().len();
+        assert!(token.transfer_from(ALICE, BOB, ID, U256::from(1)).is_err());
+        assert_eq!(vm.get_emitted_logs().len(), before);
+    }
+
+    #[test]
+    fn mint_to_many_credits_every_recipient() {
+        let (vm, mut token) = setup(ALICE);
+        let amounts = vec![U256::from(1), U256::from(2), U256::from(3)];
+        assert!(token._mint_to_many(vec![ALICE, BOB, CAROL], ID, amounts).is_ok());
+
+        assert_eq!(token.balance_of(CAROL, ID), U256::from(3));
+        assert_eq!(token.total_supply(ID), U256::from(6));
+        assert_eq!(vm.get_emitted_logs().len(), 3);
+
+        let result = token._mint_to_many(vec![ALICE, BOB], ID, vec![U256::from(1)]);
+        assert!(matches!(result, Err(Erc1155Error::InvalidArrayLength(_))));
+        let result = token._mint_to_many(vec![ALICE, Address::ZERO], ID, vec![U256::from(1); 2]);
+        assert!(matches!(result, Err(Erc1155Error::InvalidReceiver(_))));
+    }
+
+    #[test]
+    fn air_transfer_debits_total() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        let result = token._air_transfer(ALICE, vec![BOB, CAROL], vec![ID], vec![U256::from(4)]);
+        assert!(result.is_ok());
+        assert_eq!(token.balance_of(ALICE, ID), U256::from(2));
+        assert_eq!(token.balance_of(BOB, ID), U256::from(4));
+        assert_eq!(token.balance_of(CAROL, ID), U256::from(4));
+
+        let result = token._air_transfer(ALICE, vec![BOB, CAROL], vec![ID], vec![U256::from(2)]);
+        assert!(matches!(
+            result,
+            Err(Erc1155Error::InsufficientBalance(e)) if e.needed == U256::from(4)
+        ));
+    }
+
+    #[cfg(feature = "enumerable")]
+    #[test]
+    fn update_keeps_holder_and_id_sets_in_sync() {
+        let (_vm, mut token) = setup(ALICE);
+        mint(&mut token, ALICE, ID, U256::from(10));
+        mint(&mut token, ALICE, OTHER_ID, U256::from(1));
+        assert_eq!(token.enumerable().holders(ID), vec![ALICE]);
+        assert_eq!(token.enumerable().total_ids(), U256::from(2));
+
+        // A partial transfer adds the receiver and keeps the sender.
+        assert!(token._update(ALICE, BOB, vec![ID], vec![U256::from(4)]).is_ok());
+        assert_eq!(token.enumerable().holders(ID), vec![ALICE, BOB]);
+        assert_eq!(token.enumerable().ids_of(BOB), vec![ID]);
+
+        // Zero-value moves never add a holder.
+        assert!(token._update(ALICE, CAROL, vec![ID], vec![U256::ZERO]).is_ok());
+        assert!(token.enumerable().ids_of(CAROL).is_empty());
+
+        // Emptying a balance, by transfer or burn, removes the holder.
+        assert!(token._update(ALICE, BOB, vec![ID], vec![U256::from(6)]).is_ok());
+        assert_eq!(token.enumerable().holders(ID), vec![BOB]);
+        assert_eq!(token.enumerable().ids_of(ALICE), vec![OTHER_ID]);
+        assert!(token._update(ALICE, Address::ZERO, vec![OTHER_ID], vec![U256::from(1)]).is_ok());
+        assert!(token.enumerable().ids_of(ALICE).is_empty());
+        assert_eq!(token.enumerable().total_ids(), U256::from(1));
+
+        // A self-transfer of the whole balance leaves the sets as they were.
+        assert!(token._update(BOB, BOB, vec![ID], vec![U256::from(10)]).is_ok());
+        assert_eq!(token.enumerable().holders(ID), vec![BOB]);
+
+        assert!(token._update(BOB, Address::ZERO, vec![ID], vec![U256::from(10)]).is_ok());
+        assert_eq!(token.enumerable().total_ids(), U256::ZERO);
+        assert!(token.enumerable().holders(ID).is_empty());
+    }
+
+    #[cfg(feature = "enumerable")]
+    #[test]
+    fn fan_out_updates_keep_holder_sets_in_sync() {
+        let (_vm, mut token) = setup(ALICE);
+        assert!(token._mint_to_many(vec![ALICE, BOB], ID, vec![U256::from(4), U256::ZERO]).is_ok());
+        assert_eq!(token.enumerable().holders(ID), vec![ALICE]);
+
+        assert!(token._air_transfer(ALICE, vec![BOB, CAROL], vec![ID], vec![U256::from(2)]).is_ok());
+        assert_eq!(token.enumerable().holders(ID), vec![BOB, CAROL]);
+        assert!(token.enumerable().ids_of(ALICE).is_empty());
+    }
 }
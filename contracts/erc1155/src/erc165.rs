@@ -0,0 +1,19 @@
+//! ERC-165 interface detection. Each extension exports its interface id next to its
+//! code; the contract lists the ones it implements in [`Erc165::INTERFACE_IDS`].
+
+use stylus_sdk::alloy_primitives::{fixed_bytes, FixedBytes};
+
+/// `type(IERC165).interfaceId`
+pub const IERC165_INTERFACE_ID: FixedBytes<4> = fixed_bytes!("01ffc9a7");
+/// `type(IERC1155).interfaceId`
+pub const IERC1155_INTERFACE_ID: FixedBytes<4> = fixed_bytes!("d9b67a26");
+
+pub trait Erc165 {
+    /// Interfaces supported besides ERC-165 itself.
+    const INTERFACE_IDS: &'static [FixedBytes<4>];
+
+    /// `0xffffffff` is never supported, as ERC-165 requires.
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        interface_id == IERC165_INTERFACE_ID || Self::INTERFACE_IDS.contains(&interface_id)
+    }
+}
@@ -0,0 +1,240 @@
+//! ERC-20 balances for fungible ids, so game currencies can trade on venues that only
+//! speak ERC-20. Wrapping escrows the 1155 units in this contract and credits the same
+//! amount here; unwrapping reverses it.
+//!
+//! Each wrappable id gets its own ERC-20 facade: an EIP-1167 clone of the
+//! `erc20-facade` contract, created with CREATE2 (salted with the id) when the id is
+//! first made wrappable. The facade exposes the standard ERC-20 ABI and events and calls
+//! back here for every balance and allowance change, so the ledger and its transfer
+//! checks stay in one place.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{hex, Address, B256, U256},
+    alloy_sol_types::sol,
+    deploy::RawDeploy,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+/// EIP-1167 init code up to the implementation address.
+const CLONE_PREFIX: [u8; 20] = hex!("3d602d80600a3d3981f3363d3d373d3d3d363d73");
+/// EIP-1167 init code after the implementation address.
+const CLONE_SUFFIX: [u8; 15] = hex!("5af43d82803e903d91602b57fd5bf3");
+
+sol_storage! {
+    pub struct Erc20Wrapper {
+        mapping(uint256 => bool) enabled;
+        address facade_implementation;
+        mapping(uint256 => address) facades;
+        mapping(address => uint256) facade_ids;
+        mapping(uint256 => uint256) total_supply;
+        mapping(uint256 => mapping(address => uint256)) balances;
+        mapping(uint256 => mapping(address => mapping(address => uint256))) allowances;
+    }
+}
+
+sol! {
+    event WrapperEnabled(uint256 indexed id, bool enabled);
+    event WrapperImplementationSet(address implementation);
+    event WrapperFacadeDeployed(uint256 indexed id, address facade);
+    event Wrapped(address indexed account, uint256 indexed id, uint256 amount);
+    event Unwrapped(address indexed account, uint256 indexed id, uint256 amount);
+
+    error WrapperNotEnabled(uint256 id);
+    error WrapperNoImplementation();
+    error WrapperDeployFailed(uint256 id);
+    error WrapperNotFacade(address caller);
+    error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
+    error ERC20InvalidReceiver(address receiver);
+    error ERC20InvalidSpender(address spender);
+    error ERC20InsufficientAllowance(address spender, uint256 allowance, uint256 needed);
+}
+
+pub enum WrapperError {
+    NotEnabled(WrapperNotEnabled),
+    NoImplementation(WrapperNoImplementation),
+    DeployFailed(WrapperDeployFailed),
+    NotFacade(WrapperNotFacade),
+    InsufficientBalance(ERC20InsufficientBalance),
+    InvalidReceiver(ERC20InvalidReceiver),
+    InvalidSpender(ERC20InvalidSpender),
+    InsufficientAllowance(ERC20InsufficientAllowance),
+}
+
+impl From<WrapperError> for Vec<u8> {
+    fn from(error: WrapperError) -> Self {
+        match error {
+            WrapperError::NotEnabled(e) => revert::encode(e),
+            WrapperError::NoImplementation(e) => revert::encode(e),
+            WrapperError::DeployFailed(e) => revert::encode(e),
+            WrapperError::NotFacade(e) => revert::encode(e),
+            WrapperError::InsufficientBalance(e) => revert::encode(e),
+            WrapperError::InvalidReceiver(e) => revert::encode(e),
+            WrapperError::InvalidSpender(e) => revert::encode(e),
+            WrapperError::InsufficientAllowance(e) => revert::encode(e),
+        }
+    }
+}
+
+/// Init code of an EIP-1167 clone delegating to `implementation`.
+pub fn clone_code(implementation: Address) -> Vec<u8> {
+    let mut code = Vec::with_capacity(CLONE_PREFIX.len() + 20 + CLONE_SUFFIX.len());
+    code.extend_from_slice(&CLONE_PREFIX);
+    code.extend_from_slice(implementation.as_slice());
+    code.extend_from_slice(&CLONE_SUFFIX);
+    code
+}
+
+impl Erc20Wrapper {
+    pub fn is_enabled(&self, id: U256) -> bool {
+        self.enabled.get(id)
+    }
+
+    /// Disabling only stops new wraps; existing balances stay transferable and unwrappable.
+    pub fn set_enabled(&mut self, id: U256, enabled: bool) {
+        self.enabled.insert(id, enabled);
+        log(self.vm(), WrapperEnabled { id, enabled });
+    }
+
+    pub fn facade_implementation(&self) -> Address {
+        self.facade_implementation.get()
+    }
+
+    /// Only affects facades deployed afterwards.
+    pub fn set_facade_implementation(&mut self, implementation: Address) {
+        self.facade_implementation.set(implementation);
+        log(self.vm(), WrapperImplementationSet { implementation });
+    }
+
+    /// `id`'s ERC-20 facade, or zero if it hasn't been deployed.
+    pub fn facade(&self, id: U256) -> Address {
+        self.facades.get(id)
+    }
+
+    /// The id whose facade `caller` is.
+    pub fn facade_id(&self, caller: Address) -> Result<U256, WrapperError> {
+        let id = self.facade_ids.get(caller);
+        if caller.is_zero() || self.facades.get(id) != caller {
+            return Err(WrapperError::NotFacade(WrapperNotFacade { caller }));
+        }
+        Ok(id)
+    }
+
+    /// Clones the facade implementation for `id`; the caller initializes the clone.
+    pub fn _deploy_facade(&mut self, id: U256) -> Result<Address, WrapperError> {
+        let implementation = self.facade_implementation.get();
+        if implementation.is_zero() {
+            return Err(WrapperError::NoImplementation(WrapperNoImplementation {}));
+        }
+        // SAFETY: the clone's init code only returns its runtime code and calls nothing.
+        let facade = unsafe { RawDeploy::new().salt(B256::from(id)).deploy(&clone_code(implementation), U256::ZERO) }
+            .map_err(|_| WrapperError::DeployFailed(WrapperDeployFailed { id }))?;
+        self.facades.insert(id, facade);
+        self.facade_ids.insert(facade, id);
+        log(self.vm(), WrapperFacadeDeployed { id, facade });
+        Ok(facade)
+    }
+
+    pub fn total_supply(&self, id: U256) -> U256 {
+        self.total_supply.get(id)
+    }
+
+    pub fn balance_of(&self, id: U256, account: Address) -> U256 {
+        self.balances.get(id).get(account)
+    }
+
+    pub fn allowance(&self, id: U256, owner: Address, spender: Address) -> U256 {
+        self.allowances.get(id).get(owner).get(spender)
+    }
+
+    /// Credits `amount` to `account`. The caller has already escrowed the 1155 units.
+    pub fn _wrap(&mut self, id: U256, account: Address, amount: U256) -> Result<(), WrapperError> {
+        if !self.is_enabled(id) {
+            return Err(WrapperError::NotEnabled(WrapperNotEnabled { id }));
+        }
+        self.total_supply.insert(id, self.total_supply.get(id) + amount);
+        let balance = self.balance_of(id, account);
+        self.balances.setter(id).insert(account, balance + amount);
+        log(self.vm(), Wrapped { account, id, amount });
+        Ok(())
+    }
+
+    /// Debits `amount` from `account`. The caller releases the 1155 units.
+    pub fn _unwrap(&mut self, id: U256, account: Address, amount: U256) -> Result<(), WrapperError> {
+        self._debit(id, account, amount)?;
+        self.total_supply.insert(id, self.total_supply.get(id) - amount);
+        log(self.vm(), Unwrapped { account, id, amount });
+        Ok(())
+    }
+
+    /// Ledger half of the facade's `transfer`; the facade logs `Transfer`.
+    pub fn _transfer(&mut self, id: U256, from: Address, to: Address, amount: U256) -> Result<(), WrapperError> {
+        if to.is_zero() {
+            return Err(WrapperError::InvalidReceiver(ERC20InvalidReceiver { receiver: to }));
+        }
+        self._debit(id, from, amount)?;
+        let balance = self.balance_of(id, to);
+        self.balances.setter(id).insert(to, balance + amount);
+        Ok(())
+    }
+
+    /// Ledger half of the facade's `approve`; the facade logs `Approval`.
+    pub fn _approve(&mut self, id: U256, owner: Address, spender: Address, amount: U256) -> Result<(), WrapperError> {
+        if spender.is_zero() {
+            return Err(WrapperError::InvalidSpender(ERC20InvalidSpender { spender }));
+        }
+        let mut id_allowances = self.allowances.setter(id);
+        id_allowances.setter(owner).insert(spender, amount);
+        Ok(())
+    }
+
+    /// An allowance of `U256::MAX` is never decreased.
+    pub fn _spend_allowance(&mut self, id: U256, owner: Address, spender: Address, amount: U256) -> Result<(), WrapperError> {
+        let allowance = self.allowance(id, owner, spender);
+        if allowance == U256::MAX {
+            return Ok(());
+        }
+        if allowance < amount {
+            return Err(WrapperError::InsufficientAllowance(ERC20InsufficientAllowance {
+                spender,
+                allowance,
+                needed: amount,
+            }));
+        }
+        let mut id_allowances = self.allowances.setter(id);
+        id_allowances.setter(owner).insert(spender, allowance - amount);
+        Ok(())
+    }
+
+    fn _debit(&mut self, id: U256, account: Address, amount: U256) -> Result<(), WrapperError> {
+        let balance = self.balance_of(id, account);
+        if balance < amount {
+            return Err(WrapperError::InsufficientBalance(ERC20InsufficientBalance {
+                sender: account,
+                balance,
+                needed: amount,
+            }));
+        }
+        self.balances.setter(id).insert(account, balance - amount);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::alloy_primitives::address;
+
+    #[test]
+    fn clone_code_embeds_the_implementation() {
+        let implementation = address!("bebebebebebebebebebebebebebebebebebebebe");
+        let code = clone_code(implementation);
+        assert_eq!(
+            code,
+            hex!("3d602d80600a3d3981f3363d3d373d3d3d363d73bebebebebebebebebebebebebebebebebebebebe5af43d82803e903d91602b57fd5bf3")
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! The error every entrypoint returns. Each extension keeps its own error enum; this
+//! wraps them all so `?` converts any of them, and encodes to exactly the revert data
+//! the extension's error would on its own.
+
+use alloc::vec::Vec;
+use stylus_sdk::call;
+
+use crate::{
+    access_control::AccessControlError, alias::AliasError, allowlist::AllowlistError,
+    attestation::AttestationError, bonding_curve::CurveError, buyback::BuybackError, emission::EmissionError,
+    enumerable::EnumerableError, erc1155::Erc1155Error, erc20_wrapper::WrapperError, l1_snapshot::L1SnapshotError,
+    lazy_mint::LazyMintError, migration::MigrationError, minter_scope::MinterScopeError, multisig::MultisigError,
+    nonces::NonceError, oracle::OracleError, order_book::OrderBookError, otc::OtcError, ownable::OwnableError,
+    pausable::PausableError, permit::PermitError, pool::PoolError, primary_sale::SaleError,
+    recovery::RecoveryError, redemption::RedemptionError, reentrancy::ReentrancyError, reserve::ReserveError,
+    royalty::RoyaltyError, snapshot::SnapshotError, soulbound::SoulboundError,
+    storage_proof::StorageProofError, swap::SwapError, treasury::TreasuryError,
+};
+
+macro_rules! errors {
+    ($($variant:ident($error:ty)),* $(,)?) => {
+        pub enum Error {
+            $($variant($error),)*
+            /// Revert data that is already encoded: bubbled up from a call, or returned
+            /// by the transfer hooks.
+            Revert(Vec<u8>),
+        }
+
+        $(
+            impl From<$error> for Error {
+                fn from(error: $error) -> Self {
+                    Error::$variant(error)
+                }
+            }
+        )*
+
+        impl From<Error> for Vec<u8> {
+            fn from(error: Error) -> Self {
+                match error {
+                    $(Error::$variant(e) => e.into(),)*
+                    Error::Revert(data) => data,
+                }
+            }
+        }
+    };
+}
+
+errors! {
+    AccessControl(AccessControlError),
+    Alias(AliasError),
+    Allowlist(AllowlistError),
+    Attestation(AttestationError),
+    Buyback(BuybackError),
+    Curve(CurveError),
+    Emission(EmissionError),
+    Enumerable(EnumerableError),
+    Erc1155(Erc1155Error),
+    L1Snapshot(L1SnapshotError),
+    LazyMint(LazyMintError),
+    Migration(MigrationError),
+    MinterScope(MinterScopeError),
+    Multisig(MultisigError),
+    Nonce(NonceError),
+    Oracle(OracleError),
+    OrderBook(OrderBookError),
+    Otc(OtcError),
+    Ownable(OwnableError),
+    Pausable(PausableError),
+    Permit(PermitError),
+    Pool(PoolError),
+    Recovery(RecoveryError),
+    Redemption(RedemptionError),
+    Reentrancy(ReentrancyError),
+    Reserve(ReserveError),
+    Royalty(RoyaltyError),
+    Sale(SaleError),
+    Snapshot(SnapshotError),
+    Soulbound(SoulboundError),
+    StorageProof(StorageProofError),
+    Swap(SwapError),
+    Treasury(TreasuryError),
+    Wrapper(WrapperError),
+}
+
+impl From<Vec<u8>> for Error {
+    fn from(data: Vec<u8>) -> Self {
+        Error::Revert(data)
+    }
+}
+
+impl From<call::Error> for Error {
+    fn from(error: call::Error) -> Self {
+        Error::Revert(error.into())
+    }
+}
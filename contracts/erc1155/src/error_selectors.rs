@@ -0,0 +1,158 @@
+//! Generated by `scripts/gen-error-selectors.sh`; do not edit by hand.
+//!
+//! Every custom error the contract can revert with, keyed by name, so SDKs and
+//! monitoring can decode reverts without the full ABI.
+
+use stylus_sdk::alloy_primitives::{fixed_bytes, FixedBytes};
+
+/// `(name, signature, selector)` for each custom error, sorted by name.
+pub const ERROR_SELECTORS: &[(&str, &str, FixedBytes<4>)] = &[
+    ("AccessControlBadConfirmation", "AccessControlBadConfirmation()", fixed_bytes!("6697b232")),
+    ("AccessControlEnforcedDefaultAdminDelay", "AccessControlEnforcedDefaultAdminDelay(uint256)", fixed_bytes!("b7e70544")),
+    ("AccessControlEnforcedDefaultAdminRules", "AccessControlEnforcedDefaultAdminRules()", fixed_bytes!("3fc3c27a")),
+    ("AccessControlInvalidDefaultAdmin", "AccessControlInvalidDefaultAdmin(address)", fixed_bytes!("c22c8022")),
+    ("AccessControlInvalidDefaultAdminDelay", "AccessControlInvalidDefaultAdminDelay(uint256)", fixed_bytes!("f5d1c9e6")),
+    ("AccessControlOutOfBoundsIndex", "AccessControlOutOfBoundsIndex(bytes32,uint256)", fixed_bytes!("0bce05b2")),
+    ("AccessControlUnauthorizedAccount", "AccessControlUnauthorizedAccount(address,bytes32)", fixed_bytes!("e2517d3f")),
+    ("BuybackDisabled", "BuybackDisabled(uint256)", fixed_bytes!("0007a73f")),
+    ("BuybackInsufficientBudget", "BuybackInsufficientBudget(uint256,uint256)", fixed_bytes!("e7cfa936")),
+    ("BuybackInvalidShare", "BuybackInvalidShare(uint256)", fixed_bytes!("5e35decd")),
+    ("BuybackPaymentFailed", "BuybackPaymentFailed(address,uint256)", fixed_bytes!("f43cee18")),
+    ("BuybackPriceTooHigh", "BuybackPriceTooHigh(uint256,uint256,uint256)", fixed_bytes!("ccf46847")),
+    ("BuybackRateLimited", "BuybackRateLimited(uint256,uint256,uint256)", fixed_bytes!("f50ed6ad")),
+    ("CapabilityPaused", "CapabilityPaused(uint256)", fixed_bytes!("f965f07c")),
+    ("CurveActive", "CurveActive(uint256,uint256)", fixed_bytes!("fef077a4")),
+    ("CurveInsufficientSupply", "CurveInsufficientSupply(uint256,uint256,uint256)", fixed_bytes!("fe24c945")),
+    ("CurveInvalidConfig", "CurveInvalidConfig(uint8,uint256)", fixed_bytes!("1678beb5")),
+    ("CurveManagedId", "CurveManagedId(uint256)", fixed_bytes!("b248cf65")),
+    ("CurveNotConfigured", "CurveNotConfigured(uint256)", fixed_bytes!("01e552d4")),
+    ("CurveOverflow", "CurveOverflow(uint256)", fixed_bytes!("8647606f")),
+    ("CurvePaymentFailed", "CurvePaymentFailed(address,uint256)", fixed_bytes!("96082436")),
+    ("CurveSlippage", "CurveSlippage(uint256,uint256)", fixed_bytes!("06c6f9f2")),
+    ("ERC1155InsufficientBalance", "ERC1155InsufficientBalance(address,uint256,uint256,uint256)", fixed_bytes!("03dee4c5")),
+    ("ERC1155InvalidApprover", "ERC1155InvalidApprover(address)", fixed_bytes!("3e31884e")),
+    ("ERC1155InvalidArrayLength", "ERC1155InvalidArrayLength(uint256,uint256)", fixed_bytes!("5b059991")),
+    ("ERC1155InvalidOperator", "ERC1155InvalidOperator(address)", fixed_bytes!("ced3e100")),
+    ("ERC1155InvalidReceiver", "ERC1155InvalidReceiver(address)", fixed_bytes!("57f447ce")),
+    ("ERC1155InvalidSender", "ERC1155InvalidSender(address)", fixed_bytes!("01a83514")),
+    ("ERC1155MissingApprovalForAll", "ERC1155MissingApprovalForAll(address,address)", fixed_bytes!("e237d922")),
+    ("ERC1155MissingAttestation", "ERC1155MissingAttestation(address,uint256,bytes32)", fixed_bytes!("af79891a")),
+    ("ERC1155OutOfBoundsIndex", "ERC1155OutOfBoundsIndex(uint256)", fixed_bytes!("1da51912")),
+    ("ERC1155PermitExpired", "ERC1155PermitExpired(uint256)", fixed_bytes!("adb86a9b")),
+    ("ERC1155PermitInvalidSigner", "ERC1155PermitInvalidSigner(address,address)", fixed_bytes!("b8a881bb")),
+    ("ERC1155ReceiverNotAllowlisted", "ERC1155ReceiverNotAllowlisted(address)", fixed_bytes!("26e0116e")),
+    ("ERC1155TransferLocked", "ERC1155TransferLocked(uint256)", fixed_bytes!("33e20045")),
+    ("ERC20InsufficientAllowance", "ERC20InsufficientAllowance(address,uint256,uint256)", fixed_bytes!("fb8f41b2")),
+    ("ERC20InsufficientBalance", "ERC20InsufficientBalance(address,uint256,uint256)", fixed_bytes!("e450d38c")),
+    ("ERC20InvalidReceiver", "ERC20InvalidReceiver(address)", fixed_bytes!("ec442f05")),
+    ("ERC20InvalidSpender", "ERC20InvalidSpender(address)", fixed_bytes!("94280d62")),
+    ("ERC2981InvalidDefaultRoyalty", "ERC2981InvalidDefaultRoyalty(uint256,uint256)", fixed_bytes!("6f483d09")),
+    ("ERC2981InvalidDefaultRoyaltyReceiver", "ERC2981InvalidDefaultRoyaltyReceiver(address)", fixed_bytes!("b6d9900a")),
+    ("ERC2981InvalidTokenRoyalty", "ERC2981InvalidTokenRoyalty(uint256,uint256,uint256)", fixed_bytes!("dfd1fc1b")),
+    ("ERC2981InvalidTokenRoyaltyReceiver", "ERC2981InvalidTokenRoyaltyReceiver(uint256,address)", fixed_bytes!("969f0852")),
+    ("EmissionInvalidSchedule", "EmissionInvalidSchedule(uint256)", fixed_bytes!("d480bf9a")),
+    ("EnforcedPause", "EnforcedPause()", fixed_bytes!("d93c0665")),
+    ("ExpectedPause", "ExpectedPause()", fixed_bytes!("8dfc202b")),
+    ("IdAliasHasSupply", "IdAliasHasSupply(uint256,uint256)", fixed_bytes!("73c1bd65")),
+    ("InvalidAccountNonce", "InvalidAccountNonce(address,uint256)", fixed_bytes!("752d88c0")),
+    ("InvalidIdAlias", "InvalidIdAlias(uint256,uint256)", fixed_bytes!("3020cbd9")),
+    ("InvalidInitialization", "InvalidInitialization()", fixed_bytes!("f92ee8a9")),
+    ("L1MessageFailed", "L1MessageFailed()", fixed_bytes!("677763a5")),
+    ("L1ReceiverNotSet", "L1ReceiverNotSet()", fixed_bytes!("40fd4417")),
+    ("LazyMintIncorrectPayment", "LazyMintIncorrectPayment(uint256,uint256)", fixed_bytes!("86ce6b01")),
+    ("LazyMintInvalidSigner", "LazyMintInvalidSigner(address)", fixed_bytes!("0286df4c")),
+    ("LazyMintPaymentFailed", "LazyMintPaymentFailed(address,uint256)", fixed_bytes!("1aa6d654")),
+    ("LazyMintTreasuryNotSet", "LazyMintTreasuryNotSet()", fixed_bytes!("e85cba03")),
+    ("LazyMintUriLocked", "LazyMintUriLocked(uint256)", fixed_bytes!("dc775cf1")),
+    ("MigrationAmountTooSmall", "MigrationAmountTooSmall(uint256,uint256)", fixed_bytes!("ea9db7a2")),
+    ("MigrationInvalidRatio", "MigrationInvalidRatio(uint256,uint256)", fixed_bytes!("4eed0a57")),
+    ("MigrationNotConfigured", "MigrationNotConfigured(uint256)", fixed_bytes!("6a69a6dd")),
+    ("MigrationOverflow", "MigrationOverflow(uint256,uint256)", fixed_bytes!("99bd0b13")),
+    ("MinterInvalidRange", "MinterInvalidRange(uint256,uint256)", fixed_bytes!("aba3621c")),
+    ("MinterOutOfScope", "MinterOutOfScope(address,uint256)", fixed_bytes!("7d560dbd")),
+    ("MultisigAlreadyExecuted", "MultisigAlreadyExecuted(uint256)", fixed_bytes!("2979f14c")),
+    ("MultisigInvalidConfig", "MultisigInvalidConfig(uint256,uint256)", fixed_bytes!("e37a8dbe")),
+    ("MultisigNotEnoughConfirmations", "MultisigNotEnoughConfirmations(uint256,uint256,uint256)", fixed_bytes!("a2a78970")),
+    ("MultisigNotSigner", "MultisigNotSigner(address)", fixed_bytes!("3dfa1473")),
+    ("MultisigProposalExpired", "MultisigProposalExpired(uint256)", fixed_bytes!("76bfbdbd")),
+    ("MultisigUnknownProposal", "MultisigUnknownProposal(uint256)", fixed_bytes!("187cac35")),
+    ("OracleNoPrice", "OracleNoPrice(uint256)", fixed_bytes!("be0090f3")),
+    ("OracleSourcePoolMismatch", "OracleSourcePoolMismatch(uint256,uint256,uint256)", fixed_bytes!("ae36420f")),
+    ("OracleWindowTooLong", "OracleWindowTooLong(uint256,uint256,uint256)", fixed_bytes!("4a473450")),
+    ("OrderBookInvalidOrder", "OrderBookInvalidOrder()", fixed_bytes!("5aefe4eb")),
+    ("OrderBookNotMaker", "OrderBookNotMaker(uint256,address)", fixed_bytes!("6afbcfdd")),
+    ("OrderBookOrderClosed", "OrderBookOrderClosed(uint256)", fixed_bytes!("72c1be5e")),
+    ("OrderBookOverfill", "OrderBookOverfill(uint256,uint256,uint256)", fixed_bytes!("54ac13ee")),
+    ("OrderBookOverflow", "OrderBookOverflow(uint256,uint256)", fixed_bytes!("0490c5ba")),
+    ("OtcAlreadyFunded", "OtcAlreadyFunded(uint256,address)", fixed_bytes!("5473513d")),
+    ("OtcClosed", "OtcClosed(uint256)", fixed_bytes!("788f0e55")),
+    ("OtcFullyFunded", "OtcFullyFunded(uint256)", fixed_bytes!("d9ce2966")),
+    ("OtcInvalidCounterparty", "OtcInvalidCounterparty(address)", fixed_bytes!("0b6e181a")),
+    ("OtcNotFunded", "OtcNotFunded(uint256)", fixed_bytes!("8b570383")),
+    ("OtcNotParty", "OtcNotParty(uint256,address)", fixed_bytes!("fee8f697")),
+    ("OwnableInvalidOwner", "OwnableInvalidOwner(address)", fixed_bytes!("1e4fbdf7")),
+    ("OwnableUnauthorizedAccount", "OwnableUnauthorizedAccount(address)", fixed_bytes!("118cdaa7")),
+    ("PoolInsufficientLiquidity", "PoolInsufficientLiquidity(uint256,uint256,uint256)", fixed_bytes!("0d10be79")),
+    ("PoolInvalidConfig", "PoolInvalidConfig(uint8,uint256,uint256)", fixed_bytes!("32d29684")),
+    ("PoolNotOwner", "PoolNotOwner(uint256,address)", fixed_bytes!("04cea47f")),
+    ("PoolOverflow", "PoolOverflow(uint256)", fixed_bytes!("9610ce10")),
+    ("PoolPaymentFailed", "PoolPaymentFailed(address,uint256)", fixed_bytes!("cd9eb8bb")),
+    ("PoolSlippage", "PoolSlippage(uint256,uint256)", fixed_bytes!("bec9741f")),
+    ("RecoveryInvalidConfig", "RecoveryInvalidConfig(uint256,uint256)", fixed_bytes!("51d8250d")),
+    ("RecoveryInvalidSuccessor", "RecoveryInvalidSuccessor(address)", fixed_bytes!("c40636d8")),
+    ("RecoveryNotGuardian", "RecoveryNotGuardian(address,address)", fixed_bytes!("142a5aca")),
+    ("RecoveryNotReady", "RecoveryNotReady(address,uint256)", fixed_bytes!("91655edb")),
+    ("RecoveryNothingToMove", "RecoveryNothingToMove(address)", fixed_bytes!("4e68b1b1")),
+    ("RecoveryUnauthorized", "RecoveryUnauthorized(address,address)", fixed_bytes!("7c0b606f")),
+    ("RedemptionInsufficientPool", "RedemptionInsufficientPool(uint256,uint256,uint256)", fixed_bytes!("7009dc9f")),
+    ("RedemptionNotConfigured", "RedemptionNotConfigured(uint256)", fixed_bytes!("94fc6520")),
+    ("RedemptionOverflow", "RedemptionOverflow(uint256,uint256,uint256)", fixed_bytes!("9c20df40")),
+    ("RedemptionTransferFailed", "RedemptionTransferFailed(address,address,uint256)", fixed_bytes!("9447d8e3")),
+    ("ReentrancyGuardReentrantCall", "ReentrancyGuardReentrantCall()", fixed_bytes!("3ee5aeb5")),
+    ("ReservationExceeded", "ReservationExceeded(address,uint256,uint256,uint256)", fixed_bytes!("d191dec0")),
+    ("RoyaltyApportionOverflow", "RoyaltyApportionOverflow()", fixed_bytes!("5ca119df")),
+    ("RoyaltyPaymentFailed", "RoyaltyPaymentFailed(address,uint256)", fixed_bytes!("89bb601e")),
+    ("SaleDisputeWindowClosed", "SaleDisputeWindowClosed(uint256,uint256)", fixed_bytes!("14b128d1")),
+    ("SaleDisputeWindowOpen", "SaleDisputeWindowOpen(uint256,uint256)", fixed_bytes!("4b0c681c")),
+    ("SaleIncorrectPayment", "SaleIncorrectPayment(uint256,uint256)", fixed_bytes!("d375de9a")),
+    ("SaleNotActive", "SaleNotActive(uint256)", fixed_bytes!("240835cc")),
+    ("SaleNotBuyer", "SaleNotBuyer(uint256,address)", fixed_bytes!("a401c730")),
+    ("SaleOverflow", "SaleOverflow(uint256,uint256)", fixed_bytes!("cb97433a")),
+    ("SalePaymentFailed", "SalePaymentFailed(address,uint256)", fixed_bytes!("3f069fa5")),
+    ("SaleRefundExceedsPurchase", "SaleRefundExceedsPurchase(uint256,uint256,uint256)", fixed_bytes!("a18533c0")),
+    ("SnapshotNonexistentId", "SnapshotNonexistentId(uint256)", fixed_bytes!("99a18d54")),
+    ("StorageProofAccountMissing", "StorageProofAccountMissing(address)", fixed_bytes!("179e01bd")),
+    ("StorageProofInvalid", "StorageProofInvalid()", fixed_bytes!("9885e0e6")),
+    ("SupplyCapBelowCommitted", "SupplyCapBelowCommitted(uint256,uint256,uint256)", fixed_bytes!("2a5257df")),
+    ("SupplyCapExceeded", "SupplyCapExceeded(uint256,uint256,uint256)", fixed_bytes!("36a62f1f")),
+    ("SwapExpired", "SwapExpired(uint256)", fixed_bytes!("72c18cf5")),
+    ("SwapIncorrectPayment", "SwapIncorrectPayment(uint256,uint256)", fixed_bytes!("84a7cbbf")),
+    ("SwapInvalidSigner", "SwapInvalidSigner(address,address)", fixed_bytes!("f949d094")),
+    ("SwapInvalidTaker", "SwapInvalidTaker(address)", fixed_bytes!("e129b752")),
+    ("SwapPaymentFailed", "SwapPaymentFailed(address,uint256)", fixed_bytes!("fde80936")),
+    ("TreasuryInsufficientHoldings", "TreasuryInsufficientHoldings(uint256,uint256,uint256)", fixed_bytes!("af982e5d")),
+    ("TreasuryNotReady", "TreasuryNotReady(uint256,uint256)", fixed_bytes!("e6ae6f67")),
+    ("TreasuryUnauthorized", "TreasuryUnauthorized(address)", fixed_bytes!("309dfb7b")),
+    ("TreasuryUnknownProposal", "TreasuryUnknownProposal(uint256)", fixed_bytes!("5fad2736")),
+    ("UntrustedStateRoot", "UntrustedStateRoot(uint256,uint256)", fixed_bytes!("d1be31c7")),
+    ("WrapperDeployFailed", "WrapperDeployFailed(uint256)", fixed_bytes!("68c45719")),
+    ("WrapperNoImplementation", "WrapperNoImplementation()", fixed_bytes!("7b503768")),
+    ("WrapperNotEnabled", "WrapperNotEnabled(uint256)", fixed_bytes!("e782ff6a")),
+    ("WrapperNotFacade", "WrapperNotFacade(address)", fixed_bytes!("74e617ed")),
+];
+
+/// Selector of the error called `name`, if the contract declares one.
+pub fn selector_of(name: &str) -> Option<FixedBytes<4>> {
+    ERROR_SELECTORS
+        .iter()
+        .find(|(n, _, _)| *n == name)
+        .map(|(_, _, selector)| *selector)
+}
+
+/// Name of the error whose selector is `selector`, for decoding revert data.
+pub fn name_of(selector: FixedBytes<4>) -> Option<&'static str> {
+    ERROR_SELECTORS
+        .iter()
+        .find(|(_, _, s)| *s == selector)
+        .map(|(name, _, _)| *name)
+}
@@ -0,0 +1,56 @@
+//! Game-server actions (item consumption, XP grants) that can run hundreds of times
+//! per block. Their logging is chosen at build time:
+//!
+//! * default: standard `TransferSingle`, fully indexable;
+//! * `minimal-events`: one unindexed `GameAction` per call, roughly a third of the log gas;
+//! * `no-game-events`: nothing at all. Balances are only observable via `balance_of`.
+
+use stylus_sdk::{
+    alloy_primitives::{b256, Address, B256, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::Host,
+};
+
+/// `keccak256("GAME_ROLE")`
+pub const GAME_ROLE: B256 =
+    b256!("6a64baf327d646d1bca72653e2a075d15fd6ac6d8cbd7f6ee03fc55875e0fa88");
+
+pub const ACTION_CONSUME: u8 = 0;
+pub const ACTION_GRANT: u8 = 1;
+
+sol! {
+    event GameAction(uint8 action, address account, uint256 id, uint256 amount);
+}
+
+#[cfg(not(any(feature = "minimal-events", feature = "no-game-events")))]
+pub fn log_action(vm: &dyn Host, action: u8, account: Address, id: U256, amount: U256) {
+    use crate::erc1155::TransferSingle;
+    use stylus_sdk::stylus_core::log;
+
+    let (from, to) = if action == ACTION_CONSUME {
+        (account, Address::ZERO)
+    } else {
+        (Address::ZERO, account)
+    };
+    log(vm, TransferSingle {
+        operator: vm.msg_sender(),
+        from,
+        to,
+        id,
+        value: amount,
+    });
+}
+
+#[cfg(all(feature = "minimal-events", not(feature = "no-game-events")))]
+pub fn log_action(vm: &dyn Host, action: u8, account: Address, id: U256, amount: U256) {
+    stylus_sdk::stylus_core::log(vm, GameAction {
+        action,
+        account,
+        id,
+        amount,
+    });
+}
+
+#[cfg(feature = "no-game-events")]
+pub fn log_action(_vm: &dyn Host, _action: u8, _account: Address, _id: U256, _amount: U256) {}
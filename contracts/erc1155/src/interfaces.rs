@@ -0,0 +1,35 @@
+use stylus_sdk::prelude::*;
+
+sol_interface! {
+    interface IAttester {
+        function hasAttestation(address account, bytes32 schema) external view returns (bool);
+    }
+
+    interface IRoyaltyRegistry {
+        function getRoyaltyLookupAddress(address tokenAddress) external view returns (address);
+    }
+
+    interface IRoyaltyInfo {
+        function royaltyInfo(uint256 tokenId, uint256 salePrice) external view returns (address, uint256);
+    }
+
+    interface IERC1155Receiver {
+        function onERC1155Received(address operator, address from, uint256 id, uint256 value, bytes data) external returns (bytes4);
+        function onERC1155BatchReceived(address operator, address from, uint256[] ids, uint256[] values, bytes data) external returns (bytes4);
+    }
+
+    interface ITransferListener {
+        function onBalanceChange(address operator, address from, address to, uint256[] ids, uint256[] values) external;
+    }
+
+    interface IErc20Facade {
+        function initialize(uint256 id) external;
+        function notifyTransfer(address from, address to, uint256 value) external;
+    }
+
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+        function transfer(address to, uint256 value) external returns (bool);
+        function transferFrom(address from, address to, uint256 value) external returns (bool);
+    }
+}
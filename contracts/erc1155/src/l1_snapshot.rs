@@ -0,0 +1,137 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{address, Address, B256, U256},
+    alloy_sol_types::{sol, SolCall},
+    crypto::keccak,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+/// ArbSys precompile, present on every Arbitrum chain.
+pub const ARB_SYS: Address = address!("0000000000000000000000000000000000000064");
+
+sol_storage! {
+    pub struct L1Snapshots {
+        address l1_receiver;
+        uint256 epoch;
+        mapping(uint256 => bytes32) roots;
+    }
+}
+
+sol! {
+    function sendTxToL1(address destination, bytes data) external payable returns (uint256);
+    function receiveSnapshot(uint256 epoch, bytes32 root, uint256 l2BlockNumber) external;
+
+    event L1ReceiverSet(address receiver);
+    event SnapshotCommitted(uint256 indexed epoch, bytes32 root);
+    event SnapshotSentToL1(uint256 indexed epoch, bytes32 root, address indexed receiver, uint256 withdrawalId);
+
+    error L1ReceiverNotSet();
+    error L1MessageFailed();
+}
+
+pub enum L1SnapshotError {
+    ReceiverNotSet(L1ReceiverNotSet),
+    MessageFailed(L1MessageFailed),
+}
+
+impl From<L1SnapshotError> for Vec<u8> {
+    fn from(error: L1SnapshotError) -> Self {
+        match error {
+            L1SnapshotError::ReceiverNotSet(e) => revert::encode(e),
+            L1SnapshotError::MessageFailed(e) => revert::encode(e),
+        }
+    }
+}
+
+impl L1Snapshots {
+    pub fn l1_receiver(&self) -> Address {
+        self.l1_receiver.get()
+    }
+
+    pub fn epoch(&self) -> U256 {
+        self.epoch.get()
+    }
+
+    pub fn root(&self, epoch: U256) -> B256 {
+        self.roots.get(epoch)
+    }
+
+    pub fn set_l1_receiver(&mut self, receiver: Address) {
+        self.l1_receiver.set(receiver);
+        log(self.vm(), L1ReceiverSet { receiver });
+    }
+
+    /// Records `root` under a new epoch and returns `(epoch, receiver, calldata)` for the
+    /// ArbSys `sendTxToL1` call. The L1 side receives `receiveSnapshot(epoch, root, l2Block)`
+    /// once the outbox entry is confirmed and executed.
+    pub fn _commit(&mut self, root: B256) -> Result<(U256, Address, Vec<u8>), L1SnapshotError> {
+        let receiver = self.l1_receiver.get();
+        if receiver.is_zero() {
+            return Err(L1SnapshotError::ReceiverNotSet(L1ReceiverNotSet {}));
+        }
+
+        let epoch = self._record(root);
+        let message = receiveSnapshotCall {
+            epoch,
+            root,
+            l2BlockNumber: U256::from(self.vm().block_number()),
+        }
+        .abi_encode();
+        let calldata = sendTxToL1Call {
+            destination: receiver,
+            data: message.into(),
+        }
+        .abi_encode();
+        Ok((epoch, receiver, calldata))
+    }
+
+    /// Records `root` under a new epoch without bridging it, for consumers on this chain.
+    pub fn commit(&mut self, root: B256) -> U256 {
+        let epoch = self._record(root);
+        log(self.vm(), SnapshotCommitted { epoch, root });
+        epoch
+    }
+
+    /// Checks `(account, id, amount)` against the root of `epoch`. Leaves are
+    /// `keccak256(keccak256(abi.encode(account, id, amount)))` and pairs are hashed
+    /// sorted, as produced by OpenZeppelin's `StandardMerkleTree`.
+    pub fn verify_balance(&self, epoch: U256, account: Address, id: U256, amount: U256, proof: &[B256]) -> bool {
+        let root = self.root(epoch);
+        if root.is_zero() {
+            return false;
+        }
+        let mut encoded = [0u8; 96];
+        encoded[12..32].copy_from_slice(account.as_slice());
+        encoded[32..64].copy_from_slice(&id.to_be_bytes::<32>());
+        encoded[64..].copy_from_slice(&amount.to_be_bytes::<32>());
+        let leaf = keccak(keccak(encoded));
+        let computed = proof.iter().fold(leaf, |node, sibling| {
+            let (a, b) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+            keccak([a.as_slice(), b.as_slice()].concat())
+        });
+        computed == root
+    }
+
+    fn _record(&mut self, root: B256) -> U256 {
+        let epoch = self.epoch.get() + U256::from(1);
+        self.epoch.set(epoch);
+        self.roots.insert(epoch, root);
+        epoch
+    }
+
+    pub fn _sent(&self, epoch: U256, root: B256, receiver: Address, returndata: &[u8]) -> Result<(), L1SnapshotError> {
+        let withdrawal_id = sendTxToL1Call::abi_decode_returns(returndata, true)
+            .map_err(|_| L1SnapshotError::MessageFailed(L1MessageFailed {}))?
+            ._0;
+        log(self.vm(), SnapshotSentToL1 {
+            epoch,
+            root,
+            receiver,
+            withdrawalId: withdrawal_id,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+//! Lazy minting: a minter signs a [`MintVoucher`] off-chain and the buyer redeems it,
+//! paying `price` and the gas for the mint. Proceeds go straight to the configured
+//! treasury, so nothing accrues in the contract. Vouchers are signed with ECDSA or, by
+//! minters that are smart accounts, with the passkey they registered.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{b256, Address, B256, U256},
+    alloy_sol_types::sol,
+    call::transfer_eth,
+    crypto::keccak,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::{
+    ecdsa,
+    eip712::{self, address_word},
+    nonces::{InvalidAccountNonce, NonceError, SignatureNonces},
+    revert,
+    webauthn::Passkeys,
+};
+
+/// `keccak256("MintVoucher(uint256 id,uint256 amount,string uri,uint256 price,address recipient,uint256 nonce)")`
+pub const MINT_VOUCHER_TYPEHASH: B256 =
+    b256!("31a8b09bfc482a5e74a84ed1e0568889d68b54d590beb5a92c8c0fa85aeebd19");
+
+sol_storage! {
+    pub struct LazyMint {
+        address treasury;
+    }
+}
+
+sol! {
+    /// `recipient == address(0)` mints to whoever redeems; otherwise anyone may redeem
+    /// (and pay) on the recipient's behalf. A non-empty `uri` becomes the id's URI; it
+    /// can only name a fresh id unless the signer may set URIs anyway.
+    struct MintVoucher {
+        uint256 id;
+        uint256 amount;
+        string uri;
+        uint256 price;
+        address recipient;
+        uint256 nonce;
+    }
+
+    event LazyMintTreasurySet(address treasury);
+    event VoucherRedeemed(address indexed signer, uint256 indexed nonce, address indexed to, uint256 id, uint256 amount, uint256 price);
+
+    error LazyMintTreasuryNotSet();
+    error LazyMintInvalidSigner(address signer);
+    error LazyMintIncorrectPayment(uint256 expected, uint256 received);
+    error LazyMintPaymentFailed(address treasury, uint256 amount);
+    error LazyMintUriLocked(uint256 id);
+}
+
+pub enum LazyMintError {
+    TreasuryNotSet(LazyMintTreasuryNotSet),
+    InvalidSigner(LazyMintInvalidSigner),
+    IncorrectPayment(LazyMintIncorrectPayment),
+    PaymentFailed(LazyMintPaymentFailed),
+    InvalidNonce(InvalidAccountNonce),
+    UriLocked(LazyMintUriLocked),
+}
+
+impl From<LazyMintError> for Vec<u8> {
+    fn from(error: LazyMintError) -> Self {
+        match error {
+            LazyMintError::TreasuryNotSet(e) => revert::encode(e),
+            LazyMintError::InvalidSigner(e) => revert::encode(e),
+            LazyMintError::IncorrectPayment(e) => revert::encode(e),
+            LazyMintError::PaymentFailed(e) => revert::encode(e),
+            LazyMintError::InvalidNonce(e) => revert::encode(e),
+            LazyMintError::UriLocked(e) => revert::encode(e),
+        }
+    }
+}
+
+impl From<NonceError> for LazyMintError {
+    fn from(error: NonceError) -> Self {
+        match error {
+            NonceError::InvalidAccountNonce(e) => LazyMintError::InvalidNonce(e),
+        }
+    }
+}
+
+impl LazyMint {
+    pub fn treasury(&self) -> Address {
+        self.treasury.get()
+    }
+
+    pub fn set_treasury(&mut self, treasury: Address) {
+        self.treasury.set(treasury);
+        log(self.vm(), LazyMintTreasurySet { treasury });
+    }
+
+    /// Checks the payment, recovers the voucher's signer and consumes its nonce. Returns
+    /// `(signer, to)`; the caller checks the signer's authority and mints. A non-zero
+    /// `passkey_signer` names the account whose passkey signed; the signature must then
+    /// be a WebAuthn assertion by it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn _verify(
+        &self,
+        nonces: &mut SignatureNonces,
+        passkeys: &Passkeys,
+        voucher: &MintVoucher,
+        passkey_signer: Address,
+        redeemer: Address,
+        value: U256,
+        signature: &[u8],
+    ) -> Result<(Address, Address), LazyMintError> {
+        if self.treasury.get().is_zero() && !voucher.price.is_zero() {
+            return Err(LazyMintError::TreasuryNotSet(LazyMintTreasuryNotSet {}));
+        }
+        let to = if voucher.recipient.is_zero() { redeemer } else { voucher.recipient };
+        if value != voucher.price {
+            return Err(LazyMintError::IncorrectPayment(LazyMintIncorrectPayment {
+                expected: voucher.price,
+                received: value,
+            }));
+        }
+
+        let digest = eip712::hash_typed_data(self.vm(), struct_hash(voucher));
+        let signer = if passkey_signer.is_zero() {
+            ecdsa::recover_signature(digest, signature).unwrap_or_default()
+        } else if passkeys.verify(passkey_signer, digest, signature) {
+            passkey_signer
+        } else {
+            return Err(LazyMintError::InvalidSigner(LazyMintInvalidSigner { signer: passkey_signer }));
+        };
+        nonces._use_unordered(signer, voucher.nonce)?;
+        Ok((signer, to))
+    }
+
+    /// Forwards the voucher's price to the treasury and logs the redemption.
+    pub fn _settle(&self, signer: Address, to: Address, voucher: &MintVoucher) -> Result<(), LazyMintError> {
+        let treasury = self.treasury.get();
+        if !voucher.price.is_zero() && transfer_eth(treasury, voucher.price).is_err() {
+            return Err(LazyMintError::PaymentFailed(LazyMintPaymentFailed {
+                treasury,
+                amount: voucher.price,
+            }));
+        }
+        log(self.vm(), VoucherRedeemed {
+            signer,
+            nonce: voucher.nonce,
+            to,
+            id: voucher.id,
+            amount: voucher.amount,
+            price: voucher.price,
+        });
+        Ok(())
+    }
+}
+
+/// A voucher carrying a URI may set it only on an id with no supply and no URI of its
+/// own, so a minter's signature can't rewrite live metadata. Signers who also hold the
+/// URI setter role are exempt.
+pub fn check_uri(voucher: &MintVoucher, signer_sets_uris: bool, supply: U256, has_uri: bool) -> Result<(), LazyMintError> {
+    if voucher.uri.is_empty() || signer_sets_uris || (supply.is_zero() && !has_uri) {
+        return Ok(());
+    }
+    Err(LazyMintError::UriLocked(LazyMintUriLocked { id: voucher.id }))
+}
+
+fn struct_hash(voucher: &MintVoucher) -> B256 {
+    eip712::hash_words(&[
+        MINT_VOUCHER_TYPEHASH,
+        B256::from(voucher.id),
+        B256::from(voucher.amount),
+        keccak(voucher.uri.as_bytes()),
+        B256::from(voucher.price),
+        address_word(voucher.recipient),
+        B256::from(voucher.nonce),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    fn voucher(uri: &str) -> MintVoucher {
+        MintVoucher {
+            id: U256::from(1),
+            amount: U256::from(1),
+            uri: String::from(uri),
+            price: U256::ZERO,
+            recipient: Address::ZERO,
+            nonce: U256::ZERO,
+        }
+    }
+
+    #[test]
+    fn voucher_uris_only_name_fresh_ids() {
+        let with_uri = voucher("ipfs://new");
+        assert!(check_uri(&with_uri, false, U256::ZERO, false).is_ok());
+        assert!(matches!(check_uri(&with_uri, false, U256::from(5), false), Err(LazyMintError::UriLocked(_))));
+        assert!(matches!(check_uri(&with_uri, false, U256::ZERO, true), Err(LazyMintError::UriLocked(_))));
+        assert!(check_uri(&with_uri, true, U256::from(5), true).is_ok());
+        // Vouchers without a URI leave metadata alone and are always accepted.
+        assert!(check_uri(&voucher(""), false, U256::from(5), true).is_ok());
+    }
+}
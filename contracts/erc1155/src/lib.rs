@@ -2,30 +2,231 @@
 #![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
 extern crate alloc;
 
+pub mod access_control;
+pub mod alias;
+pub mod allowlist;
+pub mod attestation;
+pub mod bonding_curve;
+pub mod buyback;
+pub mod circuit_breaker;
+pub mod ecdsa;
+pub mod eip712;
+pub mod emission;
+pub mod enumerable;
 pub mod erc1155;
+pub mod erc165;
+pub mod erc20_wrapper;
+pub mod error;
+pub mod error_selectors;
+pub mod game;
+pub mod interfaces;
+pub mod l1_snapshot;
+pub mod lazy_mint;
+pub mod listener;
+pub mod metadata;
+pub mod migration;
+pub mod minter_scope;
+pub mod multisig;
+pub mod nonces;
+pub mod oracle;
+pub mod order_book;
+pub mod otc;
+pub mod ownable;
+pub mod pausable;
+pub mod permit;
+pub mod pool;
+pub mod primary_sale;
+pub mod recovery;
+pub mod redemption;
+pub mod reentrancy;
+pub mod reserve;
+pub mod revert;
+pub mod royalty;
+pub mod slots;
+pub mod snapshot;
+pub mod soulbound;
+pub mod storage_proof;
+pub mod swap;
+pub mod treasury;
+pub mod webauthn;
 
-use alloc::vec::Vec;
-use erc1155::Erc1155;
-use stylus_sdk::{alloy_primitives::{Address, U256}, prelude::*};
+use access_control::{AccessControl, DEFAULT_ADMIN_ROLE};
+use alias::IdAliases;
+use alloc::{string::String, vec::Vec};
+use allowlist::{ReceiverAllowlist, ALLOWLIST_ADMIN_ROLE};
+use attestation::AttestationGate;
+use bonding_curve::{BondingCurves, CurveError, CurvePaymentFailed};
+use buyback::{Buyback, BuybackError, BuybackPaymentFailed, TREASURY_ROLE};
+use circuit_breaker::CircuitBreaker;
+use emission::EmissionSchedules;
+use erc1155::{
+    Erc1155, Erc1155Error, Erc1155Hooks, ERC1155InvalidArrayLength, ERC1155InvalidReceiver, ERC1155InvalidSender,
+    ERC1155MissingApprovalForAll, MINTER_ROLE,
+};
+use erc165::{Erc165, IERC1155_INTERFACE_ID};
+use erc20_wrapper::Erc20Wrapper;
+use error::Error;
+use game::{ACTION_CONSUME, ACTION_GRANT, GAME_ROLE};
+use interfaces::{IErc20Facade, ITransferListener, IERC20};
+use l1_snapshot::{L1MessageFailed, L1SnapshotError, L1Snapshots, ARB_SYS};
+use lazy_mint::{LazyMint, LazyMintError, LazyMintInvalidSigner, MintVoucher};
+use listener::TransferListener;
+use metadata::{TokenMetadata, IERC1155_METADATA_URI_INTERFACE_ID, IERC4906_INTERFACE_ID, URI_SETTER_ROLE};
+use migration::Migration;
+use minter_scope::MinterScopes;
+use multisig::Multisig;
+use nonces::SignatureNonces;
+use oracle::PriceOracle;
+use order_book::OrderBook;
+use otc::OtcEscrow;
+use pausable::{Pausable, PAUSER_ROLE, PAUSE_BURN, PAUSE_MARKETPLACE, PAUSE_MINT, PAUSE_TRANSFER};
+use pool::{PoolError, PoolPaymentFailed, PoolSlippage, TradingPools};
+use primary_sale::{EscrowedSales, SaleError, SalePaymentFailed};
+use recovery::AccountRecovery;
+use redemption::{Redemption, RedemptionError, RedemptionTransferFailed};
+use reentrancy::ReentrancyGuard;
+use reserve::SupplyReserve;
+use royalty::{Royalties, RoyaltyError, RoyaltyPaymentFailed, FEE_DENOMINATOR, IERC2981_INTERFACE_ID};
+use snapshot::Erc1155Snapshot;
+use soulbound::Soulbound;
+use storage_proof::RemoteStateRoots;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, B256, U256},
+    call::{self, transfer_eth, Call},
+    prelude::*,
+};
+use swap::{SwapError, SwapOrder, SwapPaymentFailed};
+use treasury::Treasury;
+use webauthn::Passkeys;
 
 #[entrypoint]
 #[storage]
 pub struct My1155 {
     erc1155: Erc1155,
+    access: AccessControl,
+    aliases: IdAliases,
+    allowlist: ReceiverAllowlist,
+    attestations: AttestationGate,
+    soulbound: Soulbound,
+    recovery: AccountRecovery,
+    redemption: Redemption,
+    buyback: Buyback,
+    migration: Migration,
+    pausable: Pausable,
+    circuit_breaker: CircuitBreaker,
+    remote_roots: RemoteStateRoots,
+    l1_snapshots: L1Snapshots,
+    multisig: Multisig,
+    nonces: SignatureNonces,
+    order_book: OrderBook,
+    otc: OtcEscrow,
+    curves: BondingCurves,
+    pools: TradingPools,
+    oracle: PriceOracle,
+    royalties: Royalties,
+    metadata: TokenMetadata,
+    emissions: EmissionSchedules,
+    treasury: Treasury,
+    sales: EscrowedSales,
+    reserves: SupplyReserve,
+    minter_scopes: MinterScopes,
+    passkeys: Passkeys,
+    listener: TransferListener,
+    lazy_mint: LazyMint,
+    wrapper: Erc20Wrapper,
+    reentrancy: ReentrancyGuard,
+    snapshots: Erc1155Snapshot,
 }
 
 #[public]
 impl My1155 {
+    /// Makes the caller owner and default admin, and gives it the minter, URI-setter
+    /// and pauser roles so a fresh deployment is usable before any role is delegated.
+    pub fn initialize(&mut self) -> Result<(), Error> {
+        let admin = self.vm().msg_sender();
+        self.access._initialize(admin)?;
+        for role in [MINTER_ROLE, URI_SETTER_ROLE, PAUSER_ROLE] {
+            self.access._grant_role(role, admin);
+        }
+        Ok(())
+    }
+
+    pub fn owner(&self) -> Address {
+        ownable::owner(&self.access)
+    }
+
+    /// Schedules `new_owner` as default admin; it takes over by calling
+    /// `accept_default_admin_transfer` once the admin delay has passed.
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), Error> {
+        ownable::only_owner(&self.access)?;
+        ownable::check_new_owner(new_owner)?;
+        self.access.begin_default_admin_transfer(new_owner)?;
+        Ok(())
+    }
+
+    /// ERC-165 over [`Erc165::INTERFACE_IDS`].
+    pub fn supports_interface(&self, interface_id: FixedBytes<4>) -> bool {
+        <Self as Erc165>::supports_interface(interface_id)
+    }
+
+    /// Selector of the custom error called `name`, or zero if there is none.
+    pub fn error_selector(&self, name: String) -> FixedBytes<4> {
+        error_selectors::selector_of(&name).unwrap_or_default()
+    }
+
+    /// Name of the custom error with `selector`, or empty if there is none.
+    pub fn error_name(&self, selector: FixedBytes<4>) -> String {
+        error_selectors::name_of(selector).unwrap_or_default().into()
+    }
+
     pub fn balance_of(&self, account: Address, id: U256) -> U256 {
-        self.erc1155.balance_of(account, id)
+        self.erc1155.balance_of(account, self.aliases.resolve(id))
+    }
+
+    pub fn total_supply(&self, id: U256) -> U256 {
+        self.erc1155.total_supply(self.aliases.resolve(id))
+    }
+
+    /// Units of every id in circulation, as OpenZeppelin's `ERC1155Supply.totalSupply()`.
+    #[selector(name = "totalSupply")]
+    pub fn total_supply_all(&self) -> U256 {
+        self.erc1155.total_supply_all()
     }
 
-    pub fn balance_of_batch(&self, accounts: Vec<Address>, ids: Vec<U256>) -> Result<Vec<U256>, Vec<u8>> {
-        self.erc1155.balance_of_batch(accounts, ids).map_err(|e| e.into())
+    pub fn exists(&self, id: U256) -> bool {
+        self.erc1155.exists(self.aliases.resolve(id))
     }
 
-    pub fn set_approval_for_all(&mut self, operator: Address, approved: bool) -> Result<(), Vec<u8>> {
-        self.erc1155.set_approval_for_all(operator, approved).map_err(|e| e.into())
+    pub fn current_snapshot_id(&self) -> U256 {
+        self.snapshots.current_id()
+    }
+
+    /// Freezes every balance and supply at this point; returns the new snapshot id.
+    pub fn snapshot(&mut self) -> Result<U256, Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        Ok(self.snapshots._snapshot())
+    }
+
+    pub fn balance_of_at(&self, account: Address, id: U256, snapshot_id: U256) -> Result<U256, Error> {
+        let id = self.aliases.resolve(id);
+        let balance = self.erc1155.balance_of(account, id);
+        self.snapshots.balance_of_at(account, id, snapshot_id, balance).map_err(Error::from)
+    }
+
+    pub fn total_supply_at(&self, id: U256, snapshot_id: U256) -> Result<U256, Error> {
+        let id = self.aliases.resolve(id);
+        let supply = self.erc1155.total_supply(id);
+        self.snapshots.total_supply_at(id, snapshot_id, supply).map_err(Error::from)
+    }
+
+    pub fn balance_of_batch(&self, accounts: Vec<Address>, ids: Vec<U256>) -> Result<Vec<U256>, Error> {
+        let ids = self.aliases.resolve_all(ids);
+        self.erc1155.balance_of_batch(accounts, ids).map_err(Error::from)
+    }
+
+    pub fn set_approval_for_all(&mut self, operator: Address, approved: bool) -> Result<(), Error> {
+        self.erc1155.set_approval_for_all(operator, approved).map_err(Error::from)
     }
 
     pub fn is_approved_for_all(&self, account: Address, operator: Address) -> bool {
@@ -39,8 +240,17 @@ impl My1155 {
         id: U256,
         value: U256,
         data: Vec<u8>,
-    ) -> Result<(), Vec<u8>> {
-        self.erc1155.safe_transfer_from(from, to, id, value, data).map_err(|e| e.into())
+    ) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let id = this.aliases.resolve(id);
+            this._before_transfer(to, &[id])?;
+            if to == this.vm().contract_address() {
+                this.treasury._deposit(id, value);
+            }
+            this._safe_transfer(from, to, vec![id], vec![value], data)?;
+            this._after_transfer(&[id], &[value]);
+            Ok(())
+        })
     }
 
     pub fn safe_batch_transfer_from(
@@ -50,8 +260,1946 @@ impl My1155 {
         ids: Vec<U256>,
         values: Vec<U256>,
         data: Vec<u8>,
-    ) -> Result<(), Vec<u8>> {
-        self.erc1155.safe_batch_transfer_from(from, to, ids, values, data).map_err(|e| e.into())
+    ) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let ids = this.aliases.resolve_all(ids);
+            this._before_transfer(to, &ids)?;
+            if to == this.vm().contract_address() {
+                for (&id, &value) in ids.iter().zip(values.iter()) {
+                    this.treasury._deposit(id, value);
+                }
+            }
+            this._safe_transfer(from, to, ids.clone(), values.clone(), data)?;
+            this._after_transfer(&ids, &values);
+            Ok(())
+        })
+    }
+
+    /// Dry run of `safe_transfer_from` with the caller as operator. Returns `(true, "")`
+    /// or `(false, revertData)` with exactly the error the transfer would revert with.
+    pub fn can_transfer(&self, from: Address, to: Address, id: U256, value: U256) -> (bool, Bytes) {
+        self.can_transfer_batch(from, to, vec![id], vec![value])
+    }
+
+    pub fn can_transfer_batch(&self, from: Address, to: Address, ids: Vec<U256>, values: Vec<U256>) -> (bool, Bytes) {
+        match self._check_transfer(from, to, ids, &values) {
+            Ok(()) => (true, Bytes::default()),
+            Err(error) => (false, Vec::<u8>::from(error).into()),
+        }
+    }
+
+    #[selector(name = "contractURI")]
+    pub fn contract_uri(&self) -> String {
+        self.metadata.contract_uri()
+    }
+
+    #[selector(name = "setContractURI")]
+    pub fn set_contract_uri(&mut self, uri: String) -> Result<(), Error> {
+        self.access.only_role(URI_SETTER_ROLE)?;
+        self.metadata.set_contract_uri(uri);
+        Ok(())
+    }
+
+    pub fn uri(&self, id: U256) -> String {
+        self.metadata.token_uri(self.aliases.resolve(id))
+    }
+
+    pub fn uri_with_locale(&self, id: U256, locale: String) -> String {
+        self.metadata.localized_uri(self.aliases.resolve(id), &locale)
+    }
+
+    pub fn attribute(&self, id: U256, key: B256) -> String {
+        self.metadata.attribute(self.aliases.resolve(id), key)
+    }
+
+    pub fn base_uri(&self) -> String {
+        self.metadata.base_uri()
+    }
+
+    /// Template for ids without an override; `{id}` is substituted on read.
+    pub fn set_base_uri(&mut self, uri: String) -> Result<(), Error> {
+        self.access.only_role(URI_SETTER_ROLE)?;
+        self.metadata.set_base_uri(uri);
+        Ok(())
+    }
+
+    /// Per-id override of the base URI; an empty `uri` removes it.
+    pub fn set_uri(&mut self, id: U256, uri: String) -> Result<(), Error> {
+        self.access.only_role(URI_SETTER_ROLE)?;
+        self.metadata.set_token_uri(self.aliases.resolve(id), uri);
+        Ok(())
+    }
+
+    pub fn set_localized_uri(&mut self, id: U256, locale: String, uri: String) -> Result<(), Error> {
+        self.access.only_role(URI_SETTER_ROLE)?;
+        self.metadata.set_localized_uri(self.aliases.resolve(id), &locale, uri);
+        Ok(())
+    }
+
+    pub fn set_attribute(&mut self, id: U256, key: B256, value: String) -> Result<(), Error> {
+        self.access.only_role(URI_SETTER_ROLE)?;
+        self.metadata.set_attribute(self.aliases.resolve(id), key, value);
+        Ok(())
+    }
+
+    pub fn set_uris_batch(&mut self, ids: Vec<U256>, uris: Vec<String>) -> Result<(), Error> {
+        self.access.only_role(URI_SETTER_ROLE)?;
+        if ids.len() != uris.len() {
+            return Err(Erc1155Error::InvalidArrayLength(ERC1155InvalidArrayLength {
+                idsLength: U256::from(ids.len()),
+                valuesLength: U256::from(uris.len()),
+            })
+            .into());
+        }
+        let ids = self.aliases.resolve_all(ids);
+        self.metadata.set_token_uris(&ids, uris);
+        Ok(())
+    }
+
+    pub fn set_attributes_batch(&mut self, ids: Vec<U256>, keys: Vec<B256>, values: Vec<String>) -> Result<(), Error> {
+        self.access.only_role(URI_SETTER_ROLE)?;
+        let mismatch = [keys.len(), values.len()].into_iter().find(|len| *len != ids.len());
+        if let Some(len) = mismatch {
+            return Err(Erc1155Error::InvalidArrayLength(ERC1155InvalidArrayLength {
+                idsLength: U256::from(ids.len()),
+                valuesLength: U256::from(len),
+            })
+            .into());
+        }
+        let ids = self.aliases.resolve_all(ids);
+        self.metadata.set_attributes(&ids, &keys, values);
+        Ok(())
+    }
+
+    /// Emits `BatchMetadataUpdate(from, to)` so marketplaces re-fetch off-chain metadata.
+    pub fn refresh_metadata(&mut self, from: U256, to: U256) -> Result<(), Error> {
+        self.access.only_role(URI_SETTER_ROLE)?;
+        self.metadata.refresh(from, to);
+        Ok(())
+    }
+
+    /// Storage key of `balance_of(account, id)` for `eth_getProof`.
+    pub fn balance_slot(&self, account: Address, id: U256) -> B256 {
+        slots::balance_slot(account, self.aliases.resolve(id))
+    }
+
+    /// Storage key of `is_approved_for_all(owner, operator)` for `eth_getProof`.
+    pub fn approval_slot(&self, owner: Address, operator: Address) -> B256 {
+        slots::approval_slot(owner, operator)
+    }
+
+    pub fn remote_state_root(&self, chain_id: U256, block_number: U256) -> B256 {
+        self.remote_roots.root(chain_id, block_number)
+    }
+
+    /// Registers a state root of another chain, e.g. relayed by a bridge or light client.
+    pub fn set_remote_state_root(&mut self, chain_id: U256, block_number: U256, state_root: B256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.remote_roots.set_root(chain_id, block_number, state_root);
+        Ok(())
+    }
+
+    /// Proves `account`'s balance of `id` in `remote_contract` (a deployment of this
+    /// contract) at a registered block of `chain_id`.
+    pub fn verify_remote_balance(
+        &self,
+        chain_id: U256,
+        block_number: U256,
+        remote_contract: Address,
+        account: Address,
+        id: U256,
+        account_proof: Vec<Bytes>,
+        storage_proof: Vec<Bytes>,
+    ) -> Result<U256, Error> {
+        let state_root = self.remote_roots.trusted_root(chain_id, block_number)?;
+        let slot = slots::balance_slot(account, id);
+        let balance = storage_proof::verify_storage_slot(state_root, remote_contract, slot, &account_proof, &storage_proof)?;
+        Ok(balance)
+    }
+
+    pub fn l1_snapshot_receiver(&self) -> Address {
+        self.l1_snapshots.l1_receiver()
+    }
+
+    pub fn l1_snapshot_epoch(&self) -> U256 {
+        self.l1_snapshots.epoch()
+    }
+
+    pub fn l1_snapshot_root(&self, epoch: U256) -> B256 {
+        self.l1_snapshots.root(epoch)
+    }
+
+    pub fn set_l1_snapshot_receiver(&mut self, receiver: Address) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.l1_snapshots.set_l1_receiver(receiver);
+        Ok(())
+    }
+
+    /// Commits an off-chain computed supply/holder root and sends it to the L1 receiver
+    /// through ArbSys. Any `msg.value` is forwarded with the message.
+    #[payable]
+    pub fn send_snapshot_to_l1(&mut self, root: B256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            this.access.only_role(DEFAULT_ADMIN_ROLE)?;
+            let (epoch, receiver, calldata) = this.l1_snapshots._commit(root)?;
+            let value = this.vm().msg_value();
+            let returndata = call::call(Call::new_in(this).value(value), ARB_SYS, &calldata)
+                .map_err(|_| L1SnapshotError::MessageFailed(L1MessageFailed {}))?;
+            this.l1_snapshots._sent(epoch, root, receiver, &returndata)?;
+            Ok(epoch)
+        })
+    }
+
+    /// `(listener, gas_limit)` notified after every balance change.
+    pub fn transfer_listener(&self) -> (Address, u64) {
+        (self.listener.listener(), self.listener.gas_limit())
+    }
+
+    /// The listener is trusted: it runs mid-operation and may call back into the contract.
+    pub fn set_transfer_listener(&mut self, listener: Address, gas_limit: u64) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.listener.set_listener(listener, gas_limit);
+        Ok(())
+    }
+
+    /// Commits an off-chain computed balance snapshot root locally, under the same epoch
+    /// counter as roots sent to L1.
+    pub fn commit_snapshot_root(&mut self, root: B256) -> Result<U256, Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        Ok(self.l1_snapshots.commit(root))
+    }
+
+    /// Whether `account` held `amount` of `id` in the snapshot committed as `epoch`.
+    pub fn verify_snapshot_balance(&self, epoch: U256, account: Address, id: U256, amount: U256, proof: Vec<B256>) -> bool {
+        self.l1_snapshots.verify_balance(epoch, account, id, amount, &proof)
+    }
+
+    /// Enumeration views are only populated in builds with the `enumerable` feature.
+    pub fn holder_count(&self, id: U256) -> U256 {
+        self.erc1155.enumerable().holder_count(id)
+    }
+
+    pub fn holder_by_index(&self, id: U256, index: U256) -> Result<Address, Error> {
+        self.erc1155.enumerable().holder_at(id, index).map_err(Error::from)
+    }
+
+    pub fn id_count_of(&self, account: Address) -> U256 {
+        self.erc1155.enumerable().id_count_of(account)
+    }
+
+    pub fn id_of_owner_by_index(&self, account: Address, index: U256) -> Result<U256, Error> {
+        self.erc1155.enumerable().id_of_owner_at(account, index).map_err(Error::from)
+    }
+
+    pub fn total_ids(&self) -> U256 {
+        self.erc1155.enumerable().total_ids()
+    }
+
+    pub fn id_by_index(&self, index: U256) -> Result<U256, Error> {
+        self.erc1155.enumerable().id_at(index).map_err(Error::from)
+    }
+
+    pub fn canonical_id(&self, id: U256) -> U256 {
+        self.aliases.resolve(id)
+    }
+
+    pub fn set_id_alias(&mut self, alias_id: U256, canonical_id: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        let supply = self.erc1155.total_supply(alias_id);
+        self.aliases.set_alias(alias_id, canonical_id, supply).map_err(Error::from)
+    }
+
+    pub fn remove_id_alias(&mut self, alias_id: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.aliases.remove_alias(alias_id);
+        Ok(())
+    }
+
+    pub fn nonces(&self, owner: Address) -> U256 {
+        self.nonces.nonces(owner)
+    }
+
+    pub fn is_nonce_valid(&self, account: Address, nonce: U256) -> bool {
+        self.nonces.is_nonce_valid(account, nonce)
+    }
+
+    /// Cancels one unredeemed permit, voucher or order signed by the caller.
+    pub fn invalidate_nonce(&mut self, nonce: U256) {
+        self.nonces.invalidate_nonce(nonce)
+    }
+
+    /// Cancels every unredeemed permit, voucher or order signed by the caller with a nonce below `up_to`.
+    pub fn invalidate_nonces_up_to(&mut self, up_to: U256) {
+        self.nonces.invalidate_nonces_up_to(up_to)
+    }
+
+    /// `account`'s registered P-256 public key, `(x, y)`.
+    pub fn passkey(&self, account: Address) -> (B256, B256) {
+        self.passkeys.passkey(account)
+    }
+
+    /// Registers the caller's passkey so WebAuthn assertions are accepted wherever an
+    /// ECDSA signature from the caller would be. Zero coordinates remove it.
+    pub fn set_passkey(&mut self, x: B256, y: B256) {
+        self.passkeys.set_passkey(x, y)
+    }
+
+    #[selector(name = "DOMAIN_SEPARATOR")]
+    pub fn domain_separator(&self) -> B256 {
+        eip712::domain_separator(self.vm())
+    }
+
+    /// Gasless `setApprovalForAll`: anyone may submit `owner`'s signed `Permit`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn permit(
+        &mut self,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+        deadline: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), Error> {
+        let signature = ecdsa::join(v, r, s);
+        permit::verify(&mut self.nonces, &self.passkeys, owner, operator, approved, deadline, &signature)?;
+        self.erc1155._set_approval_for_all(owner, operator, approved)?;
+        Ok(())
+    }
+
+    /// Applies several operator approvals signed by `owner` in one `PermitBatch` message.
+    /// `signature` is 65-byte `r ++ s ++ v` or 64-byte EIP-2098 `r ++ vs`.
+    pub fn permit_batch(
+        &mut self,
+        owner: Address,
+        operators: Vec<Address>,
+        approved: Vec<bool>,
+        deadline: U256,
+        signature: Bytes,
+    ) -> Result<(), Error> {
+        self._permit_batch(owner, operators, approved, deadline, &signature)
+    }
+
+    #[selector(name = "permitBatch")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn permit_batch_vrs(
+        &mut self,
+        owner: Address,
+        operators: Vec<Address>,
+        approved: Vec<bool>,
+        deadline: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), Error> {
+        self._permit_batch(owner, operators, approved, deadline, &ecdsa::join(v, r, s))
+    }
+
+    pub fn has_role(&self, role: B256, account: Address) -> bool {
+        self.access.has_role(role, account)
+    }
+
+    pub fn get_role_member_count(&self, role: B256) -> U256 {
+        self.access.get_role_member_count(role)
+    }
+
+    pub fn get_role_member(&self, role: B256, index: U256) -> Result<Address, Error> {
+        self.access.get_role_member(role, index).map_err(Error::from)
+    }
+
+    pub fn get_role_members(&self, role: B256) -> Vec<Address> {
+        self.access.get_role_members(role)
+    }
+
+    pub fn default_admin(&self) -> Address {
+        self.access.default_admin()
+    }
+
+    pub fn pending_default_admin(&self) -> (Address, U256) {
+        self.access.pending_default_admin()
+    }
+
+    pub fn default_admin_delay(&self) -> U256 {
+        self.access.default_admin_delay()
+    }
+
+    pub fn pending_default_admin_delay(&self) -> (U256, U256) {
+        self.access.pending_default_admin_delay()
+    }
+
+    pub fn begin_default_admin_transfer(&mut self, new_admin: Address) -> Result<(), Error> {
+        self.access.begin_default_admin_transfer(new_admin).map_err(Error::from)
+    }
+
+    pub fn cancel_default_admin_transfer(&mut self) -> Result<(), Error> {
+        self.access.cancel_default_admin_transfer().map_err(Error::from)
+    }
+
+    pub fn accept_default_admin_transfer(&mut self) -> Result<(), Error> {
+        self.access.accept_default_admin_transfer().map_err(Error::from)
+    }
+
+    pub fn change_default_admin_delay(&mut self, new_delay: U256) -> Result<(), Error> {
+        self.access.change_default_admin_delay(new_delay).map_err(Error::from)
+    }
+
+    pub fn rollback_default_admin_delay(&mut self) -> Result<(), Error> {
+        self.access.rollback_default_admin_delay().map_err(Error::from)
+    }
+
+    pub fn get_role_admin(&self, role: B256) -> B256 {
+        self.access.get_role_admin(role)
+    }
+
+    pub fn set_role_admin(&mut self, role: B256, admin_role: B256) -> Result<(), Error> {
+        self.access.set_role_admin(role, admin_role).map_err(Error::from)
+    }
+
+    pub fn grant_role(&mut self, role: B256, account: Address) -> Result<(), Error> {
+        self.access.grant_role(role, account).map_err(Error::from)
+    }
+
+    pub fn revoke_role(&mut self, role: B256, account: Address) -> Result<(), Error> {
+        self.access.revoke_role(role, account).map_err(Error::from)
+    }
+
+    pub fn renounce_role(&mut self, role: B256, confirmation: Address) -> Result<(), Error> {
+        self.access.renounce_role(role, confirmation).map_err(Error::from)
+    }
+
+    pub fn multisig_signers(&self) -> Vec<Address> {
+        self.multisig.signers()
+    }
+
+    pub fn multisig_threshold(&self) -> U256 {
+        self.multisig.threshold()
+    }
+
+    pub fn multisig_proposal(&self, proposal_id: U256) -> (U256, U256, bool) {
+        self.multisig.proposal(proposal_id)
+    }
+
+    pub fn multisig_confirmed(&self, proposal_id: U256, signer: Address) -> bool {
+        self.multisig.is_confirmed(proposal_id, signer)
+    }
+
+    pub fn configure_multisig(&mut self, signers: Vec<Address>, threshold: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.multisig.configure(signers, threshold).map_err(Error::from)
+    }
+
+    pub fn propose(&mut self, data: Bytes, expiry: U256) -> Result<U256, Error> {
+        self.multisig.propose(data.0, expiry).map_err(Error::from)
+    }
+
+    pub fn confirm(&mut self, proposal_id: U256) -> Result<(), Error> {
+        self.multisig.confirm(proposal_id).map_err(Error::from)
+    }
+
+    pub fn revoke_confirmation(&mut self, proposal_id: U256) -> Result<(), Error> {
+        self.multisig.revoke_confirmation(proposal_id).map_err(Error::from)
+    }
+
+    /// Calls this contract with the proposal's calldata; a revert is bubbled up unchanged.
+    /// Deliberately unguarded: the call re-enters this contract by design, and whatever
+    /// entrypoint it reaches takes the guard itself. The proposal is marked executed
+    /// before the call, so it can't be replayed from inside it.
+    pub fn execute(&mut self, proposal_id: U256) -> Result<Bytes, Error> {
+        let data = self.multisig._take_for_execution(proposal_id)?;
+        let contract = self.vm().contract_address();
+        let result = call::call(Call::new_in(self), contract, &data)?;
+        Ok(result.into())
+    }
+
+    pub fn allowlist_enabled(&self) -> bool {
+        self.allowlist.is_enabled()
+    }
+
+    pub fn is_allowlisted(&self, account: Address) -> bool {
+        self.allowlist.is_allowlisted(account)
+    }
+
+    pub fn set_allowlist_enabled(&mut self, enabled: bool) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.allowlist.set_enabled(enabled);
+        Ok(())
+    }
+
+    pub fn set_allowlisted(&mut self, accounts: Vec<Address>, allowed: bool) -> Result<(), Error> {
+        self.access.only_role(ALLOWLIST_ADMIN_ROLE)?;
+        for account in accounts {
+            self.allowlist.set_allowlisted(account, allowed);
+        }
+        Ok(())
+    }
+
+    pub fn attester(&self) -> Address {
+        self.attestations.attester()
+    }
+
+    pub fn required_attestation(&self, id: U256) -> B256 {
+        self.attestations.required_schema(id)
+    }
+
+    pub fn set_attester(&mut self, attester: Address) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.attestations.set_attester(attester);
+        Ok(())
+    }
+
+    pub fn set_required_attestation(&mut self, id: U256, schema: B256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.attestations.set_required_schema(id, schema);
+        Ok(())
+    }
+
+    pub fn is_transfer_locked(&self, id: U256) -> bool {
+        self.soulbound.is_locked(id)
+    }
+
+    /// Locked ids can only be minted, burned or moved by guardian recovery.
+    pub fn set_transfer_lock(&mut self, id: U256, locked: bool) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.soulbound.set_locked(id, locked);
+        Ok(())
+    }
+
+    pub fn guardians(&self, holder: Address) -> Vec<Address> {
+        self.recovery.guardians(holder)
+    }
+
+    pub fn recovery_config(&self, holder: Address) -> (U256, U256) {
+        self.recovery.config(holder)
+    }
+
+    pub fn recovery_status(&self, holder: Address) -> (Address, U256, U256) {
+        self.recovery.status(holder)
+    }
+
+    pub fn recovery_approvals(&self, holder: Address, successor: Address) -> U256 {
+        self.recovery.approvals(holder, successor)
+    }
+
+    pub fn configure_recovery(&mut self, guardians: Vec<Address>, threshold: U256, delay: U256) -> Result<(), Error> {
+        self.recovery.configure(guardians, threshold, delay).map_err(Error::from)
+    }
+
+    pub fn approve_recovery(&mut self, holder: Address, successor: Address) -> Result<(), Error> {
+        self.recovery.approve(holder, successor).map_err(Error::from)
+    }
+
+    pub fn cancel_recovery(&mut self) {
+        self.recovery.cancel()
+    }
+
+    /// Moves `holder`'s full balance of each listed id, bound or not, to the approved
+    /// successor. Only the successor or a guardian can call it, and it closes the
+    /// recovery, so every id must be listed at once. Builds with the `enumerable`
+    /// feature ignore `ids` and move every id the holder owns.
+    pub fn execute_recovery(&mut self, holder: Address, ids: Vec<U256>) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_TRANSFER)?;
+            let ids = if cfg!(feature = "enumerable") {
+                this.erc1155.enumerable().ids_of(holder)
+            } else {
+                let mut unique: Vec<U256> = Vec::new();
+                for id in this.aliases.resolve_all(ids) {
+                    if !unique.contains(&id) {
+                        unique.push(id);
+                    }
+                }
+                unique
+            };
+            let successor = this.recovery._execute(holder, &ids)?;
+            this._check_receiver(successor, &ids)?;
+            let values: Vec<U256> = ids.iter().map(|id| this.erc1155.balance_of(holder, *id)).collect();
+            this._update(holder, successor, ids.clone(), values.clone(), Vec::new())?;
+            this._after_transfer(&ids, &values);
+            Ok(())
+        })
+    }
+
+    pub fn pause_flags(&self) -> U256 {
+        self.pausable.flags()
+    }
+
+    pub fn is_paused(&self, mask: U256) -> bool {
+        self.pausable.is_paused(mask)
+    }
+
+    pub fn paused(&self) -> bool {
+        self.pausable.paused()
+    }
+
+    /// Emergency stop: every balance change reverts with `EnforcedPause` until `unpause`.
+    pub fn pause(&mut self) -> Result<(), Error> {
+        self.access.only_role(PAUSER_ROLE)?;
+        self.pausable.pause().map_err(Error::from)
+    }
+
+    pub fn unpause(&mut self) -> Result<(), Error> {
+        self.access.only_role(PAUSER_ROLE)?;
+        self.pausable.unpause().map_err(Error::from)
+    }
+
+    /// Bit 0 mint, 1 burn, 2 transfer, 3 marketplace, 4 staking.
+    pub fn set_pause_flags(&mut self, flags: U256) -> Result<(), Error> {
+        self.access.only_role(PAUSER_ROLE)?;
+        self.pausable.set_flags(flags);
+        Ok(())
+    }
+
+    pub fn circuit_breaker_threshold(&self, id: U256) -> U256 {
+        self.circuit_breaker.threshold(id)
+    }
+
+    pub fn current_transfer_volume(&self, id: U256) -> U256 {
+        self.circuit_breaker.current_volume(id)
+    }
+
+    pub fn set_circuit_breaker_threshold(&mut self, id: U256, threshold: U256) -> Result<(), Error> {
+        self.access.only_role(PAUSER_ROLE)?;
+        self.circuit_breaker.set_threshold(id, threshold);
+        Ok(())
+    }
+
+    /// Guardian reset after the breaker has tripped and the incident is handled.
+    pub fn reset_circuit_breaker(&mut self) -> Result<(), Error> {
+        self.access.only_role(PAUSER_ROLE)?;
+        let flags = self.pausable.flags() & !PAUSE_TRANSFER;
+        self.pausable.set_flags(flags);
+        Ok(())
+    }
+
+    /// Minter-role issuance, subject to the mint pause, the caller's minter scope, the
+    /// receiver checks and the id's supply cap.
+    pub fn mint(&mut self, to: Address, id: U256, value: U256, data: Bytes) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let ids = this._before_mint(to, vec![id], &[value])?;
+            this._mint(to, ids[0], value, data.to_vec()).map_err(Error::from)
+        })
+    }
+
+    pub fn mint_batch(&mut self, to: Address, ids: Vec<U256>, values: Vec<U256>, data: Bytes) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let ids = this._before_mint(to, ids, &values)?;
+            this._mint_batch(to, ids, values, data.to_vec()).map_err(Error::from)
+        })
+    }
+
+    /// Airdrop: mints `amounts[i]` of `id` to each `recipients[i]` in one transaction,
+    /// under the same checks as `mint`.
+    pub fn mint_to_many(&mut self, recipients: Vec<Address>, id: U256, amounts: Vec<U256>) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.access.only_role(MINTER_ROLE)?;
+            this.pausable.when_not_paused(PAUSE_MINT)?;
+            let id = this.aliases.resolve(id);
+            this.minter_scopes.check(this.vm().msg_sender(), &[id])?;
+            for &to in &recipients {
+                this._check_receiver(to, &[id])?;
+            }
+            let total = amounts.iter().fold(U256::ZERO, |acc, a| acc.saturating_add(*a));
+            this._check_supply_cap(id, total)?;
+            this._mint_to_many(recipients, id, amounts, Vec::new()).map_err(Error::from)
+        })
+    }
+
+    /// Sends the same `ids`/`values` from `from` to every recipient, with the caller as
+    /// operator for all of them.
+    pub fn air_transfer(&mut self, from: Address, recipients: Vec<Address>, ids: Vec<U256>, values: Vec<U256>) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let ids = this.aliases.resolve_all(ids);
+            let operator = this.vm().msg_sender();
+            for &to in &recipients {
+                this._before_transfer(to, &ids)?;
+                this.erc1155._authorize_transfer(operator, from, to)?;
+            }
+            let count = U256::from(recipients.len());
+            let totals: Vec<U256> = values.iter().map(|v| v.saturating_mul(count)).collect();
+            if recipients.contains(&this.vm().contract_address()) {
+                for (&id, &value) in ids.iter().zip(values.iter()) {
+                    this.treasury._deposit(id, value);
+                }
+            }
+            this._air_transfer(from, recipients, ids.clone(), values, Vec::new())?;
+            this._after_transfer(&ids, &totals);
+            Ok(())
+        })
+    }
+
+    /// Mints a minter-signed voucher to its recipient (the caller if unset). The caller
+    /// pays exactly `price`, which is forwarded to the lazy-mint treasury.
+    #[payable]
+    pub fn redeem_voucher(&mut self, voucher: MintVoucher, signature: Bytes) -> Result<(), Error> {
+        self._redeem_voucher(voucher, Address::ZERO, signature)
+    }
+
+    /// [`Self::redeem_voucher`] for a voucher signed with `signer`'s registered passkey;
+    /// the signer can't be recovered from a WebAuthn assertion, so it is named.
+    #[payable]
+    pub fn redeem_passkey_voucher(&mut self, voucher: MintVoucher, signer: Address, signature: Bytes) -> Result<(), Error> {
+        self._redeem_voucher(voucher, signer, signature)
+    }
+
+    pub fn lazy_mint_treasury(&self) -> Address {
+        self.lazy_mint.treasury()
+    }
+
+    pub fn set_lazy_mint_treasury(&mut self, treasury: Address) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.lazy_mint.set_treasury(treasury);
+        Ok(())
+    }
+
+    /// Burns by the holder or one of its approved operators.
+    pub fn burn(&mut self, account: Address, id: U256, value: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this._before_burn(account)?;
+            let id = this.aliases.resolve(id);
+            this._burn(account, id, value).map_err(Error::from)
+        })
     }
-}
 
+    pub fn burn_batch(&mut self, account: Address, ids: Vec<U256>, values: Vec<U256>) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this._before_burn(account)?;
+            let ids = this.aliases.resolve_all(ids);
+            this._burn_batch(account, ids, values).map_err(Error::from)
+        })
+    }
+
+    pub fn is_wrappable(&self, id: U256) -> bool {
+        self.wrapper.is_enabled(self.aliases.resolve(id))
+    }
+
+    /// The ERC-20 facade contract of `id`'s wrapped balances, or zero before the id is
+    /// first made wrappable.
+    pub fn wrapper_facade(&self, id: U256) -> Address {
+        self.wrapper.facade(self.aliases.resolve(id))
+    }
+
+    pub fn wrapper_implementation(&self) -> Address {
+        self.wrapper.facade_implementation()
+    }
+
+    /// Sets the `erc20-facade` deployment that per-id facades are cloned from.
+    pub fn set_wrapper_implementation(&mut self, implementation: Address) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.wrapper.set_facade_implementation(implementation);
+        Ok(())
+    }
+
+    /// Enabling an id for the first time deploys and initializes its facade.
+    pub fn set_wrappable(&mut self, id: U256, enabled: bool) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.access.only_role(DEFAULT_ADMIN_ROLE)?;
+            let id = this.aliases.resolve(id);
+            if enabled && this.wrapper.facade(id).is_zero() {
+                let facade = this.wrapper._deploy_facade(id)?;
+                IErc20Facade::new(facade).initialize(Call::new_in(this), id)?;
+            }
+            this.wrapper.set_enabled(id, enabled);
+            Ok(())
+        })
+    }
+
+    /// Escrows `amount` of the caller's `id` and credits the same wrapped balance.
+    pub fn wrap(&mut self, id: U256, amount: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let id = this.aliases.resolve(id);
+            let account = this.vm().msg_sender();
+            this.pausable.when_not_paused(PAUSE_TRANSFER)?;
+            this.soulbound.check_transferable(&[id])?;
+            this.wrapper._wrap(id, account, amount)?;
+            this._update(account, this.vm().contract_address(), vec![id], vec![amount], Vec::new())?;
+            let facade = this.wrapper.facade(id);
+            IErc20Facade::new(facade).notify_transfer(Call::new_in(this), Address::ZERO, account, amount)?;
+            Ok(())
+        })
+    }
+
+    pub fn unwrap(&mut self, id: U256, amount: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let id = this.aliases.resolve(id);
+            let account = this.vm().msg_sender();
+            this.pausable.when_not_paused(PAUSE_TRANSFER)?;
+            this._check_receiver(account, &[id])?;
+            this.wrapper._unwrap(id, account, amount)?;
+            this._update(this.vm().contract_address(), account, vec![id], vec![amount], Vec::new())?;
+            let facade = this.wrapper.facade(id);
+            IErc20Facade::new(facade).notify_transfer(Call::new_in(this), account, Address::ZERO, amount)?;
+            Ok(())
+        })
+    }
+
+    pub fn wrapped_total_supply(&self, id: U256) -> U256 {
+        self.wrapper.total_supply(self.aliases.resolve(id))
+    }
+
+    pub fn wrapped_balance_of(&self, id: U256, account: Address) -> U256 {
+        self.wrapper.balance_of(self.aliases.resolve(id), account)
+    }
+
+    pub fn wrapped_allowance(&self, id: U256, owner: Address, spender: Address) -> U256 {
+        self.wrapper.allowance(self.aliases.resolve(id), owner, spender)
+    }
+
+    /// Facade-only: the ledger side of its `transfer`. Runs the same checks as a transfer
+    /// of the underlying units.
+    pub fn facade_transfer(&mut self, from: Address, to: Address, amount: U256) -> Result<bool, Error> {
+        self._non_reentrant(|this| {
+            let id = this.wrapper.facade_id(this.vm().msg_sender())?;
+            this._before_transfer(to, &[id])?;
+            this.wrapper._transfer(id, from, to, amount)?;
+            Ok(true)
+        })
+    }
+
+    /// Facade-only: the ledger side of its `transferFrom` by `spender`.
+    pub fn facade_transfer_from(&mut self, spender: Address, from: Address, to: Address, amount: U256) -> Result<bool, Error> {
+        self._non_reentrant(|this| {
+            let id = this.wrapper.facade_id(this.vm().msg_sender())?;
+            this._before_transfer(to, &[id])?;
+            this.wrapper._spend_allowance(id, from, spender, amount)?;
+            this.wrapper._transfer(id, from, to, amount)?;
+            Ok(true)
+        })
+    }
+
+    /// Facade-only: the ledger side of its `approve`.
+    pub fn facade_approve(&mut self, owner: Address, spender: Address, amount: U256) -> Result<bool, Error> {
+        self._non_reentrant(|this| {
+            let id = this.wrapper.facade_id(this.vm().msg_sender())?;
+            this.wrapper._approve(id, owner, spender, amount)?;
+            Ok(true)
+        })
+    }
+
+    /// Game-server burn of an account's consumable. Logging depends on the build features.
+    pub fn consume(&mut self, account: Address, id: U256, amount: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.access.only_role(GAME_ROLE)?;
+            this.pausable.when_not_paused(PAUSE_BURN)?;
+            if account.is_zero() {
+                return Err(Erc1155Error::InvalidSender(ERC1155InvalidSender { sender: account }).into());
+            }
+            let id = this.aliases.resolve(id);
+            this._apply_single(account, Address::ZERO, id, amount)?;
+            game::log_action(this.vm(), ACTION_CONSUME, account, id, amount);
+            Ok(())
+        })
+    }
+
+    /// Game-server mint (XP, drops) to many accounts. Logging depends on the build features;
+    /// the receiver checks and acceptance call are those of `mint`.
+    pub fn grant(&mut self, accounts: Vec<Address>, id: U256, amounts: Vec<U256>) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.access.only_role(GAME_ROLE)?;
+            if accounts.len() != amounts.len() {
+                return Err(Erc1155Error::InvalidArrayLength(ERC1155InvalidArrayLength {
+                    idsLength: U256::from(accounts.len()),
+                    valuesLength: U256::from(amounts.len()),
+                })
+                .into());
+            }
+            let id = this.aliases.resolve(id);
+            let total = amounts.iter().fold(U256::ZERO, |acc, a| acc.saturating_add(*a));
+            for &account in &accounts {
+                if account.is_zero() {
+                    return Err(Erc1155Error::InvalidReceiver(ERC1155InvalidReceiver { receiver: account }).into());
+                }
+                this._check_mint(this.vm().msg_sender(), account, vec![id], &[total])?;
+            }
+            for (account, amount) in accounts.into_iter().zip(amounts) {
+                this._apply_single(Address::ZERO, account, id, amount)?;
+                game::log_action(this.vm(), ACTION_GRANT, account, id, amount);
+                this._check_on_received(Address::ZERO, account, vec![id], vec![amount], Vec::new())?;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn scheduled_emission_ids(&self) -> Vec<U256> {
+        self.emissions.scheduled_ids()
+    }
+
+    /// Returns `(start, epoch_length, amount_per_epoch, epochs, epochs_emitted)`.
+    pub fn emission_schedule(&self, id: U256) -> (U256, U256, U256, U256, U256) {
+        self.emissions.schedule(self.aliases.resolve(id))
+    }
+
+    pub fn emission_recipients(&self, id: U256) -> (Vec<Address>, Vec<U256>) {
+        self.emissions.recipients(self.aliases.resolve(id))
+    }
+
+    pub fn pending_emission_epochs(&self, id: U256) -> U256 {
+        self.emissions.pending_epochs(self.aliases.resolve(id))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn configure_emission(
+        &mut self,
+        id: U256,
+        start: U256,
+        epoch_length: U256,
+        amount_per_epoch: U256,
+        epochs: U256,
+        recipients: Vec<Address>,
+        shares: Vec<U256>,
+    ) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        let id = self.aliases.resolve(id);
+        self.emissions
+            .configure(id, start, epoch_length, amount_per_epoch, epochs, recipients, shares)?;
+        Ok(())
+    }
+
+    /// Permissionless: mints as many of `id`'s elapsed epochs as its supply cap has room
+    /// for, oldest first; the rest stay pending. Reverts if not even one epoch fits, and
+    /// only stops the schedule when the cap is below a single epoch's amount.
+    pub fn tick(&mut self, id: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_MINT)?;
+            let id = this.aliases.resolve(id);
+            let pending = this.emissions.pending_epochs(id);
+            if pending.is_zero() {
+                return Ok(());
+            }
+            this.curves.check_external_mint(id)?;
+            let per_epoch = this.emissions.schedule(id).2;
+            let cap = this.reserves.max_supply(id);
+            if !cap.is_zero() && cap < per_epoch {
+                this.emissions._stop(id);
+                return Ok(());
+            }
+            let available = this.reserves.available(id, this.erc1155.total_supply(id));
+            let fits = if per_epoch.is_zero() { pending } else { pending.min(available / per_epoch) };
+            if fits.is_zero() {
+                this._check_supply_cap(id, per_epoch)?;
+            }
+            for (recipient, amount) in this.emissions._tick(id, fits) {
+                if !amount.is_zero() {
+                    this._check_receiver(recipient, &[id])?;
+                    this._update(Address::ZERO, recipient, vec![id], vec![amount], Vec::new())?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    pub fn treasury_governor(&self) -> Address {
+        self.treasury.governor()
+    }
+
+    pub fn treasury_delay(&self) -> U256 {
+        self.treasury.delay()
+    }
+
+    /// Units of `id` custodied by the treasury; transfers to this contract are booked here.
+    pub fn treasury_balance(&self, id: U256) -> U256 {
+        self.treasury.held(id)
+    }
+
+    /// Returns `(to, ids, amounts, eta, executed, cancelled)`.
+    pub fn treasury_proposal(&self, proposal_id: U256) -> (Address, Vec<U256>, Vec<U256>, U256, bool, bool) {
+        self.treasury.proposal(proposal_id)
+    }
+
+    /// The default admin installs the first governor; after that only the governor can hand over.
+    pub fn set_treasury_governor(&mut self, governor: Address) -> Result<(), Error> {
+        if self.treasury.governor().is_zero() {
+            self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        } else {
+            self.treasury.only_governor()?;
+        }
+        self.treasury._set_governor(governor);
+        Ok(())
+    }
+
+    pub fn set_treasury_delay(&mut self, delay: U256) -> Result<(), Error> {
+        self.treasury.set_delay(delay).map_err(Error::from)
+    }
+
+    pub fn queue_treasury_transfer(&mut self, to: Address, ids: Vec<U256>, amounts: Vec<U256>) -> Result<U256, Error> {
+        let ids = self.aliases.resolve_all(ids);
+        self.treasury.queue(to, ids, amounts).map_err(Error::from)
+    }
+
+    pub fn cancel_treasury_transfer(&mut self, proposal_id: U256) -> Result<(), Error> {
+        self.treasury.cancel(proposal_id).map_err(Error::from)
+    }
+
+    /// Anyone can release a queued transfer once its timelock has passed.
+    pub fn execute_treasury_transfer(&mut self, proposal_id: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let (to, ids, amounts) = this.treasury._execute(proposal_id)?;
+            this._before_transfer(to, &ids)?;
+            this._update(this.vm().contract_address(), to, ids.clone(), amounts.clone(), Vec::new())?;
+            this._after_transfer(&ids, &amounts);
+            Ok(())
+        })
+    }
+
+    /// Returns `(creator, price, dispute_window)` of `id`'s escrowed sale.
+    pub fn sale_config(&self, id: U256) -> (Address, U256, U256) {
+        self.sales.config(self.aliases.resolve(id))
+    }
+
+    /// Returns `(buyer, creator, id, amount, price, refundable_until)`.
+    pub fn purchase(&self, purchase_id: U256) -> (Address, Address, U256, U256, U256, U256) {
+        self.sales.purchase(purchase_id)
+    }
+
+    pub fn sale_proceeds_owed(&self, creator: Address) -> U256 {
+        self.sales.owed(creator)
+    }
+
+    pub fn configure_sale(&mut self, id: U256, creator: Address, price: U256, dispute_window: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.sales.configure(self.aliases.resolve(id), creator, price, dispute_window);
+        Ok(())
+    }
+
+    /// Mints `amount` of `id` for exactly `price * amount` wei, held in escrow.
+    #[payable]
+    pub fn buy_escrowed(&mut self, id: U256, amount: U256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_MINT)?;
+            let buyer = this.vm().msg_sender();
+            let id = this.aliases.resolve(id);
+            this._check_receiver(buyer, &[id])?;
+            this._check_supply_cap(id, amount)?;
+            let purchase_id = this.sales._buy(id, amount, this.vm().msg_value())?;
+            this._update(Address::ZERO, buyer, vec![id], vec![amount], Vec::new())?;
+            Ok(purchase_id)
+        })
+    }
+
+    /// Burns `amount` of a purchase during its dispute window and refunds what was paid.
+    /// Deliberately not subject to the burn pause.
+    pub fn refund_purchase(&mut self, purchase_id: U256, amount: U256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            let buyer = this.vm().msg_sender();
+            let (id, refund) = this.sales._refund(purchase_id, amount)?;
+            this._update(buyer, Address::ZERO, vec![id], vec![amount], Vec::new())?;
+            if !refund.is_zero() && transfer_eth(buyer, refund).is_err() {
+                return Err(SaleError::PaymentFailed(SalePaymentFailed { to: buyer, amount: refund }).into());
+            }
+            Ok(refund)
+        })
+    }
+
+    pub fn release_sale_proceeds(&mut self, purchase_ids: Vec<U256>) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let share_bps = this.buyback.sale_share_bps();
+            let mut cut = U256::ZERO;
+            for purchase_id in purchase_ids {
+                cut += this.sales.release(purchase_id, share_bps)?;
+            }
+            if !cut.is_zero() {
+                this.buyback._fund(this.vm().contract_address(), cut);
+            }
+            Ok(())
+        })
+    }
+
+    pub fn withdraw_sale_proceeds(&mut self) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            let creator = this.vm().msg_sender();
+            let amount = this.sales._withdraw();
+            if !amount.is_zero() && transfer_eth(creator, amount).is_err() {
+                return Err(SaleError::PaymentFailed(SalePaymentFailed { to: creator, amount }).into());
+            }
+            Ok(amount)
+        })
+    }
+
+    /// Cap on `id`'s supply including reservations; zero means uncapped.
+    pub fn max_supply(&self, id: U256) -> U256 {
+        self.reserves.max_supply(self.aliases.resolve(id))
+    }
+
+    pub fn reserved_supply(&self, id: U256) -> U256 {
+        self.reserves.reserved(self.aliases.resolve(id))
+    }
+
+    pub fn reserved_allocation(&self, id: U256, beneficiary: Address) -> U256 {
+        self.reserves.allocation(self.aliases.resolve(id), beneficiary)
+    }
+
+    pub fn set_max_supply(&mut self, id: U256, max_supply: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        let id = self.aliases.resolve(id);
+        let supply = self.erc1155.total_supply(id);
+        self.reserves.set_max_supply(id, max_supply, supply).map_err(Error::from)
+    }
+
+    /// Earmarks `amount` of `id` for `beneficiary`, counted against the cap until claimed.
+    pub fn reserve(&mut self, id: U256, amount: U256, beneficiary: Address) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        let id = self.aliases.resolve(id);
+        let supply = self.erc1155.total_supply(id);
+        self.reserves.reserve(id, amount, beneficiary, supply).map_err(Error::from)
+    }
+
+    /// Mints `amount` of the caller's reserved allocation of `id`.
+    pub fn claim_reserved(&mut self, id: U256, amount: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_MINT)?;
+            let beneficiary = this.vm().msg_sender();
+            let id = this.aliases.resolve(id);
+            this._check_receiver(beneficiary, &[id])?;
+            this.curves.check_external_mint(id)?;
+            this.reserves._claim(id, amount)?;
+            this._update(Address::ZERO, beneficiary, vec![id], vec![amount], Vec::new())?;
+            Ok(())
+        })
+    }
+
+    /// Returns `(scoped, ids, has_range, range_start, range_end)`; unscoped minters may mint any id.
+    pub fn minter_scope(&self, minter: Address) -> (bool, Vec<U256>, bool, U256, U256) {
+        self.minter_scopes.scope(minter)
+    }
+
+    pub fn can_mint(&self, minter: Address, id: U256) -> bool {
+        self.minter_scopes.can_mint(minter, id)
+    }
+
+    /// Restricts `minter` to an explicit id set (plus its range, if one is set).
+    pub fn set_minter_scope(&mut self, minter: Address, ids: Vec<U256>) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.minter_scopes.set_ids(minter, ids);
+        Ok(())
+    }
+
+    pub fn set_minter_range(&mut self, minter: Address, start: U256, end: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.minter_scopes.set_range(minter, start, end).map_err(Error::from)
+    }
+
+    pub fn clear_minter_scope(&mut self, minter: Address) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.minter_scopes.clear(minter);
+        Ok(())
+    }
+
+    pub fn order(&self, order_id: U256) -> (Address, U256, U256, U256, U256, U256) {
+        self.order_book.order(order_id)
+    }
+
+    pub fn order_count(&self) -> U256 {
+        self.order_book.order_count()
+    }
+
+    /// Escrows `give_amount` of `give_id` and offers it for `want_amount` of `want_id`.
+    pub fn post_order(&mut self, give_id: U256, give_amount: U256, want_id: U256, want_amount: U256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_MARKETPLACE)?;
+            let give_id = this.aliases.resolve(give_id);
+            let want_id = this.aliases.resolve(want_id);
+            this.soulbound.check_transferable(&[give_id])?;
+            let order_id = this.order_book._post(give_id, give_amount, want_id, want_amount)?;
+            this._update(this.vm().msg_sender(), this.vm().contract_address(), vec![give_id], vec![give_amount], Vec::new())?;
+            Ok(order_id)
+        })
+    }
+
+    /// Takes `amount` of an order's give side, paying the pro-rata want side to the maker.
+    pub fn fill_order(&mut self, order_id: U256, amount: U256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_MARKETPLACE)?;
+            let taker = this.vm().msg_sender();
+            let fill = this.order_book._fill(order_id, amount)?;
+            this._before_transfer(fill.maker, &[fill.want_id])?;
+            this._check_receiver(taker, &[fill.give_id])?;
+            this._update(taker, fill.maker, vec![fill.want_id], vec![fill.want_paid], Vec::new())?;
+            this._update(this.vm().contract_address(), taker, vec![fill.give_id], vec![amount], Vec::new())?;
+            this._after_transfer(&[fill.want_id, fill.give_id], &[fill.want_paid, amount]);
+            Ok(fill.want_paid)
+        })
+    }
+
+    pub fn cancel_order(&mut self, order_id: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let (give_id, remaining) = this.order_book._cancel(order_id)?;
+            this._update(this.vm().contract_address(), this.vm().msg_sender(), vec![give_id], vec![remaining], Vec::new())?;
+            Ok(())
+        })
+    }
+
+    /// Settles a maker-signed swap: the caller pays the want side (tokens and exactly
+    /// `wantEth`) and receives the give side, all in one call. Royalties on the give ids
+    /// come out of the maker's ETH, split across them by unit count; see
+    /// [`royalty::apportion`] for what that means for mixed bundles.
+    #[payable]
+    pub fn fill_swap(&mut self, order: SwapOrder, maker_signature: Bytes) -> Result<(), Error> {
+        self._non_reentrant(|this| this._fill_swap(order, &maker_signature))
+    }
+
+    #[payable]
+    #[selector(name = "fillSwap")]
+    pub fn fill_swap_vrs(&mut self, order: SwapOrder, v: u8, r: B256, s: B256) -> Result<(), Error> {
+        self._non_reentrant(|this| this._fill_swap(order, &ecdsa::join(v, r, s)))
+    }
+
+    pub fn royalty_info(&self, id: U256, sale_price: U256) -> (Address, U256) {
+        self.royalties.royalty_info(self.aliases.resolve(id), sale_price)
+    }
+
+    /// Denominator of every fee numerator, i.e. numerators are basis points.
+    pub fn fee_denominator(&self) -> U256 {
+        FEE_DENOMINATOR
+    }
+
+    pub fn royalty_registry(&self) -> Address {
+        self.royalties.registry()
+    }
+
+    pub fn royalty_source(&self, id: U256) -> (Address, U256) {
+        self.royalties.source(id)
+    }
+
+    pub fn set_default_royalty(&mut self, receiver: Address, fee_numerator: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.royalties.set_default_royalty(receiver, fee_numerator).map_err(Error::from)
+    }
+
+    pub fn set_token_royalty(&mut self, id: U256, receiver: Address, fee_numerator: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.royalties.set_token_royalty(id, receiver, fee_numerator).map_err(Error::from)
+    }
+
+    pub fn delete_default_royalty(&mut self) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.royalties.delete_default_royalty();
+        Ok(())
+    }
+
+    pub fn reset_token_royalty(&mut self, id: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.royalties.reset_token_royalty(id);
+        Ok(())
+    }
+
+    /// Registry consulted for ids without a local royalty; zero disables the fallback.
+    pub fn set_royalty_registry(&mut self, registry: Address) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.royalties.set_registry(registry);
+        Ok(())
+    }
+
+    /// Records the original collection and token id a wrapped `id` came from.
+    pub fn set_royalty_source(&mut self, id: U256, collection: Address, token_id: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.royalties.set_source(id, collection, token_id);
+        Ok(())
+    }
+
+    pub fn otc_deal(&self, deal_id: U256) -> (Address, Address, bool, bool, bool) {
+        self.otc.deal(deal_id)
+    }
+
+    pub fn otc_terms(&self, deal_id: U256) -> (Vec<U256>, Vec<U256>, Vec<U256>, Vec<U256>) {
+        self.otc.terms(deal_id)
+    }
+
+    /// Proposes a two-sided escrowed swap with `counterparty`; each side then deposits.
+    pub fn create_otc(
+        &mut self,
+        counterparty: Address,
+        give_ids: Vec<U256>,
+        give_amounts: Vec<U256>,
+        want_ids: Vec<U256>,
+        want_amounts: Vec<U256>,
+    ) -> Result<U256, Error> {
+        let give_ids = self.aliases.resolve_all(give_ids);
+        let want_ids = self.aliases.resolve_all(want_ids);
+        self.otc._create(counterparty, give_ids, give_amounts, want_ids, want_amounts).map_err(Error::from)
+    }
+
+    pub fn deposit_otc(&mut self, deal_id: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_MARKETPLACE)?;
+            let leg = this.otc._deposit(deal_id)?;
+            this.soulbound.check_transferable(&leg.ids)?;
+            if !leg.ids.is_empty() {
+                this._update(leg.party, this.vm().contract_address(), leg.ids, leg.amounts, Vec::new())?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Either party settles a fully funded deal; each receives the other's deposit.
+    pub fn execute_otc(&mut self, deal_id: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_MARKETPLACE)?;
+            let (a, b) = this.otc._execute(deal_id)?;
+            for (leg, to) in [(a.clone(), b.party), (b, a.party)] {
+                if !leg.ids.is_empty() {
+                    this._check_receiver(to, &leg.ids)?;
+                    this._update(this.vm().contract_address(), to, leg.ids.clone(), leg.amounts.clone(), Vec::new())?;
+                    this._after_transfer(&leg.ids, &leg.amounts);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Either party backs out before both sides are funded; deposits are returned.
+    pub fn cancel_otc(&mut self, deal_id: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            for leg in this.otc._cancel(deal_id)? {
+                if !leg.ids.is_empty() {
+                    this._update(this.vm().contract_address(), leg.party, leg.ids, leg.amounts, Vec::new())?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns `(kind, base_price, factor, supply, reserve)`; kind 1 is linear, 2 exponential.
+    pub fn curve_config(&self, id: U256) -> (u8, U256, U256, U256, U256) {
+        self.curves.config(self.aliases.resolve(id))
+    }
+
+    pub fn curve_spot_price(&self, id: U256) -> Result<U256, Error> {
+        self.curves.spot_price(self.aliases.resolve(id)).map_err(Error::from)
+    }
+
+    pub fn quote_curve_mint(&self, id: U256, amount: U256) -> Result<U256, Error> {
+        self.curves.quote_mint(self.aliases.resolve(id), amount).map_err(Error::from)
+    }
+
+    pub fn quote_curve_burn(&self, id: U256, amount: U256) -> Result<U256, Error> {
+        self.curves.quote_burn(self.aliases.resolve(id), amount).map_err(Error::from)
+    }
+
+    pub fn configure_curve(&mut self, id: U256, kind: u8, base_price: U256, factor: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        let id = self.aliases.resolve(id);
+        let supply = self.erc1155.total_supply(id);
+        self.curves.configure(id, kind, base_price, factor, supply).map_err(Error::from)
+    }
+
+    /// Mints `amount` of `id` at the curve price. Any ETH sent above the cost is refunded.
+    #[payable]
+    pub fn curve_mint(&mut self, id: U256, amount: U256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_MINT)?;
+            let account = this.vm().msg_sender();
+            let value = this.vm().msg_value();
+            let id = this.aliases.resolve(id);
+            this._check_receiver(account, &[id])?;
+            this.reserves.check_mint(id, amount, this.erc1155.total_supply(id))?;
+            let cost = this.curves._mint(id, amount, value)?;
+            this._update(Address::ZERO, account, vec![id], vec![amount], Vec::new())?;
+            this._record_curve_price(id);
+            let refund = value - cost;
+            if !refund.is_zero() && transfer_eth(account, refund).is_err() {
+                return Err(CurveError::PaymentFailed(CurvePaymentFailed { to: account, amount: refund }).into());
+            }
+            Ok(cost)
+        })
+    }
+
+    /// Burns `amount` of `id` back into the curve for at least `min_proceeds` wei.
+    pub fn curve_burn_for_eth(&mut self, id: U256, amount: U256, min_proceeds: U256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_BURN)?;
+            let account = this.vm().msg_sender();
+            let id = this.aliases.resolve(id);
+            let proceeds = this.curves._burn(id, amount, min_proceeds)?;
+            this._update(account, Address::ZERO, vec![id], vec![amount], Vec::new())?;
+            this._record_curve_price(id);
+            if transfer_eth(account, proceeds).is_err() {
+                return Err(CurveError::PaymentFailed(CurvePaymentFailed { to: account, amount: proceeds }).into());
+            }
+            Ok(proceeds)
+        })
+    }
+
+    /// Returns `(owner, id, kind, spot_price, delta, fee_bps, eth_reserve, token_reserve)`.
+    pub fn pool(&self, pool_id: U256) -> (Address, U256, u8, U256, U256, U256, U256, U256) {
+        self.pools.pool(pool_id)
+    }
+
+    pub fn pool_count(&self) -> U256 {
+        self.pools.pool_count()
+    }
+
+    /// Returns `(cost, fee, royalty)` of buying `amount` units; the cost includes both.
+    pub fn quote_pool_buy(&self, pool_id: U256, amount: U256) -> Result<(U256, U256, U256), Error> {
+        let quote = self.pools.quote_buy(pool_id, amount)?;
+        let price = quote.eth + quote.fee;
+        let (_, royalty) = self.royalties.settlement(self.pools.pool(pool_id).1, price);
+        Ok((price + royalty, quote.fee, royalty))
+    }
+
+    /// Returns `(proceeds, fee, royalty)` of selling `amount` units; the proceeds are net of both.
+    pub fn quote_pool_sell(&self, pool_id: U256, amount: U256) -> Result<(U256, U256, U256), Error> {
+        let quote = self.pools.quote_sell(pool_id, amount)?;
+        let price = quote.eth - quote.fee;
+        let (_, royalty) = self.royalties.settlement(self.pools.pool(pool_id).1, price);
+        Ok((price - royalty, quote.fee, royalty))
+    }
+
+    /// Opens a pool for `id` seeded with `token_amount` of the caller's units and the ETH sent.
+    #[payable]
+    pub fn create_pool(
+        &mut self,
+        id: U256,
+        kind: u8,
+        spot_price: U256,
+        delta: U256,
+        fee_bps: U256,
+        token_amount: U256,
+    ) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_MARKETPLACE)?;
+            let id = this.aliases.resolve(id);
+            this.soulbound.check_transferable(&[id])?;
+            let pool_id = this.pools._create(id, kind, spot_price, delta, fee_bps)?;
+            this.pools._rebalance(pool_id, this.vm().msg_value(), token_amount, U256::ZERO, U256::ZERO)?;
+            this._update(this.vm().msg_sender(), this.vm().contract_address(), vec![id], vec![token_amount], Vec::new())?;
+            Ok(pool_id)
+        })
+    }
+
+    /// Owner top-up of a pool with units and/or ETH.
+    #[payable]
+    pub fn deposit_pool(&mut self, pool_id: U256, token_amount: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let id = this.pools._rebalance(pool_id, this.vm().msg_value(), token_amount, U256::ZERO, U256::ZERO)?;
+            this.soulbound.check_transferable(&[id])?;
+            this._update(this.vm().msg_sender(), this.vm().contract_address(), vec![id], vec![token_amount], Vec::new())?;
+            Ok(())
+        })
+    }
+
+    /// Owner withdrawal of units and/or ETH, including accrued fees.
+    pub fn withdraw_pool(&mut self, pool_id: U256, token_amount: U256, eth_amount: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let owner = this.vm().msg_sender();
+            let id = this.pools._rebalance(pool_id, U256::ZERO, U256::ZERO, eth_amount, token_amount)?;
+            this._update(this.vm().contract_address(), owner, vec![id], vec![token_amount], Vec::new())?;
+            if !eth_amount.is_zero() && transfer_eth(owner, eth_amount).is_err() {
+                return Err(PoolError::PaymentFailed(PoolPaymentFailed { to: owner, amount: eth_amount }).into());
+            }
+            Ok(())
+        })
+    }
+
+    /// Buys `amount` units from a pool; the royalty is charged on top of the pool's price.
+    /// Any ETH sent above the total is refunded.
+    #[payable]
+    pub fn pool_buy(&mut self, pool_id: U256, amount: U256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_MARKETPLACE)?;
+            let trader = this.vm().msg_sender();
+            let value = this.vm().msg_value();
+            let (id, cost) = this.pools._buy(pool_id, amount, value)?;
+            this.oracle._update_from_pool(id, pool_id, this.pools.spot_price(pool_id));
+            this.soulbound.check_transferable(&[id])?;
+            this._check_receiver(trader, &[id])?;
+            this._update(this.vm().contract_address(), trader, vec![id], vec![amount], Vec::new())?;
+            this._after_transfer(&[id], &[amount]);
+            let total = cost + this._pay_royalty(id, cost)?;
+            if total > value {
+                return Err(PoolError::Slippage(PoolSlippage { ethAmount: total, limit: value }).into());
+            }
+            let refund = value - total;
+            if !refund.is_zero() && transfer_eth(trader, refund).is_err() {
+                return Err(PoolError::PaymentFailed(PoolPaymentFailed { to: trader, amount: refund }).into());
+            }
+            Ok(total)
+        })
+    }
+
+    /// Sells `amount` units into a pool for at least `min_proceeds` wei after royalty.
+    pub fn pool_sell(&mut self, pool_id: U256, amount: U256, min_proceeds: U256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            this.pausable.when_not_paused(PAUSE_MARKETPLACE)?;
+            let trader = this.vm().msg_sender();
+            let (id, gross) = this.pools._sell(pool_id, amount, min_proceeds)?;
+            this.oracle._update_from_pool(id, pool_id, this.pools.spot_price(pool_id));
+            this.soulbound.check_transferable(&[id])?;
+            this._update(trader, this.vm().contract_address(), vec![id], vec![amount], Vec::new())?;
+            this._after_transfer(&[id], &[amount]);
+            let proceeds = gross - this._pay_royalty(id, gross)?;
+            if proceeds < min_proceeds {
+                return Err(PoolError::Slippage(PoolSlippage { ethAmount: proceeds, limit: min_proceeds }).into());
+            }
+            if transfer_eth(trader, proceeds).is_err() {
+                return Err(PoolError::PaymentFailed(PoolPaymentFailed { to: trader, amount: proceeds }).into());
+            }
+            Ok(proceeds)
+        })
+    }
+
+    /// Returns `(cumulative, timestamp)`; the TWAP between two reads is the cumulative
+    /// difference divided by the elapsed time.
+    pub fn price_cumulative(&self, id: U256) -> (U256, U256) {
+        self.oracle.price_cumulative(self.aliases.resolve(id))
+    }
+
+    /// Time-weighted average trade price of `id` over at least the last `window` seconds.
+    pub fn consult(&self, id: U256, window: U256) -> Result<U256, Error> {
+        self.oracle.consult(self.aliases.resolve(id), window).map_err(Error::from)
+    }
+
+    /// The pool whose trades feed `id`'s price alongside its bonding curve; zero if none.
+    pub fn oracle_source_pool(&self, id: U256) -> U256 {
+        self.oracle.source_pool(self.aliases.resolve(id))
+    }
+
+    pub fn set_oracle_source_pool(&mut self, id: U256, pool_id: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        let id = self.aliases.resolve(id);
+        self.oracle.set_source_pool(id, pool_id, self.pools.pool(pool_id).1).map_err(Error::from)
+    }
+
+    pub fn redemption_config(&self, id: U256) -> (Address, U256, U256, U256) {
+        self.redemption.config(id)
+    }
+
+    pub fn quote_redemption(&self, id: U256, amount: U256) -> Result<U256, Error> {
+        self.redemption.quote(id, amount).map_err(Error::from)
+    }
+
+    pub fn configure_redemption(&mut self, id: U256, token: Address, rate: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.redemption.configure(id, token, rate);
+        Ok(())
+    }
+
+    /// Pulls `amount` of the id's payout token from the caller into the redemption pool.
+    pub fn fund_redemption(&mut self, id: U256, amount: U256, units: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.access.only_role(DEFAULT_ADMIN_ROLE)?;
+            let token = this.redemption._fund(id, amount, units)?;
+            let funder = this.vm().msg_sender();
+            let contract = this.vm().contract_address();
+            let pulled = IERC20::new(token)
+                .transfer_from(Call::new_in(this), funder, contract, amount)
+                .unwrap_or(false);
+            if !pulled {
+                return Err(RedemptionError::TransferFailed(RedemptionTransferFailed {
+                    token,
+                    to: contract,
+                    amount,
+                })
+                .into());
+            }
+            Ok(())
+        })
+    }
+
+    /// Burns `amount` of the caller's `id` and pays out the configured ERC-20.
+    pub fn redeem(&mut self, id: U256, amount: U256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            let account = this.vm().msg_sender();
+            this.pausable.when_not_paused(PAUSE_BURN)?;
+            this._update(account, Address::ZERO, vec![id], vec![amount], Vec::new())?;
+            let (token, payout) = this.redemption._redeem(id, amount)?;
+            let paid = IERC20::new(token)
+                .transfer(Call::new_in(this), account, payout)
+                .unwrap_or(false);
+            if !paid {
+                return Err(RedemptionError::TransferFailed(RedemptionTransferFailed {
+                    token,
+                    to: account,
+                    amount: payout,
+                })
+                .into());
+            }
+            Ok(payout)
+        })
+    }
+
+    pub fn buyback_budget(&self) -> U256 {
+        self.buyback.budget()
+    }
+
+    pub fn buyback_config(&self, id: U256) -> (U256, U256, U256, U256) {
+        self.buyback.config(id)
+    }
+
+    pub fn buyback_sale_share(&self) -> U256 {
+        self.buyback.sale_share_bps()
+    }
+
+    /// Share of released primary-sale proceeds, in basis points, diverted to the budget.
+    pub fn set_buyback_sale_share(&mut self, share_bps: U256) -> Result<(), Error> {
+        self.access.only_role(TREASURY_ROLE)?;
+        self.buyback.set_sale_share(share_bps).map_err(Error::from)
+    }
+
+    /// Tops the budget up with treasury ETH on top of what proceeds feed it.
+    #[payable]
+    pub fn fund_buyback(&mut self) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.access.only_role(TREASURY_ROLE)?;
+            this.buyback._fund(this.vm().msg_sender(), this.vm().msg_value());
+            Ok(())
+        })
+    }
+
+    pub fn configure_buyback(&mut self, id: U256, price: U256, period: U256, period_limit: U256) -> Result<(), Error> {
+        self.access.only_role(TREASURY_ROLE)?;
+        self.buyback.configure(id, price, period, period_limit);
+        Ok(())
+    }
+
+    /// Buys `amount` units off a pool with the budget, paying at most `max_cost` wei
+    /// including the royalty, and burns them.
+    pub fn buyback_from_pool(&mut self, pool_id: U256, amount: U256, max_cost: U256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            this.access.only_role(TREASURY_ROLE)?;
+            this.pausable.when_not_paused(PAUSE_MARKETPLACE)?;
+            let (id, cost) = this.pools._buy(pool_id, amount, max_cost)?;
+            this.oracle._update_from_pool(id, pool_id, this.pools.spot_price(pool_id));
+            let total = cost + this.royalties.settlement(id, cost).1;
+            if total > max_cost {
+                return Err(PoolError::Slippage(PoolSlippage { ethAmount: total, limit: max_cost }).into());
+            }
+            this.buyback._execute_from_pool(pool_id, id, amount, total)?;
+            this._pay_royalty(id, cost)?;
+            this._update(this.vm().contract_address(), Address::ZERO, vec![id], vec![amount], Vec::new())?;
+            Ok(total)
+        })
+    }
+
+    /// Sells `amount` of `id` into the treasury's standing bid; the units are burned.
+    pub fn sell_to_buyback(&mut self, id: U256, amount: U256) -> Result<U256, Error> {
+        self._non_reentrant(|this| {
+            let seller = this.vm().msg_sender();
+            this.pausable.when_not_paused(PAUSE_BURN)?;
+            let cost = this.buyback._execute(id, amount)?;
+            this._update(seller, Address::ZERO, vec![id], vec![amount], Vec::new())?;
+            if transfer_eth(seller, cost).is_err() {
+                return Err(BuybackError::PaymentFailed(BuybackPaymentFailed { to: seller, amount: cost }).into());
+            }
+            Ok(cost)
+        })
+    }
+
+    pub fn migration_config(&self, old_id: U256) -> (bool, U256, U256, U256) {
+        self.migration.config(self.aliases.resolve(old_id))
+    }
+
+    pub fn migrate_id(&mut self, old_id: U256, new_id: U256, numerator: U256, denominator: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        let old_id = self.aliases.resolve(old_id);
+        let new_id = self.aliases.resolve(new_id);
+        self.migration.configure(old_id, new_id, numerator, denominator).map_err(Error::from)
+    }
+
+    pub fn disable_migration(&mut self, old_id: U256) -> Result<(), Error> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        self.migration.disable(self.aliases.resolve(old_id));
+        Ok(())
+    }
+
+    /// Per-holder claim: converts `amount` of the caller's `old_id` balance.
+    pub fn claim_migration(&mut self, old_id: U256, amount: U256) -> Result<(), Error> {
+        self._non_reentrant(|this| this._migrate(this.vm().msg_sender(), old_id, amount))
+    }
+
+    /// Global swap: converts the full `old_id` balance of every listed holder.
+    pub fn migrate_holders(&mut self, old_id: U256, holders: Vec<Address>) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            this.access.only_role(DEFAULT_ADMIN_ROLE)?;
+            let old_id = this.aliases.resolve(old_id);
+            for holder in holders {
+                let amount = this.erc1155.balance_of(holder, old_id);
+                if !amount.is_zero() {
+                    this._migrate(holder, old_id, amount)?;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Erc1155Hooks for My1155 {
+    fn erc1155_mut(&mut self) -> &mut Erc1155 {
+        &mut self.erc1155
+    }
+
+    /// Mints to this contract are treasury deposits; every mint path lands here, so they
+    /// are booked once, unlike escrow transfers in, which only the user-facing transfer
+    /// entrypoints book.
+    fn before_token_transfer(&mut self, from: Address, to: Address, ids: &[U256], values: &[U256]) -> Result<(), Vec<u8>> {
+        self.pausable.when_not_stopped()?;
+        self._record_snapshots(from, to, ids);
+        if from.is_zero() && to == self.vm().contract_address() {
+            for (&id, &value) in ids.iter().zip(values) {
+                self.treasury._deposit(id, value);
+            }
+        }
+        Ok(())
+    }
+
+    fn after_token_transfer(&mut self, from: Address, to: Address, ids: &[U256], values: &[U256]) -> Result<(), Vec<u8>> {
+        self._notify(from, to, ids, values);
+        Ok(())
+    }
+}
+
+impl Erc165 for My1155 {
+    const INTERFACE_IDS: &'static [FixedBytes<4>] = &[
+        IERC1155_INTERFACE_ID,
+        IERC1155_METADATA_URI_INTERFACE_ID,
+        IERC4906_INTERFACE_ID,
+        IERC2981_INTERFACE_ID,
+    ];
+}
+
+impl My1155 {
+    /// Game-path bookkeeping without a transfer log, still wrapped in the hooks.
+    fn _apply_single(&mut self, from: Address, to: Address, id: U256, value: U256) -> Result<(), Error> {
+        self.before_token_transfer(from, to, &[id], &[value])?;
+        self.erc1155._apply_single(from, to, id, value)?;
+        self.after_token_transfer(from, to, &[id], &[value]).map_err(Error::from)
+    }
+
+    /// Calls the configured listener with a bounded gas stipend. A revert or out-of-gas in
+    /// the listener is logged and otherwise ignored.
+    fn _notify(&mut self, from: Address, to: Address, ids: &[U256], values: &[U256]) {
+        let listener = self.listener.listener();
+        if listener.is_zero() {
+            return;
+        }
+        let gas = self.listener.gas_limit();
+        let operator = self.vm().msg_sender();
+        let result = ITransferListener::new(listener).on_balance_change(
+            Call::new_in(self).gas(gas),
+            operator,
+            from,
+            to,
+            ids.to_vec(),
+            values.to_vec(),
+        );
+        if result.is_err() {
+            self.listener._failed(listener);
+        }
+    }
+
+    /// Checkpoints the balances and supplies an update is about to change, as they were
+    /// before it.
+    fn _record_snapshots(&mut self, from: Address, to: Address, ids: &[U256]) {
+        if self.snapshots.current_id().is_zero() {
+            return;
+        }
+        for &id in ids {
+            for account in [from, to] {
+                if !account.is_zero() {
+                    self.snapshots._update_account(id, account, self.erc1155.balance_of(account, id));
+                }
+            }
+            if from.is_zero() || to.is_zero() {
+                self.snapshots._update_supply(id, self.erc1155.total_supply(id));
+            }
+        }
+    }
+
+    /// Runs an entrypoint body under the reentrancy guard. Entrypoints that move balances
+    /// or ETH, or call out, go through here, so a callback can't re-enter any of them
+    /// mid-update. Role-gated setters that only write configuration don't need it; the
+    /// one other exemption, multisig `execute`, says why at its definition.
+    fn _non_reentrant<T>(&mut self, body: impl FnOnce(&mut Self) -> Result<T, Error>) -> Result<T, Error> {
+        self.reentrancy._enter()?;
+        let result = body(self)?;
+        self.reentrancy._exit();
+        Ok(result)
+    }
+
+    /// Extension checks shared by every path that moves existing balances.
+    fn _before_transfer(&self, to: Address, ids: &[U256]) -> Result<(), Error> {
+        self.pausable.when_not_stopped()?;
+        self.pausable.when_not_paused(PAUSE_TRANSFER)?;
+        self.soulbound.check_transferable(ids)?;
+        self._check_receiver(to, ids)
+    }
+
+    /// The transfer pipeline up to, but not including, the balance writes.
+    fn _check_transfer(&self, from: Address, to: Address, ids: Vec<U256>, values: &[U256]) -> Result<(), Error> {
+        let ids = self.aliases.resolve_all(ids);
+        self._before_transfer(to, &ids)?;
+        self.erc1155.check_transfer(self.vm().msg_sender(), from, to, &ids, values)?;
+        Ok(())
+    }
+
+    /// Bookkeeping after a successful transfer. Trips the transfer pause when any
+    /// id's per-block volume crosses its circuit-breaker threshold.
+    fn _after_transfer(&mut self, ids: &[U256], values: &[U256]) {
+        let mut tripped = false;
+        for (&id, &value) in ids.iter().zip(values.iter()) {
+            tripped |= self.circuit_breaker._record(id, value);
+        }
+        if tripped && !self.pausable.is_paused(PAUSE_TRANSFER) {
+            let flags = self.pausable.flags() | PAUSE_TRANSFER;
+            self.pausable.set_flags(flags);
+        }
+    }
+
+    fn _permit_batch(
+        &mut self,
+        owner: Address,
+        operators: Vec<Address>,
+        approved: Vec<bool>,
+        deadline: U256,
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        permit::verify_batch(&mut self.nonces, &self.passkeys, owner, &operators, &approved, deadline, signature)?;
+        for (operator, approved) in operators.into_iter().zip(approved) {
+            self.erc1155._set_approval_for_all(owner, operator, approved)?;
+        }
+        Ok(())
+    }
+
+    fn _fill_swap(&mut self, order: SwapOrder, maker_signature: &[u8]) -> Result<(), Error> {
+        self.pausable.when_not_paused(PAUSE_MARKETPLACE)?;
+        let taker = self.vm().msg_sender();
+        let value = self.vm().msg_value();
+        swap::verify(&mut self.nonces, &self.passkeys, &order, taker, value, maker_signature)?;
+
+        let give_ids = self.aliases.resolve_all(order.giveIds);
+        let want_ids = self.aliases.resolve_all(order.wantIds);
+        if !give_ids.is_empty() {
+            self._before_transfer(taker, &give_ids)?;
+            self._update(order.maker, taker, give_ids.clone(), order.giveAmounts.clone(), Vec::new())?;
+            self._after_transfer(&give_ids, &order.giveAmounts);
+        }
+        if !want_ids.is_empty() {
+            self._before_transfer(order.maker, &want_ids)?;
+            self._update(taker, order.maker, want_ids.clone(), order.wantAmounts.clone(), Vec::new())?;
+            self._after_transfer(&want_ids, &order.wantAmounts);
+        }
+        let mut proceeds = value;
+        if !value.is_zero() {
+            let prices = royalty::apportion(value, &order.giveAmounts)?;
+            for (&id, price) in give_ids.iter().zip(prices) {
+                proceeds -= self._pay_royalty(id, price)?;
+            }
+        }
+        if !proceeds.is_zero() && transfer_eth(order.maker, proceeds).is_err() {
+            return Err(SwapError::PaymentFailed(SwapPaymentFailed { to: order.maker, amount: proceeds }).into());
+        }
+        Ok(())
+    }
+
+    fn _redeem_voucher(&mut self, voucher: MintVoucher, passkey_signer: Address, signature: Bytes) -> Result<(), Error> {
+        self._non_reentrant(|this| {
+            let (sender, value) = (this.vm().msg_sender(), this.vm().msg_value());
+            let (signer, to) = this.lazy_mint._verify(
+                &mut this.nonces,
+                &this.passkeys,
+                &voucher,
+                passkey_signer,
+                sender,
+                value,
+                &signature,
+            )?;
+            if !this.access.has_role(MINTER_ROLE, signer) {
+                return Err(LazyMintError::InvalidSigner(LazyMintInvalidSigner { signer }).into());
+            }
+            let ids = this._check_mint(signer, to, vec![voucher.id], &[voucher.amount])?;
+            lazy_mint::check_uri(
+                &voucher,
+                this.access.has_role(URI_SETTER_ROLE, signer),
+                this.erc1155.total_supply(ids[0]),
+                this.metadata.has_token_uri(ids[0]),
+            )?;
+            if !voucher.uri.is_empty() {
+                this.metadata.set_token_uri(ids[0], voucher.uri.clone());
+            }
+            this._mint(to, ids[0], voucher.amount, Vec::new())?;
+            this.lazy_mint._settle(signer, to, &voucher)?;
+            Ok(())
+        })
+    }
+
+    /// Pays `id`'s royalty on a `sale_price` the contract is holding and returns the
+    /// amount paid. Used by every settlement path that moves ETH for a resale. Royalties
+    /// whose receiver is this contract stay here and go to the buyback budget.
+    fn _pay_royalty(&mut self, id: U256, sale_price: U256) -> Result<U256, Error> {
+        let (receiver, amount) = self.royalties.settlement(id, sale_price);
+        if receiver == self.vm().contract_address() {
+            self.buyback._fund(receiver, amount);
+        } else if !amount.is_zero() && transfer_eth(receiver, amount).is_err() {
+            return Err(RoyaltyError::PaymentFailed(RoyaltyPaymentFailed { receiver, amount }).into());
+        }
+        Ok(amount)
+    }
+
+    /// Checks shared by `mint` and `mint_batch`; returns the canonical ids. Repeated ids
+    /// are held to their cap by their combined amount.
+    fn _before_mint(&self, to: Address, ids: Vec<U256>, values: &[U256]) -> Result<Vec<U256>, Error> {
+        self.access.only_role(MINTER_ROLE)?;
+        self._check_mint(self.vm().msg_sender(), to, ids, values)
+    }
+
+    /// The part of `_before_mint` that doesn't depend on the caller, for mints authorized
+    /// by a `minter` signature.
+    fn _check_mint(&self, minter: Address, to: Address, ids: Vec<U256>, values: &[U256]) -> Result<Vec<U256>, Error> {
+        self.pausable.when_not_paused(PAUSE_MINT)?;
+        let ids = self.aliases.resolve_all(ids);
+        self.minter_scopes.check(minter, &ids)?;
+        self._check_receiver(to, &ids)?;
+        let mut totals: Vec<(U256, U256)> = Vec::new();
+        for (&id, &value) in ids.iter().zip(values) {
+            match totals.iter_mut().find(|(seen, _)| *seen == id) {
+                Some((_, total)) => *total = total.saturating_add(value),
+                None => totals.push((id, value)),
+            }
+        }
+        for (id, total) in totals {
+            self._check_supply_cap(id, total)?;
+        }
+        Ok(ids)
+    }
+
+    fn _before_burn(&self, account: Address) -> Result<(), Error> {
+        self.pausable.when_not_paused(PAUSE_BURN)?;
+        let operator = self.vm().msg_sender();
+        if account != operator && !self.erc1155.is_approved_for_all(account, operator) {
+            return Err(Erc1155Error::MissingApprovalForAll(ERC1155MissingApprovalForAll {
+                operator,
+                owner: account,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Ordinary mints may not eat into the cap's reserved allocations, nor mint ids whose
+    /// supply is backed by a bonding curve.
+    fn _check_supply_cap(&self, id: U256, amount: U256) -> Result<(), Error> {
+        self.curves.check_external_mint(id)?;
+        self.reserves.check_mint(id, amount, self.erc1155.total_supply(id))?;
+        Ok(())
+    }
+
+    fn _record_curve_price(&mut self, id: U256) {
+        let price = self.curves.spot_price(id).unwrap_or_default();
+        self.oracle._update(id, price);
+    }
+
+    fn _migrate(&mut self, account: Address, old_id: U256, amount: U256) -> Result<(), Error> {
+        self.pausable.when_not_paused(PAUSE_MINT | PAUSE_BURN)?;
+        let old_id = self.aliases.resolve(old_id);
+        let (new_id, minted) = self.migration._convert(account, old_id, amount, |id| self.aliases.resolve(id))?;
+        self._update(account, Address::ZERO, vec![old_id], vec![amount], Vec::new())?;
+        self._check_supply_cap(new_id, minted)?;
+        self._update(Address::ZERO, account, vec![new_id], vec![minted], Vec::new())?;
+        Ok(())
+    }
+
+    /// Receiver-side restrictions, also applied when recovery migrates an account.
+    fn _check_receiver(&self, to: Address, ids: &[U256]) -> Result<(), Error> {
+        self.allowlist.check_receiver(to)?;
+        self.attestations.check_receiver(to, ids)?;
+        Ok(())
+    }
+}
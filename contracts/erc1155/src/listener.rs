@@ -0,0 +1,46 @@
+//! Optional on-chain subscriber notified after every balance change, for quest trackers
+//! and leaderboards that would otherwise have to poll events. The call is gas-bounded
+//! and its failure never reverts the transfer.
+
+use stylus_sdk::{
+    alloy_primitives::{Address, U64},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+sol_storage! {
+    pub struct TransferListener {
+        address listener;
+        uint64 gas_limit;
+    }
+}
+
+sol! {
+    event TransferListenerSet(address listener, uint64 gasLimit);
+    event TransferListenerFailed(address indexed listener);
+}
+
+impl TransferListener {
+    pub fn listener(&self) -> Address {
+        self.listener.get()
+    }
+
+    pub fn gas_limit(&self) -> u64 {
+        self.gas_limit.get().to::<u64>()
+    }
+
+    /// The zero address turns notifications off.
+    pub fn set_listener(&mut self, listener: Address, gas_limit: u64) {
+        self.listener.set(listener);
+        self.gas_limit.set(U64::from(gas_limit));
+        log(self.vm(), TransferListenerSet {
+            listener,
+            gasLimit: gas_limit,
+        });
+    }
+
+    pub fn _failed(&self, listener: Address) {
+        log(self.vm(), TransferListenerFailed { listener });
+    }
+}
@@ -0,0 +1,156 @@
+use alloc::{format, string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::{b256, fixed_bytes, FixedBytes, B256, U256},
+    alloy_sol_types::sol,
+    crypto::keccak,
+    prelude::*,
+    stylus_core::log,
+};
+
+/// `keccak256("URI_SETTER_ROLE")`
+pub const URI_SETTER_ROLE: B256 =
+    b256!("7804d923f43a17d325d77e781528e0793b2edd9890ab45fc64efd7b4b427744c");
+
+/// ERC-4906 interface id, `bytes4(0x49064906)`.
+pub const IERC4906_INTERFACE_ID: FixedBytes<4> = fixed_bytes!("49064906");
+/// `type(IERC1155MetadataURI).interfaceId`
+pub const IERC1155_METADATA_URI_INTERFACE_ID: FixedBytes<4> = fixed_bytes!("0e89341c");
+
+sol_storage! {
+    pub struct TokenMetadata {
+        string contract_uri;
+        string base_uri;
+        mapping(uint256 => string) token_uri;
+        mapping(uint256 => mapping(bytes32 => string)) attributes;
+        mapping(uint256 => mapping(bytes32 => string)) localized_uri;
+    }
+}
+
+sol! {
+    event URI(string value, uint256 indexed id);
+    event MetadataUpdate(uint256 _tokenId);
+    event BatchMetadataUpdate(uint256 _fromTokenId, uint256 _toTokenId);
+    event ContractURIUpdated();
+}
+
+impl TokenMetadata {
+    /// ERC-7572 collection-level metadata.
+    pub fn contract_uri(&self) -> String {
+        self.contract_uri.get_string()
+    }
+
+    pub fn set_contract_uri(&mut self, uri: String) {
+        self.contract_uri.set_str(uri);
+        log(self.vm(), ContractURIUpdated {});
+    }
+
+    pub fn base_uri(&self) -> String {
+        self.base_uri.get_string()
+    }
+
+    /// The per-id override if set, otherwise the base URI with every `{id}` replaced by
+    /// the id as 64 lowercase hex digits, per the ERC-1155 metadata convention.
+    pub fn token_uri(&self, id: U256) -> String {
+        let uri = self.token_uri.getter(id).get_string();
+        if !uri.is_empty() {
+            return uri;
+        }
+        self.base_uri().replace("{id}", &format!("{id:064x}"))
+    }
+
+    /// Whether `id` has a per-id override rather than the base URI.
+    pub fn has_token_uri(&self, id: U256) -> bool {
+        !self.token_uri.getter(id).is_empty()
+    }
+
+    /// Changes the URI of every id without an override.
+    pub fn set_base_uri(&mut self, uri: String) {
+        self.base_uri.set_str(uri);
+        self.refresh(U256::ZERO, U256::MAX);
+    }
+
+    /// Resolves `locale` (BCP 47, case-insensitive) as exact tag, then its primary
+    /// language subtag, then the default token URI: `fr-CA` → `fr` → default.
+    pub fn localized_uri(&self, id: U256, locale: &str) -> String {
+        let uris = self.localized_uri.getter(id);
+        let mut tag = locale;
+        loop {
+            if !tag.is_empty() {
+                let uri = uris.getter(locale_key(tag)).get_string();
+                if !uri.is_empty() {
+                    return uri;
+                }
+            }
+            match tag.rfind(['-', '_']) {
+                Some(end) => tag = &tag[..end],
+                None => return self.token_uri(id),
+            }
+        }
+    }
+
+    /// An empty `uri` clears the override so the locale falls back again.
+    pub fn set_localized_uri(&mut self, id: U256, locale: &str, uri: String) {
+        self.localized_uri.setter(id).setter(locale_key(locale)).set_str(uri);
+        log(self.vm(), MetadataUpdate { _tokenId: id });
+    }
+
+    pub fn attribute(&self, id: U256, key: B256) -> String {
+        self.attributes.getter(id).getter(key).get_string()
+    }
+
+    /// An empty `uri` clears the override, falling back to the base URI.
+    pub fn set_token_uri(&mut self, id: U256, uri: String) {
+        self.token_uri.setter(id).set_str(uri);
+        self._log_uri(id);
+        log(self.vm(), MetadataUpdate { _tokenId: id });
+    }
+
+    /// An empty `value` clears the attribute.
+    pub fn set_attribute(&mut self, id: U256, key: B256, value: String) {
+        self.attributes.setter(id).setter(key).set_str(value);
+        log(self.vm(), MetadataUpdate { _tokenId: id });
+    }
+
+    /// Bulk form of [`Self::set_token_uri`]; emits one `BatchMetadataUpdate` spanning
+    /// `ids` instead of an event per id. Lengths are checked by the caller.
+    pub fn set_token_uris(&mut self, ids: &[U256], uris: Vec<String>) {
+        for (&id, uri) in ids.iter().zip(uris) {
+            self.token_uri.setter(id).set_str(uri);
+            self._log_uri(id);
+        }
+        self._refresh_span(ids);
+    }
+
+    /// Bulk form of [`Self::set_attribute`], one `(id, key, value)` per index.
+    pub fn set_attributes(&mut self, ids: &[U256], keys: &[B256], values: Vec<String>) {
+        for ((&id, &key), value) in ids.iter().zip(keys).zip(values) {
+            self.attributes.setter(id).setter(key).set_str(value);
+        }
+        self._refresh_span(ids);
+    }
+
+    /// Asks indexers to re-fetch `from..=to`, e.g. after off-chain metadata was redeployed.
+    pub fn refresh(&self, from: U256, to: U256) {
+        log(self.vm(), BatchMetadataUpdate {
+            _fromTokenId: from,
+            _toTokenId: to,
+        });
+    }
+
+    fn _log_uri(&self, id: U256) {
+        log(self.vm(), URI {
+            value: self.token_uri(id),
+            id,
+        });
+    }
+
+    fn _refresh_span(&self, ids: &[U256]) {
+        if let (Some(from), Some(to)) = (ids.iter().min(), ids.iter().max()) {
+            self.refresh(*from, *to);
+        }
+    }
+}
+
+fn locale_key(locale: &str) -> B256 {
+    keccak(locale.to_ascii_lowercase().as_bytes())
+}
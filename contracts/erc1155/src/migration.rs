@@ -0,0 +1,113 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct Migration {
+        mapping(uint256 => bool) enabled;
+        mapping(uint256 => uint256) target;
+        mapping(uint256 => uint256) numerator;
+        mapping(uint256 => uint256) denominator;
+    }
+}
+
+sol! {
+    event MigrationConfigured(uint256 indexed oldId, uint256 indexed newId, uint256 numerator, uint256 denominator);
+    event MigrationDisabled(uint256 indexed oldId);
+    event Migrated(address indexed account, uint256 indexed oldId, uint256 indexed newId, uint256 burned, uint256 minted);
+
+    error MigrationNotConfigured(uint256 oldId);
+    error MigrationInvalidRatio(uint256 numerator, uint256 denominator);
+    error MigrationOverflow(uint256 oldId, uint256 amount);
+    error MigrationAmountTooSmall(uint256 oldId, uint256 amount);
+}
+
+pub enum MigrationError {
+    NotConfigured(MigrationNotConfigured),
+    InvalidRatio(MigrationInvalidRatio),
+    Overflow(MigrationOverflow),
+    AmountTooSmall(MigrationAmountTooSmall),
+}
+
+impl From<MigrationError> for Vec<u8> {
+    fn from(error: MigrationError) -> Self {
+        match error {
+            MigrationError::NotConfigured(e) => revert::encode(e),
+            MigrationError::InvalidRatio(e) => revert::encode(e),
+            MigrationError::Overflow(e) => revert::encode(e),
+            MigrationError::AmountTooSmall(e) => revert::encode(e),
+        }
+    }
+}
+
+impl Migration {
+    /// Returns `(enabled, new_id, numerator, denominator)` for `old_id`.
+    pub fn config(&self, old_id: U256) -> (bool, U256, U256, U256) {
+        (
+            self.enabled.get(old_id),
+            self.target.get(old_id),
+            self.numerator.get(old_id),
+            self.denominator.get(old_id),
+        )
+    }
+
+    /// Every unit of `old_id` converts into `numerator / denominator` units of `new_id`.
+    pub fn configure(&mut self, old_id: U256, new_id: U256, numerator: U256, denominator: U256) -> Result<(), MigrationError> {
+        if numerator.is_zero() || denominator.is_zero() || old_id == new_id {
+            return Err(MigrationError::InvalidRatio(MigrationInvalidRatio { numerator, denominator }));
+        }
+        self.enabled.insert(old_id, true);
+        self.target.insert(old_id, new_id);
+        self.numerator.insert(old_id, numerator);
+        self.denominator.insert(old_id, denominator);
+        log(self.vm(), MigrationConfigured {
+            oldId: old_id,
+            newId: new_id,
+            numerator,
+            denominator,
+        });
+        Ok(())
+    }
+
+    pub fn disable(&mut self, old_id: U256) {
+        self.enabled.insert(old_id, false);
+        log(self.vm(), MigrationDisabled { oldId: old_id });
+    }
+
+    /// Returns `(new_id, minted)` for burning `amount` of `old_id` from `account`, with the
+    /// configured target passed through `resolve` in case it has since become an alias.
+    /// Reverts if the conversion would mint nothing, so dust can't be burned for zero.
+    pub fn _convert(
+        &self,
+        account: Address,
+        old_id: U256,
+        amount: U256,
+        resolve: impl FnOnce(U256) -> U256,
+    ) -> Result<(U256, U256), MigrationError> {
+        let new_id = resolve(self.target.get(old_id));
+        if !self.enabled.get(old_id) || new_id == old_id {
+            return Err(MigrationError::NotConfigured(MigrationNotConfigured { oldId: old_id }));
+        }
+        let minted = amount
+            .checked_mul(self.numerator.get(old_id))
+            .ok_or(MigrationError::Overflow(MigrationOverflow { oldId: old_id, amount }))?
+            / self.denominator.get(old_id);
+        if minted.is_zero() {
+            return Err(MigrationError::AmountTooSmall(MigrationAmountTooSmall { oldId: old_id, amount }));
+        }
+        log(self.vm(), Migrated {
+            account,
+            oldId: old_id,
+            newId: new_id,
+            burned: amount,
+            minted,
+        });
+        Ok((new_id, minted))
+    }
+}
@@ -0,0 +1,125 @@
+//! Optional per-minter restrictions. A minter without a scope may mint any id; once
+//! scoped it may only mint ids in its explicit set or inside its range.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct MinterScopes {
+        mapping(address => bool) scoped;
+        mapping(address => uint256[]) ids;
+        mapping(address => mapping(uint256 => bool)) allowed;
+        mapping(address => bool) has_range;
+        mapping(address => uint256) range_start;
+        mapping(address => uint256) range_end;
+    }
+}
+
+sol! {
+    event MinterScopeSet(address indexed minter, uint256[] ids);
+    event MinterRangeSet(address indexed minter, uint256 start, uint256 end);
+    event MinterScopeCleared(address indexed minter);
+
+    error MinterOutOfScope(address minter, uint256 id);
+    error MinterInvalidRange(uint256 start, uint256 end);
+}
+
+pub enum MinterScopeError {
+    OutOfScope(MinterOutOfScope),
+    InvalidRange(MinterInvalidRange),
+}
+
+impl From<MinterScopeError> for Vec<u8> {
+    fn from(error: MinterScopeError) -> Self {
+        match error {
+            MinterScopeError::OutOfScope(e) => revert::encode(e),
+            MinterScopeError::InvalidRange(e) => revert::encode(e),
+        }
+    }
+}
+
+impl MinterScopes {
+    /// Returns `(scoped, ids, has_range, range_start, range_end)`.
+    pub fn scope(&self, minter: Address) -> (bool, Vec<U256>, bool, U256, U256) {
+        let ids = self.ids.get(minter);
+        (
+            self.scoped.get(minter),
+            (0..ids.len()).filter_map(|i| ids.get(i)).collect(),
+            self.has_range.get(minter),
+            self.range_start.get(minter),
+            self.range_end.get(minter),
+        )
+    }
+
+    pub fn can_mint(&self, minter: Address, id: U256) -> bool {
+        if !self.scoped.get(minter) || self.allowed.get(minter).get(id) {
+            return true;
+        }
+        self.has_range.get(minter) && id >= self.range_start.get(minter) && id <= self.range_end.get(minter)
+    }
+
+    pub fn check(&self, minter: Address, ids: &[U256]) -> Result<(), MinterScopeError> {
+        for &id in ids {
+            if !self.can_mint(minter, id) {
+                return Err(MinterScopeError::OutOfScope(MinterOutOfScope { minter, id }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces `minter`'s explicit id set; its range, if any, is kept.
+    pub fn set_ids(&mut self, minter: Address, ids: Vec<U256>) {
+        self._clear_ids(minter);
+        let mut allowed = self.allowed.setter(minter);
+        let mut list = self.ids.setter(minter);
+        for &id in ids.iter() {
+            if !allowed.get(id) {
+                allowed.insert(id, true);
+                list.push(id);
+            }
+        }
+        self.scoped.insert(minter, true);
+        log(self.vm(), MinterScopeSet { minter, ids });
+    }
+
+    /// Allows `start..=end` in addition to `minter`'s explicit ids.
+    pub fn set_range(&mut self, minter: Address, start: U256, end: U256) -> Result<(), MinterScopeError> {
+        if start > end {
+            return Err(MinterScopeError::InvalidRange(MinterInvalidRange { start, end }));
+        }
+        self.has_range.insert(minter, true);
+        self.range_start.insert(minter, start);
+        self.range_end.insert(minter, end);
+        self.scoped.insert(minter, true);
+        log(self.vm(), MinterRangeSet { minter, start, end });
+        Ok(())
+    }
+
+    /// Lifts every restriction on `minter`.
+    pub fn clear(&mut self, minter: Address) {
+        self._clear_ids(minter);
+        self.has_range.delete(minter);
+        self.range_start.delete(minter);
+        self.range_end.delete(minter);
+        self.scoped.delete(minter);
+        log(self.vm(), MinterScopeCleared { minter });
+    }
+
+    fn _clear_ids(&mut self, minter: Address) {
+        let mut allowed = self.allowed.setter(minter);
+        let mut list = self.ids.setter(minter);
+        for i in 0..list.len() {
+            if let Some(id) = list.get(i) {
+                allowed.delete(id);
+            }
+        }
+        list.erase();
+    }
+}
@@ -0,0 +1,226 @@
+//! m-of-n signer set that administers the contract through self-calls. Hand the default
+//! admin to the contract's own address (`begin_default_admin_transfer`, then a proposal
+//! calling `accept_default_admin_transfer`) and grant it any other role it should hold;
+//! executed proposals reach gated entrypoints with the contract's own address as the caller.
+//! Those self-calls need the SDK's `reentrant` feature, so the crate relies on
+//! [`crate::reentrancy`] rather than the SDK to keep callbacks out of value-moving paths.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct Multisig {
+        address[] signers;
+        mapping(address => bool) is_signer;
+        uint256 threshold;
+        uint256 config_nonce;
+        uint256 proposal_count;
+        mapping(uint256 => MultisigProposal) proposals;
+        mapping(uint256 => mapping(address => bool)) confirmed;
+    }
+
+    pub struct MultisigProposal {
+        bytes data;
+        uint256 expiry;
+        uint256 confirmations;
+        uint256 config_nonce;
+        bool executed;
+    }
+}
+
+sol! {
+    event MultisigConfigured(address[] signers, uint256 threshold);
+    event ProposalCreated(uint256 indexed proposalId, address indexed proposer, bytes data, uint256 expiry);
+    event ProposalConfirmed(uint256 indexed proposalId, address indexed signer, uint256 confirmations);
+    event ConfirmationRevoked(uint256 indexed proposalId, address indexed signer, uint256 confirmations);
+    event ProposalExecuted(uint256 indexed proposalId);
+
+    error MultisigInvalidConfig(uint256 signers, uint256 threshold);
+    error MultisigNotSigner(address account);
+    error MultisigUnknownProposal(uint256 proposalId);
+    error MultisigProposalExpired(uint256 proposalId);
+    error MultisigAlreadyExecuted(uint256 proposalId);
+    error MultisigNotEnoughConfirmations(uint256 proposalId, uint256 confirmations, uint256 threshold);
+}
+
+pub enum MultisigError {
+    InvalidConfig(MultisigInvalidConfig),
+    NotSigner(MultisigNotSigner),
+    UnknownProposal(MultisigUnknownProposal),
+    ProposalExpired(MultisigProposalExpired),
+    AlreadyExecuted(MultisigAlreadyExecuted),
+    NotEnoughConfirmations(MultisigNotEnoughConfirmations),
+}
+
+impl From<MultisigError> for Vec<u8> {
+    fn from(error: MultisigError) -> Self {
+        match error {
+            MultisigError::InvalidConfig(e) => revert::encode(e),
+            MultisigError::NotSigner(e) => revert::encode(e),
+            MultisigError::UnknownProposal(e) => revert::encode(e),
+            MultisigError::ProposalExpired(e) => revert::encode(e),
+            MultisigError::AlreadyExecuted(e) => revert::encode(e),
+            MultisigError::NotEnoughConfirmations(e) => revert::encode(e),
+        }
+    }
+}
+
+impl Multisig {
+    pub fn signers(&self) -> Vec<Address> {
+        (0..self.signers.len()).filter_map(|i| self.signers.get(i)).collect()
+    }
+
+    pub fn threshold(&self) -> U256 {
+        self.threshold.get()
+    }
+
+    /// Returns `(expiry, confirmations, executed)` for `proposal_id`.
+    pub fn proposal(&self, proposal_id: U256) -> (U256, U256, bool) {
+        let proposal = self.proposals.get(proposal_id);
+        (proposal.expiry.get(), proposal.confirmations.get(), proposal.executed.get())
+    }
+
+    pub fn is_confirmed(&self, proposal_id: U256, signer: Address) -> bool {
+        self.confirmed.get(proposal_id).get(signer)
+    }
+
+    /// Replaces the signer set. Proposals created under the previous set can no longer execute.
+    pub fn configure(&mut self, signers: Vec<Address>, threshold: U256) -> Result<(), MultisigError> {
+        let count = U256::from(signers.len());
+        let has_invalid = signers
+            .iter()
+            .enumerate()
+            .any(|(i, s)| s.is_zero() || signers[..i].contains(s));
+        if has_invalid || threshold.is_zero() || threshold > count {
+            return Err(MultisigError::InvalidConfig(MultisigInvalidConfig {
+                signers: count,
+                threshold,
+            }));
+        }
+
+        for old in self.signers() {
+            self.is_signer.delete(old);
+        }
+        self.signers.erase();
+        for signer in signers.iter() {
+            self.signers.push(*signer);
+            self.is_signer.insert(*signer, true);
+        }
+        self.threshold.set(threshold);
+        self.config_nonce.set(self.config_nonce.get() + U256::from(1));
+
+        log(self.vm(), MultisigConfigured { signers, threshold });
+        Ok(())
+    }
+
+    /// Creates a proposal to call this contract with `data` and confirms it for the proposer.
+    pub fn propose(&mut self, data: Vec<u8>, expiry: U256) -> Result<U256, MultisigError> {
+        let proposer = self._only_signer()?;
+        let proposal_id = self.proposal_count.get() + U256::from(1);
+        self.proposal_count.set(proposal_id);
+
+        let mut proposal = self.proposals.setter(proposal_id);
+        proposal.data.set_bytes(&data);
+        proposal.expiry.set(expiry);
+        proposal.config_nonce.set(self.config_nonce.get());
+
+        log(self.vm(), ProposalCreated {
+            proposalId: proposal_id,
+            proposer,
+            data: data.into(),
+            expiry,
+        });
+        self.confirm(proposal_id)?;
+        Ok(proposal_id)
+    }
+
+    pub fn confirm(&mut self, proposal_id: U256) -> Result<(), MultisigError> {
+        let signer = self._only_signer()?;
+        self._check_pending(proposal_id)?;
+        if self.is_confirmed(proposal_id, signer) {
+            return Ok(());
+        }
+
+        self.confirmed.setter(proposal_id).insert(signer, true);
+        let mut proposal = self.proposals.setter(proposal_id);
+        let confirmations = proposal.confirmations.get() + U256::from(1);
+        proposal.confirmations.set(confirmations);
+
+        log(self.vm(), ProposalConfirmed {
+            proposalId: proposal_id,
+            signer,
+            confirmations,
+        });
+        Ok(())
+    }
+
+    pub fn revoke_confirmation(&mut self, proposal_id: U256) -> Result<(), MultisigError> {
+        let signer = self._only_signer()?;
+        self._check_pending(proposal_id)?;
+        if !self.is_confirmed(proposal_id, signer) {
+            return Ok(());
+        }
+
+        self.confirmed.setter(proposal_id).delete(signer);
+        let mut proposal = self.proposals.setter(proposal_id);
+        let confirmations = proposal.confirmations.get() - U256::from(1);
+        proposal.confirmations.set(confirmations);
+
+        log(self.vm(), ConfirmationRevoked {
+            proposalId: proposal_id,
+            signer,
+            confirmations,
+        });
+        Ok(())
+    }
+
+    /// Marks an approved proposal executed and returns its calldata for the self-call.
+    pub fn _take_for_execution(&mut self, proposal_id: U256) -> Result<Vec<u8>, MultisigError> {
+        self._only_signer()?;
+        self._check_pending(proposal_id)?;
+        let threshold = self.threshold.get();
+        let mut proposal = self.proposals.setter(proposal_id);
+        let confirmations = proposal.confirmations.get();
+        if confirmations < threshold {
+            return Err(MultisigError::NotEnoughConfirmations(MultisigNotEnoughConfirmations {
+                proposalId: proposal_id,
+                confirmations,
+                threshold,
+            }));
+        }
+        proposal.executed.set(true);
+        let data = proposal.data.get_bytes();
+        log(self.vm(), ProposalExecuted { proposalId: proposal_id });
+        Ok(data)
+    }
+
+    fn _only_signer(&self) -> Result<Address, MultisigError> {
+        let account = self.vm().msg_sender();
+        if !self.is_signer.get(account) {
+            return Err(MultisigError::NotSigner(MultisigNotSigner { account }));
+        }
+        Ok(account)
+    }
+
+    fn _check_pending(&self, proposal_id: U256) -> Result<(), MultisigError> {
+        if proposal_id.is_zero() || proposal_id > self.proposal_count.get() {
+            return Err(MultisigError::UnknownProposal(MultisigUnknownProposal { proposalId: proposal_id }));
+        }
+        let proposal = self.proposals.get(proposal_id);
+        if proposal.executed.get() {
+            return Err(MultisigError::AlreadyExecuted(MultisigAlreadyExecuted { proposalId: proposal_id }));
+        }
+        let stale = proposal.config_nonce.get() != self.config_nonce.get();
+        if stale || U256::from(self.vm().block_timestamp()) > proposal.expiry.get() {
+            return Err(MultisigError::ProposalExpired(MultisigProposalExpired { proposalId: proposal_id }));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    /// Nonces shared by every signed message the contract accepts. Permits consume them
+    /// in order; vouchers and orders may use any nonce at or above the account's floor.
+    pub struct SignatureNonces {
+        mapping(address => uint256) next;
+        mapping(address => mapping(uint256 => bool)) used;
+    }
+}
+
+sol! {
+    event NonceInvalidated(address indexed account, uint256 nonce);
+    event NoncesInvalidated(address indexed account, uint256 upTo);
+
+    error InvalidAccountNonce(address account, uint256 nonce);
+}
+
+pub enum NonceError {
+    InvalidAccountNonce(InvalidAccountNonce),
+}
+
+impl From<NonceError> for Vec<u8> {
+    fn from(error: NonceError) -> Self {
+        match error {
+            NonceError::InvalidAccountNonce(e) => revert::encode(e),
+        }
+    }
+}
+
+impl SignatureNonces {
+    /// Next sequential nonce; also the floor below which every nonce is dead.
+    pub fn nonces(&self, account: Address) -> U256 {
+        self.next.get(account)
+    }
+
+    pub fn is_nonce_valid(&self, account: Address, nonce: U256) -> bool {
+        nonce >= self.next.get(account) && !self.used.get(account).get(nonce)
+    }
+
+    /// Cancels a single outstanding nonce of the caller.
+    pub fn invalidate_nonce(&mut self, nonce: U256) {
+        let account = self.vm().msg_sender();
+        self.used.setter(account).insert(nonce, true);
+        if nonce == self.next.get(account) {
+            self.next.insert(account, nonce + U256::from(1));
+        }
+        log(self.vm(), NonceInvalidated { account, nonce });
+    }
+
+    /// Cancels every outstanding nonce of the caller below `up_to`.
+    pub fn invalidate_nonces_up_to(&mut self, up_to: U256) {
+        let account = self.vm().msg_sender();
+        if up_to > self.next.get(account) {
+            self.next.insert(account, up_to);
+        }
+        log(self.vm(), NoncesInvalidated { account, upTo: up_to });
+    }
+
+    /// Returns `account`'s next sequential nonce and advances past it, skipping nonces
+    /// that were invalidated individually.
+    pub fn _use_next(&mut self, account: Address) -> U256 {
+        let mut nonce = self.next.get(account);
+        while self.used.get(account).get(nonce) {
+            nonce += U256::from(1);
+        }
+        self.next.insert(account, nonce + U256::from(1));
+        nonce
+    }
+
+    /// Consumes an arbitrary (unordered) nonce, rejecting replays and invalidated nonces.
+    pub fn _use_unordered(&mut self, account: Address, nonce: U256) -> Result<(), NonceError> {
+        if !self.is_nonce_valid(account, nonce) {
+            return Err(NonceError::InvalidAccountNonce(InvalidAccountNonce { account, nonce }));
+        }
+        self.used.setter(account).insert(nonce, true);
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+//! Time-weighted average prices for traded ids. Every bonding-curve trade, and every
+//! trade on the id's source pool, pushes the resulting spot price; the accumulator
+//! integrates price over time, so a price only moves the average in proportion to how
+//! long it was held. Pools are permissionless, so only the one an admin names as the
+//! id's source counts; any other pool's price is whatever its creator says it is.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::U256,
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+/// Observations kept per id; bounds the longest window `consult` can answer.
+pub const OBSERVATION_CAPACITY: u64 = 64;
+
+sol_storage! {
+    pub struct PriceOracle {
+        mapping(uint256 => uint256) price;
+        mapping(uint256 => uint256) cumulative;
+        mapping(uint256 => uint256) last_update;
+        mapping(uint256 => uint256) observation_count;
+        mapping(uint256 => mapping(uint256 => uint256)) observation_timestamp;
+        mapping(uint256 => mapping(uint256 => uint256)) observation_cumulative;
+        mapping(uint256 => uint256) source_pool;
+    }
+}
+
+sol! {
+    event OracleSourcePoolSet(uint256 indexed id, uint256 poolId);
+
+    error OracleNoPrice(uint256 id);
+    error OracleWindowTooLong(uint256 id, uint256 window, uint256 oldest);
+    error OracleSourcePoolMismatch(uint256 id, uint256 poolId, uint256 poolTokenId);
+}
+
+pub enum OracleError {
+    NoPrice(OracleNoPrice),
+    WindowTooLong(OracleWindowTooLong),
+    SourcePoolMismatch(OracleSourcePoolMismatch),
+}
+
+impl From<OracleError> for Vec<u8> {
+    fn from(error: OracleError) -> Self {
+        match error {
+            OracleError::NoPrice(e) => revert::encode(e),
+            OracleError::WindowTooLong(e) => revert::encode(e),
+            OracleError::SourcePoolMismatch(e) => revert::encode(e),
+        }
+    }
+}
+
+impl PriceOracle {
+    /// Returns `(cumulative, timestamp)` as of now: the running sum of `price * seconds`.
+    pub fn price_cumulative(&self, id: U256) -> (U256, U256) {
+        let now = U256::from(self.vm().block_timestamp());
+        (self._cumulative_at(id, now), now)
+    }
+
+    /// The pool whose trades feed `id`'s price; zero if only the bonding curve does.
+    pub fn source_pool(&self, id: U256) -> U256 {
+        self.source_pool.get(id)
+    }
+
+    /// `pool_token_id` is the id `pool_id` trades, which must be `id` unless `pool_id`
+    /// is zero.
+    pub fn set_source_pool(&mut self, id: U256, pool_id: U256, pool_token_id: U256) -> Result<(), OracleError> {
+        if !pool_id.is_zero() && pool_token_id != id {
+            return Err(OracleError::SourcePoolMismatch(OracleSourcePoolMismatch {
+                id,
+                poolId: pool_id,
+                poolTokenId: pool_token_id,
+            }));
+        }
+        self.source_pool.insert(id, pool_id);
+        log(self.vm(), OracleSourcePoolSet { id, poolId: pool_id });
+        Ok(())
+    }
+
+    /// Average price of `id` over at least the last `window` seconds. The average starts at
+    /// the newest observation at or before `now - window`; a zero window is the spot price.
+    pub fn consult(&self, id: U256, window: U256) -> Result<U256, OracleError> {
+        let count = self.observation_count.get(id);
+        if count.is_zero() {
+            return Err(OracleError::NoPrice(OracleNoPrice { id }));
+        }
+        if window.is_zero() {
+            return Ok(self.price.get(id));
+        }
+
+        let now = U256::from(self.vm().block_timestamp());
+        let target = now.saturating_sub(window);
+        let capacity = U256::from(OBSERVATION_CAPACITY);
+        let oldest = count.saturating_sub(capacity);
+        let timestamps = self.observation_timestamp.get(id);
+
+        let mut index = count;
+        while index > oldest {
+            index -= U256::from(1);
+            let timestamp = timestamps.get(index % capacity);
+            if timestamp <= target {
+                let then = self.observation_cumulative.get(id).get(index % capacity);
+                return Ok(self._cumulative_at(id, now).wrapping_sub(then) / (now - timestamp));
+            }
+        }
+        Err(OracleError::WindowTooLong(OracleWindowTooLong {
+            id,
+            window,
+            oldest: timestamps.get(oldest % capacity),
+        }))
+    }
+
+    /// Records a trade on `pool_id`, ignored unless it is `id`'s source pool.
+    pub fn _update_from_pool(&mut self, id: U256, pool_id: U256, price: U256) {
+        if !pool_id.is_zero() && self.source_pool.get(id) == pool_id {
+            self._update(id, price);
+        }
+    }
+
+    /// Accrues the old price up to now, then records `price` as the new spot. Writes at
+    /// most one observation per timestamp.
+    pub fn _update(&mut self, id: U256, price: U256) {
+        let now = U256::from(self.vm().block_timestamp());
+        let cumulative = self._cumulative_at(id, now);
+        let capacity = U256::from(OBSERVATION_CAPACITY);
+        let count = self.observation_count.get(id);
+
+        if count.is_zero() || self.last_update.get(id) != now {
+            let slot = count % capacity;
+            self.observation_timestamp.setter(id).insert(slot, now);
+            self.observation_cumulative.setter(id).insert(slot, cumulative);
+            self.observation_count.insert(id, count + U256::from(1));
+        }
+        self.cumulative.insert(id, cumulative);
+        self.last_update.insert(id, now);
+        self.price.insert(id, price);
+    }
+
+    fn _cumulative_at(&self, id: U256, now: U256) -> U256 {
+        let elapsed = now - self.last_update.get(id);
+        self.cumulative
+            .get(id)
+            .wrapping_add(self.price.get(id).wrapping_mul(elapsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{alloy_primitives::uint, testing::*};
+
+    const ID: U256 = uint!(1_U256);
+    const SOURCE_POOL: U256 = uint!(1_U256);
+    const ATTACKER_POOL: U256 = uint!(2_U256);
+
+    #[test]
+    fn only_the_source_pool_moves_the_price() {
+        let vm = TestVM::default();
+        vm.set_block_timestamp(1_000);
+        let mut oracle = PriceOracle::from(&vm);
+        assert!(oracle.set_source_pool(ID, SOURCE_POOL, ID).is_ok());
+        oracle._update_from_pool(ID, SOURCE_POOL, U256::from(100));
+
+        vm.set_block_timestamp(1_100);
+        let (cumulative, _) = oracle.price_cumulative(ID);
+        oracle._update_from_pool(ID, ATTACKER_POOL, U256::from(1_000_000));
+        oracle._update_from_pool(ID, U256::ZERO, U256::from(1_000_000));
+
+        vm.set_block_timestamp(1_200);
+        assert_eq!(oracle.consult(ID, U256::ZERO).ok(), Some(U256::from(100)));
+        assert_eq!(oracle.consult(ID, U256::from(150)).ok(), Some(U256::from(100)));
+        assert_eq!(oracle.price_cumulative(ID).0 - cumulative, U256::from(100 * 100));
+    }
+
+    #[test]
+    fn pools_without_a_source_are_ignored() {
+        let vm = TestVM::default();
+        let mut oracle = PriceOracle::from(&vm);
+        oracle._update_from_pool(ID, ATTACKER_POOL, U256::from(1));
+        assert!(matches!(oracle.consult(ID, U256::ZERO), Err(OracleError::NoPrice(_))));
+    }
+
+    #[test]
+    fn source_pool_must_trade_the_id() {
+        let vm = TestVM::default();
+        let mut oracle = PriceOracle::from(&vm);
+        let result = oracle.set_source_pool(ID, SOURCE_POOL, ID + U256::from(1));
+        assert!(matches!(result, Err(OracleError::SourcePoolMismatch(_))));
+        assert!(oracle.set_source_pool(ID, U256::ZERO, U256::ZERO).is_ok());
+    }
+}
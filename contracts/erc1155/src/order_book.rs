@@ -0,0 +1,216 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct OrderBook {
+        uint256 order_count;
+        mapping(uint256 => Order) orders;
+    }
+
+    pub struct Order {
+        address maker;
+        uint256 give_id;
+        uint256 give_amount;
+        uint256 give_remaining;
+        uint256 want_id;
+        uint256 want_amount;
+    }
+}
+
+sol! {
+    event OrderPosted(uint256 indexed orderId, address indexed maker, uint256 giveId, uint256 giveAmount, uint256 wantId, uint256 wantAmount);
+    event OrderFilled(uint256 indexed orderId, address indexed taker, uint256 giveFilled, uint256 wantPaid);
+    event OrderCancelled(uint256 indexed orderId, uint256 giveReturned);
+
+    error OrderBookInvalidOrder();
+    error OrderBookOrderClosed(uint256 orderId);
+    error OrderBookOverfill(uint256 orderId, uint256 requested, uint256 remaining);
+    error OrderBookNotMaker(uint256 orderId, address account);
+    error OrderBookOverflow(uint256 giveAmount, uint256 wantAmount);
+}
+
+pub enum OrderBookError {
+    InvalidOrder(OrderBookInvalidOrder),
+    OrderClosed(OrderBookOrderClosed),
+    Overfill(OrderBookOverfill),
+    NotMaker(OrderBookNotMaker),
+    Overflow(OrderBookOverflow),
+}
+
+impl From<OrderBookError> for Vec<u8> {
+    fn from(error: OrderBookError) -> Self {
+        match error {
+            OrderBookError::InvalidOrder(e) => revert::encode(e),
+            OrderBookError::OrderClosed(e) => revert::encode(e),
+            OrderBookError::Overfill(e) => revert::encode(e),
+            OrderBookError::NotMaker(e) => revert::encode(e),
+            OrderBookError::Overflow(e) => revert::encode(e),
+        }
+    }
+}
+
+/// A fill against an order, as computed by [`OrderBook::_fill`].
+pub struct Fill {
+    pub maker: Address,
+    pub give_id: U256,
+    pub want_id: U256,
+    pub want_paid: U256,
+}
+
+impl OrderBook {
+    /// Returns `(maker, give_id, give_amount, give_remaining, want_id, want_amount)`.
+    pub fn order(&self, order_id: U256) -> (Address, U256, U256, U256, U256, U256) {
+        let order = self.orders.get(order_id);
+        (
+            order.maker.get(),
+            order.give_id.get(),
+            order.give_amount.get(),
+            order.give_remaining.get(),
+            order.want_id.get(),
+            order.want_amount.get(),
+        )
+    }
+
+    pub fn order_count(&self) -> U256 {
+        self.order_count.get()
+    }
+
+    /// Records an order whose `give_amount` the caller escrows from the maker. The
+    /// amounts' product must fit, so that no fill's price can overflow.
+    pub fn _post(&mut self, give_id: U256, give_amount: U256, want_id: U256, want_amount: U256) -> Result<U256, OrderBookError> {
+        if give_amount.is_zero() || want_amount.is_zero() || give_id == want_id {
+            return Err(OrderBookError::InvalidOrder(OrderBookInvalidOrder {}));
+        }
+        price(give_amount, want_amount, give_amount)?;
+
+        let maker = self.vm().msg_sender();
+        let order_id = self.order_count.get() + U256::from(1);
+        self.order_count.set(order_id);
+
+        {
+            let mut order = self.orders.setter(order_id);
+            order.maker.set(maker);
+            order.give_id.set(give_id);
+            order.give_amount.set(give_amount);
+            order.give_remaining.set(give_amount);
+            order.want_id.set(want_id);
+            order.want_amount.set(want_amount);
+        }
+
+        log(self.vm(), OrderPosted {
+            orderId: order_id,
+            maker,
+            giveId: give_id,
+            giveAmount: give_amount,
+            wantId: want_id,
+            wantAmount: want_amount,
+        });
+        Ok(order_id)
+    }
+
+    /// Takes `amount` of the order's give side. The price is rounded up in the maker's
+    /// favour, so dust fills can't drain an order for free.
+    pub fn _fill(&mut self, order_id: U256, amount: U256) -> Result<Fill, OrderBookError> {
+        let fill = {
+            let mut order = self.orders.setter(order_id);
+            let remaining = order.give_remaining.get();
+            if remaining.is_zero() {
+                return Err(OrderBookError::OrderClosed(OrderBookOrderClosed { orderId: order_id }));
+            }
+            if amount.is_zero() || amount > remaining {
+                return Err(OrderBookError::Overfill(OrderBookOverfill {
+                    orderId: order_id,
+                    requested: amount,
+                    remaining,
+                }));
+            }
+
+            let want_paid = price(amount, order.want_amount.get(), order.give_amount.get())?;
+            order.give_remaining.set(remaining - amount);
+            Fill {
+                maker: order.maker.get(),
+                give_id: order.give_id.get(),
+                want_id: order.want_id.get(),
+                want_paid,
+            }
+        };
+
+        log(self.vm(), OrderFilled {
+            orderId: order_id,
+            taker: self.vm().msg_sender(),
+            giveFilled: amount,
+            wantPaid: fill.want_paid,
+        });
+        Ok(fill)
+    }
+
+    /// Closes the caller's order and returns `(give_id, give_remaining)` to hand back.
+    pub fn _cancel(&mut self, order_id: U256) -> Result<(U256, U256), OrderBookError> {
+        let account = self.vm().msg_sender();
+        let (give_id, remaining) = {
+            let mut order = self.orders.setter(order_id);
+            if order.maker.get() != account {
+                return Err(OrderBookError::NotMaker(OrderBookNotMaker { orderId: order_id, account }));
+            }
+            let remaining = order.give_remaining.get();
+            if remaining.is_zero() {
+                return Err(OrderBookError::OrderClosed(OrderBookOrderClosed { orderId: order_id }));
+            }
+            order.give_remaining.set(U256::ZERO);
+            (order.give_id.get(), remaining)
+        };
+
+        log(self.vm(), OrderCancelled {
+            orderId: order_id,
+            giveReturned: remaining,
+        });
+        Ok((give_id, remaining))
+    }
+}
+
+/// `amount * want_amount / give_amount`, rounded up.
+fn price(amount: U256, want_amount: U256, give_amount: U256) -> Result<U256, OrderBookError> {
+    let product = amount.checked_mul(want_amount).ok_or(OrderBookError::Overflow(OrderBookOverflow {
+        giveAmount: amount,
+        wantAmount: want_amount,
+    }))?;
+    Ok(product.div_ceil(give_amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{alloy_primitives::{address, uint}, testing::*};
+
+    const MAKER: Address = address!("a11ce00000000000000000000000000000000001");
+    const GOLD: U256 = uint!(1_U256);
+    const GEMS: U256 = uint!(2_U256);
+
+    #[test]
+    fn fills_round_up_for_the_maker() {
+        let vm = TestVM::default();
+        vm.set_sender(MAKER);
+        let mut book = OrderBook::from(&vm);
+        let order_id = book._post(GOLD, U256::from(3), GEMS, U256::from(10)).ok().unwrap();
+        let fill = book._fill(order_id, U256::from(1)).ok().unwrap();
+        assert_eq!(fill.want_paid, U256::from(4));
+        assert_eq!(book.order(order_id).3, U256::from(2));
+    }
+
+    #[test]
+    fn orders_whose_price_overflows_are_rejected() {
+        let vm = TestVM::default();
+        vm.set_sender(MAKER);
+        let mut book = OrderBook::from(&vm);
+        let result = book._post(GOLD, U256::from(2), GEMS, U256::MAX);
+        assert!(matches!(result, Err(OrderBookError::Overflow(_))));
+        assert!(book.order_count().is_zero());
+    }
+}
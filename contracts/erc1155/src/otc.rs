@@ -0,0 +1,223 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    storage::{StorageU256, StorageVec},
+    stylus_core::log,
+};
+
+use crate::{erc1155::ERC1155InvalidArrayLength, revert};
+
+sol_storage! {
+    pub struct OtcEscrow {
+        uint256 deal_count;
+        mapping(uint256 => OtcDeal) deals;
+    }
+
+    /// Party A delivers the `a_*` side, party B the `b_*` side.
+    pub struct OtcDeal {
+        address party_a;
+        address party_b;
+        uint256[] a_ids;
+        uint256[] a_amounts;
+        uint256[] b_ids;
+        uint256[] b_amounts;
+        bool a_funded;
+        bool b_funded;
+        bool closed;
+    }
+}
+
+sol! {
+    event OtcCreated(uint256 indexed dealId, address indexed partyA, address indexed partyB);
+    event OtcDeposited(uint256 indexed dealId, address indexed party);
+    event OtcExecuted(uint256 indexed dealId);
+    event OtcCancelled(uint256 indexed dealId, address indexed by);
+
+    error OtcInvalidCounterparty(address counterparty);
+    error OtcNotParty(uint256 dealId, address account);
+    error OtcAlreadyFunded(uint256 dealId, address party);
+    error OtcNotFunded(uint256 dealId);
+    error OtcFullyFunded(uint256 dealId);
+    error OtcClosed(uint256 dealId);
+}
+
+pub enum OtcError {
+    InvalidCounterparty(OtcInvalidCounterparty),
+    NotParty(OtcNotParty),
+    AlreadyFunded(OtcAlreadyFunded),
+    NotFunded(OtcNotFunded),
+    FullyFunded(OtcFullyFunded),
+    Closed(OtcClosed),
+    InvalidArrayLength(ERC1155InvalidArrayLength),
+}
+
+impl From<OtcError> for Vec<u8> {
+    fn from(error: OtcError) -> Self {
+        match error {
+            OtcError::InvalidCounterparty(e) => revert::encode(e),
+            OtcError::NotParty(e) => revert::encode(e),
+            OtcError::AlreadyFunded(e) => revert::encode(e),
+            OtcError::NotFunded(e) => revert::encode(e),
+            OtcError::FullyFunded(e) => revert::encode(e),
+            OtcError::Closed(e) => revert::encode(e),
+            OtcError::InvalidArrayLength(e) => revert::encode(e),
+        }
+    }
+}
+
+/// One side of a deal: who delivers it and what it contains.
+#[derive(Clone)]
+pub struct OtcLeg {
+    pub party: Address,
+    pub ids: Vec<U256>,
+    pub amounts: Vec<U256>,
+}
+
+fn read(list: &StorageVec<StorageU256>) -> Vec<U256> {
+    (0..list.len()).filter_map(|i| list.get(i)).collect()
+}
+
+impl OtcEscrow {
+    /// Returns `(party_a, party_b, a_funded, b_funded, closed)`.
+    pub fn deal(&self, deal_id: U256) -> (Address, Address, bool, bool, bool) {
+        let deal = self.deals.get(deal_id);
+        (
+            deal.party_a.get(),
+            deal.party_b.get(),
+            deal.a_funded.get(),
+            deal.b_funded.get(),
+            deal.closed.get(),
+        )
+    }
+
+    /// Returns `(a_ids, a_amounts, b_ids, b_amounts)`.
+    pub fn terms(&self, deal_id: U256) -> (Vec<U256>, Vec<U256>, Vec<U256>, Vec<U256>) {
+        let deal = self.deals.get(deal_id);
+        (read(&deal.a_ids), read(&deal.a_amounts), read(&deal.b_ids), read(&deal.b_amounts))
+    }
+
+    /// Records the terms with the caller as party A. Nothing moves until each side deposits.
+    pub fn _create(
+        &mut self,
+        counterparty: Address,
+        a_ids: Vec<U256>,
+        a_amounts: Vec<U256>,
+        b_ids: Vec<U256>,
+        b_amounts: Vec<U256>,
+    ) -> Result<U256, OtcError> {
+        let party_a = self.vm().msg_sender();
+        if counterparty.is_zero() || counterparty == party_a {
+            return Err(OtcError::InvalidCounterparty(OtcInvalidCounterparty { counterparty }));
+        }
+        for (ids, amounts) in [(&a_ids, &a_amounts), (&b_ids, &b_amounts)] {
+            if ids.len() != amounts.len() {
+                return Err(OtcError::InvalidArrayLength(ERC1155InvalidArrayLength {
+                    idsLength: U256::from(ids.len()),
+                    valuesLength: U256::from(amounts.len()),
+                }));
+            }
+        }
+
+        let deal_id = self.deal_count.get() + U256::from(1);
+        self.deal_count.set(deal_id);
+
+        let mut deal = self.deals.setter(deal_id);
+        deal.party_a.set(party_a);
+        deal.party_b.set(counterparty);
+        for (id, amount) in a_ids.into_iter().zip(a_amounts) {
+            deal.a_ids.push(id);
+            deal.a_amounts.push(amount);
+        }
+        for (id, amount) in b_ids.into_iter().zip(b_amounts) {
+            deal.b_ids.push(id);
+            deal.b_amounts.push(amount);
+        }
+
+        log(self.vm(), OtcCreated {
+            dealId: deal_id,
+            partyA: party_a,
+            partyB: counterparty,
+        });
+        Ok(deal_id)
+    }
+
+    /// Marks the caller's side as funded and returns what the caller must escrow.
+    pub fn _deposit(&mut self, deal_id: U256) -> Result<OtcLeg, OtcError> {
+        let party = self.vm().msg_sender();
+        let mut deal = self.deals.setter(deal_id);
+        if deal.closed.get() {
+            return Err(OtcError::Closed(OtcClosed { dealId: deal_id }));
+        }
+
+        let leg = if party == deal.party_a.get() {
+            if deal.a_funded.get() {
+                return Err(OtcError::AlreadyFunded(OtcAlreadyFunded { dealId: deal_id, party }));
+            }
+            deal.a_funded.set(true);
+            OtcLeg { party, ids: read(&deal.a_ids), amounts: read(&deal.a_amounts) }
+        } else if party == deal.party_b.get() {
+            if deal.b_funded.get() {
+                return Err(OtcError::AlreadyFunded(OtcAlreadyFunded { dealId: deal_id, party }));
+            }
+            deal.b_funded.set(true);
+            OtcLeg { party, ids: read(&deal.b_ids), amounts: read(&deal.b_amounts) }
+        } else {
+            return Err(OtcError::NotParty(OtcNotParty { dealId: deal_id, account: party }));
+        };
+
+        log(self.vm(), OtcDeposited { dealId: deal_id, party });
+        Ok(leg)
+    }
+
+    /// Closes a fully funded deal. Returns the A and B legs; each goes to the other party.
+    pub fn _execute(&mut self, deal_id: U256) -> Result<(OtcLeg, OtcLeg), OtcError> {
+        self._check_open(deal_id)?;
+        let mut deal = self.deals.setter(deal_id);
+        if !deal.a_funded.get() || !deal.b_funded.get() {
+            return Err(OtcError::NotFunded(OtcNotFunded { dealId: deal_id }));
+        }
+        deal.closed.set(true);
+        let legs = (
+            OtcLeg { party: deal.party_a.get(), ids: read(&deal.a_ids), amounts: read(&deal.a_amounts) },
+            OtcLeg { party: deal.party_b.get(), ids: read(&deal.b_ids), amounts: read(&deal.b_amounts) },
+        );
+
+        log(self.vm(), OtcExecuted { dealId: deal_id });
+        Ok(legs)
+    }
+
+    /// Closes a deal that is not yet fully funded and returns the legs to refund.
+    pub fn _cancel(&mut self, deal_id: U256) -> Result<Vec<OtcLeg>, OtcError> {
+        self._check_open(deal_id)?;
+        let mut deal = self.deals.setter(deal_id);
+        if deal.a_funded.get() && deal.b_funded.get() {
+            return Err(OtcError::FullyFunded(OtcFullyFunded { dealId: deal_id }));
+        }
+        deal.closed.set(true);
+
+        let mut refunds = Vec::new();
+        if deal.a_funded.get() {
+            refunds.push(OtcLeg { party: deal.party_a.get(), ids: read(&deal.a_ids), amounts: read(&deal.a_amounts) });
+        }
+        if deal.b_funded.get() {
+            refunds.push(OtcLeg { party: deal.party_b.get(), ids: read(&deal.b_ids), amounts: read(&deal.b_amounts) });
+        }
+
+        log(self.vm(), OtcCancelled { dealId: deal_id, by: self.vm().msg_sender() });
+        Ok(refunds)
+    }
+
+    fn _check_open(&self, deal_id: U256) -> Result<(), OtcError> {
+        let deal = self.deals.get(deal_id);
+        let account = self.vm().msg_sender();
+        if account != deal.party_a.get() && account != deal.party_b.get() {
+            return Err(OtcError::NotParty(OtcNotParty { dealId: deal_id, account }));
+        }
+        if deal.closed.get() {
+            return Err(OtcError::Closed(OtcClosed { dealId: deal_id }));
+        }
+        Ok(())
+    }
+}
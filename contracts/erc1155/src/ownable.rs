@@ -0,0 +1,50 @@
+//! `Ownable` / ERC-5313 view of [`AccessControl`]. The owner *is* the default admin, so
+//! ownership only moves through the delayed two-step default-admin transfer and there is
+//! never a second authority to keep in sync.
+
+use alloc::vec::Vec;
+use stylus_sdk::{alloy_primitives::Address, alloy_sol_types::sol, prelude::*};
+
+use crate::{access_control::AccessControl, revert};
+
+sol! {
+    event OwnershipTransferred(address indexed previousOwner, address indexed newOwner);
+
+    error OwnableUnauthorizedAccount(address account);
+    error OwnableInvalidOwner(address owner);
+}
+
+pub enum OwnableError {
+    UnauthorizedAccount(OwnableUnauthorizedAccount),
+    InvalidOwner(OwnableInvalidOwner),
+}
+
+impl From<OwnableError> for Vec<u8> {
+    fn from(error: OwnableError) -> Self {
+        match error {
+            OwnableError::UnauthorizedAccount(e) => revert::encode(e),
+            OwnableError::InvalidOwner(e) => revert::encode(e),
+        }
+    }
+}
+
+pub fn owner(access: &AccessControl) -> Address {
+    access.default_admin()
+}
+
+/// Reverts unless the caller is the owner.
+pub fn only_owner(access: &AccessControl) -> Result<(), OwnableError> {
+    let account = access.vm().msg_sender();
+    if account != owner(access) {
+        return Err(OwnableError::UnauthorizedAccount(OwnableUnauthorizedAccount { account }));
+    }
+    Ok(())
+}
+
+/// Ownership can't be handed to the zero address; renouncing goes through `renounce_role`.
+pub fn check_new_owner(new_owner: Address) -> Result<(), OwnableError> {
+    if new_owner.is_zero() {
+        return Err(OwnableError::InvalidOwner(OwnableInvalidOwner { owner: Address::ZERO }));
+    }
+    Ok(())
+}
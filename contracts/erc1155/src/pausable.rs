@@ -0,0 +1,107 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{b256, B256, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+/// `keccak256("PAUSER_ROLE")`
+pub const PAUSER_ROLE: B256 =
+    b256!("65d7a28e3265b37a6474929f336521b332c1681b933f6cb9f3376673440d862a");
+
+pub const PAUSE_MINT: U256 = U256::from_limbs([1 << 0, 0, 0, 0]);
+pub const PAUSE_BURN: U256 = U256::from_limbs([1 << 1, 0, 0, 0]);
+pub const PAUSE_TRANSFER: U256 = U256::from_limbs([1 << 2, 0, 0, 0]);
+pub const PAUSE_MARKETPLACE: U256 = U256::from_limbs([1 << 3, 0, 0, 0]);
+pub const PAUSE_STAKING: U256 = U256::from_limbs([1 << 4, 0, 0, 0]);
+
+sol_storage! {
+    pub struct Pausable {
+        uint256 flags;
+        bool paused;
+    }
+}
+
+sol! {
+    event PauseFlagsSet(address indexed account, uint256 flags);
+    event Paused(address account);
+    event Unpaused(address account);
+
+    error CapabilityPaused(uint256 capability);
+    error EnforcedPause();
+    error ExpectedPause();
+}
+
+pub enum PausableError {
+    CapabilityPaused(CapabilityPaused),
+    EnforcedPause(EnforcedPause),
+    ExpectedPause(ExpectedPause),
+}
+
+impl From<PausableError> for Vec<u8> {
+    fn from(error: PausableError) -> Self {
+        match error {
+            PausableError::CapabilityPaused(e) => revert::encode(e),
+            PausableError::EnforcedPause(e) => revert::encode(e),
+            PausableError::ExpectedPause(e) => revert::encode(e),
+        }
+    }
+}
+
+impl Pausable {
+    pub fn flags(&self) -> U256 {
+        self.flags.get()
+    }
+
+    /// True if any capability in `mask` is paused.
+    pub fn is_paused(&self, mask: U256) -> bool {
+        !(self.flags.get() & mask).is_zero()
+    }
+
+    pub fn set_flags(&mut self, flags: U256) {
+        self.flags.set(flags);
+        log(self.vm(), PauseFlagsSet {
+            account: self.vm().msg_sender(),
+            flags,
+        });
+    }
+
+    pub fn when_not_paused(&self, mask: U256) -> Result<(), PausableError> {
+        let paused = self.flags.get() & mask;
+        if !paused.is_zero() {
+            return Err(PausableError::CapabilityPaused(CapabilityPaused { capability: paused }));
+        }
+        Ok(())
+    }
+
+    /// The emergency stop, independent of the capability flags.
+    pub fn paused(&self) -> bool {
+        self.paused.get()
+    }
+
+    pub fn pause(&mut self) -> Result<(), PausableError> {
+        self.when_not_stopped()?;
+        self.paused.set(true);
+        log(self.vm(), Paused { account: self.vm().msg_sender() });
+        Ok(())
+    }
+
+    pub fn unpause(&mut self) -> Result<(), PausableError> {
+        if !self.paused() {
+            return Err(PausableError::ExpectedPause(ExpectedPause {}));
+        }
+        self.paused.set(false);
+        log(self.vm(), Unpaused { account: self.vm().msg_sender() });
+        Ok(())
+    }
+
+    pub fn when_not_stopped(&self) -> Result<(), PausableError> {
+        if self.paused() {
+            return Err(PausableError::EnforcedPause(EnforcedPause {}));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{b256, Address, B256, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+};
+
+use crate::{
+    ecdsa,
+    eip712::{self, address_word, bool_word},
+    erc1155::ERC1155InvalidArrayLength,
+    nonces::SignatureNonces,
+    revert,
+    webauthn::Passkeys,
+};
+
+/// `keccak256("Permit(address owner,address operator,bool approved,uint256 nonce,uint256 deadline)")`
+pub const PERMIT_TYPEHASH: B256 =
+    b256!("7bf72c3e57bc00556754c7454ead7d72d1c0e263909e7c643fbc0d1e0f3c3e74");
+
+/// `keccak256("PermitBatch(address owner,address[] operators,bool[] approved,uint256 nonce,uint256 deadline)")`
+pub const PERMIT_BATCH_TYPEHASH: B256 =
+    b256!("c71209065c333b25cb3c4e60fdf8863a6562bfae78d37ddf53fe57d0aaa57850");
+
+sol! {
+    error ERC1155PermitExpired(uint256 deadline);
+    error ERC1155PermitInvalidSigner(address signer, address owner);
+}
+
+pub enum PermitError {
+    Expired(ERC1155PermitExpired),
+    InvalidSigner(ERC1155PermitInvalidSigner),
+    InvalidArrayLength(ERC1155InvalidArrayLength),
+}
+
+impl From<PermitError> for Vec<u8> {
+    fn from(error: PermitError) -> Self {
+        match error {
+            PermitError::Expired(e) => revert::encode(e),
+            PermitError::InvalidSigner(e) => revert::encode(e),
+            PermitError::InvalidArrayLength(e) => revert::encode(e),
+        }
+    }
+}
+
+/// Checks a single-operator `Permit` signature and consumes `owner`'s sequential nonce.
+/// The caller applies the approval.
+pub fn verify(
+    nonces: &mut SignatureNonces,
+    passkeys: &Passkeys,
+    owner: Address,
+    operator: Address,
+    approved: bool,
+    deadline: U256,
+    signature: &[u8],
+) -> Result<(), PermitError> {
+    if U256::from(nonces.vm().block_timestamp()) > deadline {
+        return Err(PermitError::Expired(ERC1155PermitExpired { deadline }));
+    }
+    let struct_hash = eip712::hash_words(&[
+        PERMIT_TYPEHASH,
+        address_word(owner),
+        address_word(operator),
+        bool_word(approved),
+        B256::from(nonces._use_next(owner)),
+        B256::from(deadline),
+    ]);
+    check_signer(passkeys, owner, eip712::hash_typed_data(nonces.vm(), struct_hash), signature)
+}
+
+/// Checks a `PermitBatch` signature, ECDSA or `owner`'s passkey, and consumes `owner`'s
+/// sequential nonce. The caller applies the approvals.
+pub fn verify_batch(
+    nonces: &mut SignatureNonces,
+    passkeys: &Passkeys,
+    owner: Address,
+    operators: &[Address],
+    approved: &[bool],
+    deadline: U256,
+    signature: &[u8],
+) -> Result<(), PermitError> {
+    if operators.len() != approved.len() {
+        return Err(PermitError::InvalidArrayLength(ERC1155InvalidArrayLength {
+            idsLength: U256::from(operators.len()),
+            valuesLength: U256::from(approved.len()),
+        }));
+    }
+    if U256::from(nonces.vm().block_timestamp()) > deadline {
+        return Err(PermitError::Expired(ERC1155PermitExpired { deadline }));
+    }
+
+    let operator_words: Vec<B256> = operators.iter().map(|a| address_word(*a)).collect();
+    let approved_words: Vec<B256> = approved.iter().map(|a| bool_word(*a)).collect();
+    let struct_hash = eip712::hash_words(&[
+        PERMIT_BATCH_TYPEHASH,
+        address_word(owner),
+        eip712::hash_words(&operator_words),
+        eip712::hash_words(&approved_words),
+        B256::from(nonces._use_next(owner)),
+        B256::from(deadline),
+    ]);
+    check_signer(passkeys, owner, eip712::hash_typed_data(nonces.vm(), struct_hash), signature)
+}
+
+fn check_signer(passkeys: &Passkeys, owner: Address, digest: B256, signature: &[u8]) -> Result<(), PermitError> {
+    if passkeys.verify(owner, digest, signature) {
+        return Ok(());
+    }
+    let signer = ecdsa::recover_signature(digest, signature).unwrap_or_default();
+    if signer != owner {
+        return Err(PermitError::InvalidSigner(ERC1155PermitInvalidSigner { signer, owner }));
+    }
+    Ok(())
+}
@@ -0,0 +1,300 @@
+//! Single-owner id/ETH trading pools in the style of sudoswap. The owner deposits units
+//! of one id plus ETH; traders buy and sell against a linear or exponential price curve
+//! and pay the pool's fee on top, which stays in the pool for the owner.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{uint, Address, U256, U8},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::{
+    bonding_curve::{wad_pow, CURVE_EXPONENTIAL, CURVE_LINEAR, WAD},
+    revert,
+};
+
+/// Fee denominator; `fee_bps` is in basis points.
+const BPS: U256 = uint!(10_000_U256);
+pub const MAX_FEE_BPS: U256 = uint!(1_000_U256);
+
+sol_storage! {
+    pub struct TradingPools {
+        uint256 pool_count;
+        mapping(uint256 => Pool) pools;
+    }
+
+    /// Linear pools move `spot_price` by `delta` wei per unit; exponential pools
+    /// multiply it by `delta` (WAD-scaled, above 1e18) per unit.
+    pub struct Pool {
+        address owner;
+        uint256 id;
+        uint8 kind;
+        uint256 spot_price;
+        uint256 delta;
+        uint256 fee_bps;
+        uint256 eth_reserve;
+        uint256 token_reserve;
+    }
+}
+
+sol! {
+    event PoolCreated(uint256 indexed poolId, address indexed owner, uint256 indexed id, uint8 kind, uint256 spotPrice, uint256 delta, uint256 feeBps);
+    event PoolLiquidityChanged(uint256 indexed poolId, uint256 ethReserve, uint256 tokenReserve);
+    event PoolSwap(uint256 indexed poolId, address indexed trader, bool buy, uint256 amount, uint256 ethAmount, uint256 fee, uint256 newSpotPrice);
+
+    error PoolInvalidConfig(uint8 kind, uint256 delta, uint256 feeBps);
+    error PoolNotOwner(uint256 poolId, address account);
+    error PoolInsufficientLiquidity(uint256 poolId, uint256 requested, uint256 available);
+    error PoolSlippage(uint256 ethAmount, uint256 limit);
+    error PoolOverflow(uint256 poolId);
+    error PoolPaymentFailed(address to, uint256 amount);
+}
+
+pub enum PoolError {
+    InvalidConfig(PoolInvalidConfig),
+    NotOwner(PoolNotOwner),
+    InsufficientLiquidity(PoolInsufficientLiquidity),
+    Slippage(PoolSlippage),
+    Overflow(PoolOverflow),
+    PaymentFailed(PoolPaymentFailed),
+}
+
+impl From<PoolError> for Vec<u8> {
+    fn from(error: PoolError) -> Self {
+        match error {
+            PoolError::InvalidConfig(e) => revert::encode(e),
+            PoolError::NotOwner(e) => revert::encode(e),
+            PoolError::InsufficientLiquidity(e) => revert::encode(e),
+            PoolError::Slippage(e) => revert::encode(e),
+            PoolError::Overflow(e) => revert::encode(e),
+            PoolError::PaymentFailed(e) => revert::encode(e),
+        }
+    }
+}
+
+/// A priced trade: `eth` excludes the fee, `new_spot` is the spot price afterwards.
+pub struct Quote {
+    pub eth: U256,
+    pub fee: U256,
+    pub new_spot: U256,
+}
+
+impl TradingPools {
+    /// Returns `(owner, id, kind, spot_price, delta, fee_bps, eth_reserve, token_reserve)`.
+    pub fn pool(&self, pool_id: U256) -> (Address, U256, u8, U256, U256, U256, U256, U256) {
+        let pool = self.pools.get(pool_id);
+        (
+            pool.owner.get(),
+            pool.id.get(),
+            pool.kind.get().to::<u8>(),
+            pool.spot_price.get(),
+            pool.delta.get(),
+            pool.fee_bps.get(),
+            pool.eth_reserve.get(),
+            pool.token_reserve.get(),
+        )
+    }
+
+    pub fn spot_price(&self, pool_id: U256) -> U256 {
+        self.pools.get(pool_id).spot_price.get()
+    }
+
+    pub fn pool_count(&self) -> U256 {
+        self.pool_count.get()
+    }
+
+    pub fn _create(&mut self, id: U256, kind: u8, spot_price: U256, delta: U256, fee_bps: U256) -> Result<U256, PoolError> {
+        let valid_kind = kind == CURVE_LINEAR || (kind == CURVE_EXPONENTIAL && delta > WAD);
+        if !valid_kind || fee_bps > MAX_FEE_BPS {
+            return Err(PoolError::InvalidConfig(PoolInvalidConfig { kind, delta, feeBps: fee_bps }));
+        }
+
+        let owner = self.vm().msg_sender();
+        let pool_id = self.pool_count.get() + U256::from(1);
+        self.pool_count.set(pool_id);
+
+        let mut pool = self.pools.setter(pool_id);
+        pool.owner.set(owner);
+        pool.id.set(id);
+        pool.kind.set(U8::from(kind));
+        pool.spot_price.set(spot_price);
+        pool.delta.set(delta);
+        pool.fee_bps.set(fee_bps);
+
+        log(self.vm(), PoolCreated {
+            poolId: pool_id,
+            owner,
+            id,
+            kind,
+            spotPrice: spot_price,
+            delta,
+            feeBps: fee_bps,
+        });
+        Ok(pool_id)
+    }
+
+    /// Owner-only. Adds `eth_in`/`tokens_in` and removes `eth_out`/`tokens_out`; returns the pool's id.
+    pub fn _rebalance(
+        &mut self,
+        pool_id: U256,
+        eth_in: U256,
+        tokens_in: U256,
+        eth_out: U256,
+        tokens_out: U256,
+    ) -> Result<U256, PoolError> {
+        let account = self.vm().msg_sender();
+        let mut pool = self.pools.setter(pool_id);
+        if pool.owner.get() != account {
+            return Err(PoolError::NotOwner(PoolNotOwner { poolId: pool_id, account }));
+        }
+
+        let eth = pool.eth_reserve.get() + eth_in;
+        let tokens = pool.token_reserve.get() + tokens_in;
+        if eth_out > eth {
+            return Err(PoolError::InsufficientLiquidity(PoolInsufficientLiquidity {
+                poolId: pool_id,
+                requested: eth_out,
+                available: eth,
+            }));
+        }
+        if tokens_out > tokens {
+            return Err(PoolError::InsufficientLiquidity(PoolInsufficientLiquidity {
+                poolId: pool_id,
+                requested: tokens_out,
+                available: tokens,
+            }));
+        }
+        pool.eth_reserve.set(eth - eth_out);
+        pool.token_reserve.set(tokens - tokens_out);
+        let id = pool.id.get();
+
+        log(self.vm(), PoolLiquidityChanged {
+            poolId: pool_id,
+            ethReserve: eth - eth_out,
+            tokenReserve: tokens - tokens_out,
+        });
+        Ok(id)
+    }
+
+    /// Cost of buying `amount` units from the pool, rounded up.
+    pub fn quote_buy(&self, pool_id: U256, amount: U256) -> Result<Quote, PoolError> {
+        let pool = self.pools.get(pool_id);
+        let available = pool.token_reserve.get();
+        if amount.is_zero() || amount > available {
+            return Err(PoolError::InsufficientLiquidity(PoolInsufficientLiquidity {
+                poolId: pool_id,
+                requested: amount,
+                available,
+            }));
+        }
+
+        let spot = pool.spot_price.get();
+        let delta = pool.delta.get();
+        let priced = match pool.kind.get().to::<u8>() {
+            // sum_{i=1..n} (spot + i * delta)
+            CURVE_LINEAR => amount
+                .checked_add(U256::from(1))
+                .and_then(|n1| amount.checked_mul(n1))
+                .and_then(|steps| delta.checked_mul(steps / U256::from(2)))
+                .and_then(|slope| spot.checked_mul(amount)?.checked_add(slope))
+                .zip(delta.checked_mul(amount).and_then(|d| spot.checked_add(d))),
+            // sum_{i=1..n} spot * delta^i = spot * delta * (delta^n - 1) / (delta - 1)
+            _ => wad_pow(delta, amount).and_then(|growth| {
+                let eth = spot
+                    .checked_mul(delta)?
+                    .checked_mul(growth - WAD)?
+                    .div_ceil(WAD.checked_mul(delta - WAD)?);
+                Some((eth, spot.checked_mul(growth)? / WAD))
+            }),
+        };
+        let (eth, new_spot) = priced.ok_or(PoolError::Overflow(PoolOverflow { poolId: pool_id }))?;
+        let fee = (eth * pool.fee_bps.get()).div_ceil(BPS);
+        Ok(Quote { eth, fee, new_spot })
+    }
+
+    /// Proceeds of selling `amount` units into the pool, rounded down.
+    pub fn quote_sell(&self, pool_id: U256, amount: U256) -> Result<Quote, PoolError> {
+        let pool = self.pools.get(pool_id);
+        let spot = pool.spot_price.get();
+        let delta = pool.delta.get();
+        let priced = match pool.kind.get().to::<u8>() {
+            // sum_{i=0..n-1} (spot - i * delta); the price can't go below zero.
+            CURVE_LINEAR => delta
+                .checked_mul(amount.saturating_sub(U256::from(1)))
+                .filter(|last| *last <= spot)
+                .and_then(|_| amount.checked_mul(amount.saturating_sub(U256::from(1))))
+                .and_then(|steps| delta.checked_mul(steps / U256::from(2)))
+                .and_then(|slope| spot.checked_mul(amount)?.checked_sub(slope))
+                .map(|eth| (eth, delta.checked_mul(amount).map_or(U256::ZERO, |d| spot.saturating_sub(d)))),
+            // sum_{i=0..n-1} spot / delta^i = spot * (delta^n - 1) * delta / (delta^n * (delta - 1))
+            _ => wad_pow(delta, amount).and_then(|growth| {
+                let eth = spot.checked_mul(growth - WAD)?.checked_mul(delta)? / growth.checked_mul(delta - WAD)?;
+                Some((eth, spot.checked_mul(WAD)? / growth))
+            }),
+        };
+        let (eth, new_spot) = priced.ok_or(PoolError::Overflow(PoolOverflow { poolId: pool_id }))?;
+        let available = pool.eth_reserve.get();
+        if amount.is_zero() || eth > available {
+            return Err(PoolError::InsufficientLiquidity(PoolInsufficientLiquidity {
+                poolId: pool_id,
+                requested: eth,
+                available,
+            }));
+        }
+        let fee = eth * pool.fee_bps.get() / BPS;
+        Ok(Quote { eth, fee, new_spot })
+    }
+
+    /// Books a buy paid with `value`; returns `(id, cost)` where cost includes the fee.
+    pub fn _buy(&mut self, pool_id: U256, amount: U256, value: U256) -> Result<(U256, U256), PoolError> {
+        let quote = self.quote_buy(pool_id, amount)?;
+        let cost = quote.eth + quote.fee;
+        if cost > value {
+            return Err(PoolError::Slippage(PoolSlippage { ethAmount: cost, limit: value }));
+        }
+        let id = {
+            let mut pool = self.pools.setter(pool_id);
+            pool.eth_reserve.set(pool.eth_reserve.get() + cost);
+            pool.token_reserve.set(pool.token_reserve.get() - amount);
+            pool.spot_price.set(quote.new_spot);
+            pool.id.get()
+        };
+        self._log_swap(pool_id, true, amount, cost, quote);
+        Ok((id, cost))
+    }
+
+    /// Books a sell; returns `(id, proceeds)` net of the fee.
+    pub fn _sell(&mut self, pool_id: U256, amount: U256, min_proceeds: U256) -> Result<(U256, U256), PoolError> {
+        let quote = self.quote_sell(pool_id, amount)?;
+        let proceeds = quote.eth - quote.fee;
+        if proceeds < min_proceeds {
+            return Err(PoolError::Slippage(PoolSlippage {
+                ethAmount: proceeds,
+                limit: min_proceeds,
+            }));
+        }
+        let id = {
+            let mut pool = self.pools.setter(pool_id);
+            pool.eth_reserve.set(pool.eth_reserve.get() - proceeds);
+            pool.token_reserve.set(pool.token_reserve.get() + amount);
+            pool.spot_price.set(quote.new_spot);
+            pool.id.get()
+        };
+        self._log_swap(pool_id, false, amount, proceeds, quote);
+        Ok((id, proceeds))
+    }
+
+    fn _log_swap(&self, pool_id: U256, buy: bool, amount: U256, eth_amount: U256, quote: Quote) {
+        log(self.vm(), PoolSwap {
+            poolId: pool_id,
+            trader: self.vm().msg_sender(),
+            buy,
+            amount,
+            ethAmount: eth_amount,
+            fee: quote.fee,
+            newSpotPrice: quote.new_spot,
+        });
+    }
+}
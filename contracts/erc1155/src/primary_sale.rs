@@ -0,0 +1,258 @@
+//! Primary sales whose proceeds sit in escrow for a dispute window. Until the window of
+//! a purchase closes its buyer can burn the units for a full refund; afterwards the
+//! proceeds are credited to the creator, who withdraws them.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::{buyback::share_of, revert};
+
+sol_storage! {
+    pub struct EscrowedSales {
+        mapping(uint256 => address) creator;
+        mapping(uint256 => uint256) price;
+        mapping(uint256 => uint256) dispute_window;
+        uint256 purchase_count;
+        mapping(uint256 => Purchase) purchases;
+        mapping(address => uint256) owed;
+    }
+
+    pub struct Purchase {
+        address buyer;
+        address creator;
+        uint256 id;
+        uint256 amount;
+        uint256 price;
+        uint256 refundable_until;
+    }
+}
+
+sol! {
+    event SaleConfigured(uint256 indexed id, address creator, uint256 price, uint256 disputeWindow);
+    event EscrowedPurchase(uint256 indexed purchaseId, address indexed buyer, uint256 indexed id, uint256 amount, uint256 paid, uint256 refundableUntil);
+    event PurchaseRefunded(uint256 indexed purchaseId, uint256 amount, uint256 refund);
+    event SaleProceedsReleased(uint256 indexed purchaseId, address indexed creator, uint256 amount);
+    event SaleProceedsWithdrawn(address indexed creator, uint256 amount);
+
+    error SaleNotActive(uint256 id);
+    error SaleIncorrectPayment(uint256 expected, uint256 received);
+    error SaleNotBuyer(uint256 purchaseId, address account);
+    error SaleDisputeWindowClosed(uint256 purchaseId, uint256 refundableUntil);
+    error SaleDisputeWindowOpen(uint256 purchaseId, uint256 refundableUntil);
+    error SaleRefundExceedsPurchase(uint256 purchaseId, uint256 requested, uint256 remaining);
+    error SalePaymentFailed(address to, uint256 amount);
+    error SaleOverflow(uint256 id, uint256 amount);
+}
+
+pub enum SaleError {
+    NotActive(SaleNotActive),
+    IncorrectPayment(SaleIncorrectPayment),
+    NotBuyer(SaleNotBuyer),
+    DisputeWindowClosed(SaleDisputeWindowClosed),
+    DisputeWindowOpen(SaleDisputeWindowOpen),
+    RefundExceedsPurchase(SaleRefundExceedsPurchase),
+    PaymentFailed(SalePaymentFailed),
+    Overflow(SaleOverflow),
+}
+
+impl From<SaleError> for Vec<u8> {
+    fn from(error: SaleError) -> Self {
+        match error {
+            SaleError::NotActive(e) => revert::encode(e),
+            SaleError::IncorrectPayment(e) => revert::encode(e),
+            SaleError::NotBuyer(e) => revert::encode(e),
+            SaleError::DisputeWindowClosed(e) => revert::encode(e),
+            SaleError::DisputeWindowOpen(e) => revert::encode(e),
+            SaleError::RefundExceedsPurchase(e) => revert::encode(e),
+            SaleError::PaymentFailed(e) => revert::encode(e),
+            SaleError::Overflow(e) => revert::encode(e),
+        }
+    }
+}
+
+impl EscrowedSales {
+    /// Returns `(creator, price, dispute_window)` for `id`.
+    pub fn config(&self, id: U256) -> (Address, U256, U256) {
+        (self.creator.get(id), self.price.get(id), self.dispute_window.get(id))
+    }
+
+    /// Returns `(buyer, creator, id, amount, price, refundable_until)`; `amount` is what
+    /// is still escrowed, zero once refunded or released.
+    pub fn purchase(&self, purchase_id: U256) -> (Address, Address, U256, U256, U256, U256) {
+        let purchase = self.purchases.get(purchase_id);
+        (
+            purchase.buyer.get(),
+            purchase.creator.get(),
+            purchase.id.get(),
+            purchase.amount.get(),
+            purchase.price.get(),
+            purchase.refundable_until.get(),
+        )
+    }
+
+    pub fn owed(&self, creator: Address) -> U256 {
+        self.owed.get(creator)
+    }
+
+    /// A zero `creator` closes the sale; purchases already made keep their terms.
+    pub fn configure(&mut self, id: U256, creator: Address, price: U256, dispute_window: U256) {
+        self.creator.insert(id, creator);
+        self.price.insert(id, price);
+        self.dispute_window.insert(id, dispute_window);
+        log(self.vm(), SaleConfigured {
+            id,
+            creator,
+            price,
+            disputeWindow: dispute_window,
+        });
+    }
+
+    /// Books a purchase paid with exactly `value`; the caller mints the units.
+    pub fn _buy(&mut self, id: U256, amount: U256, value: U256) -> Result<U256, SaleError> {
+        let creator = self.creator.get(id);
+        if creator.is_zero() {
+            return Err(SaleError::NotActive(SaleNotActive { id }));
+        }
+        let price = self.price.get(id);
+        let expected = cost(id, price, amount)?;
+        if value != expected {
+            return Err(SaleError::IncorrectPayment(SaleIncorrectPayment { expected, received: value }));
+        }
+
+        let buyer = self.vm().msg_sender();
+        let purchase_id = self.purchase_count.get() + U256::from(1);
+        self.purchase_count.set(purchase_id);
+        let refundable_until = U256::from(self.vm().block_timestamp()) + self.dispute_window.get(id);
+
+        let mut purchase = self.purchases.setter(purchase_id);
+        purchase.buyer.set(buyer);
+        purchase.creator.set(creator);
+        purchase.id.set(id);
+        purchase.amount.set(amount);
+        purchase.price.set(price);
+        purchase.refundable_until.set(refundable_until);
+
+        log(self.vm(), EscrowedPurchase {
+            purchaseId: purchase_id,
+            buyer,
+            id,
+            amount,
+            paid: value,
+            refundableUntil: refundable_until,
+        });
+        Ok(purchase_id)
+    }
+
+    /// Returns `(id, refund)`; the caller burns `amount` from the buyer and pays the refund.
+    pub fn _refund(&mut self, purchase_id: U256, amount: U256) -> Result<(U256, U256), SaleError> {
+        let account = self.vm().msg_sender();
+        let now = U256::from(self.vm().block_timestamp());
+        let mut purchase = self.purchases.setter(purchase_id);
+        if purchase.buyer.get() != account {
+            return Err(SaleError::NotBuyer(SaleNotBuyer { purchaseId: purchase_id, account }));
+        }
+        let refundable_until = purchase.refundable_until.get();
+        if now > refundable_until {
+            return Err(SaleError::DisputeWindowClosed(SaleDisputeWindowClosed {
+                purchaseId: purchase_id,
+                refundableUntil: refundable_until,
+            }));
+        }
+        let remaining = purchase.amount.get();
+        if amount > remaining {
+            return Err(SaleError::RefundExceedsPurchase(SaleRefundExceedsPurchase {
+                purchaseId: purchase_id,
+                requested: amount,
+                remaining,
+            }));
+        }
+        let id = purchase.id.get();
+        let refund = cost(id, purchase.price.get(), amount)?;
+        purchase.amount.set(remaining - amount);
+
+        log(self.vm(), PurchaseRefunded {
+            purchaseId: purchase_id,
+            amount,
+            refund,
+        });
+        Ok((id, refund))
+    }
+
+    /// Permissionless: credits the creator with what is left of a purchase whose
+    /// dispute window has closed, less `treasury_bps` of it, which is returned for the
+    /// buyback budget.
+    pub fn release(&mut self, purchase_id: U256, treasury_bps: U256) -> Result<U256, SaleError> {
+        let now = U256::from(self.vm().block_timestamp());
+        let mut purchase = self.purchases.setter(purchase_id);
+        let refundable_until = purchase.refundable_until.get();
+        if now <= refundable_until {
+            return Err(SaleError::DisputeWindowOpen(SaleDisputeWindowOpen {
+                purchaseId: purchase_id,
+                refundableUntil: refundable_until,
+            }));
+        }
+        let creator = purchase.creator.get();
+        let proceeds = cost(purchase.id.get(), purchase.price.get(), purchase.amount.get())?;
+        purchase.amount.set(U256::ZERO);
+        let cut = share_of(proceeds, treasury_bps);
+        let amount = proceeds - cut;
+        self.owed.insert(creator, self.owed.get(creator) + amount);
+
+        log(self.vm(), SaleProceedsReleased {
+            purchaseId: purchase_id,
+            creator,
+            amount,
+        });
+        Ok(cut)
+    }
+
+    /// Zeroes the caller's released proceeds and returns the amount to pay out.
+    pub fn _withdraw(&mut self) -> U256 {
+        let creator = self.vm().msg_sender();
+        let amount = self.owed.get(creator);
+        self.owed.delete(creator);
+        log(self.vm(), SaleProceedsWithdrawn { creator, amount });
+        amount
+    }
+}
+
+/// `price * amount`, reverting instead of wrapping.
+fn cost(id: U256, price: U256, amount: U256) -> Result<U256, SaleError> {
+    price
+        .checked_mul(amount)
+        .ok_or(SaleError::Overflow(SaleOverflow { id, amount }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{
+        alloy_primitives::{address, uint},
+        testing::*,
+    };
+
+    const CREATOR: Address = address!("c4ea700000000000000000000000000000000001");
+    const ID: U256 = uint!(1_U256);
+    const ONE_ETH: U256 = uint!(1_000_000_000_000_000_000_U256);
+
+    #[test]
+    fn buy_with_wrapping_amount_reverts() {
+        let vm = TestVM::default();
+        let mut sales = EscrowedSales::from(&vm);
+        sales.creator.insert(ID, CREATOR);
+        sales.price.insert(ID, ONE_ETH);
+
+        // 2^256 / 1e18 rounded up: `price * amount` wraps to a few wei.
+        let amount = U256::MAX / ONE_ETH + U256::from(1);
+        let wrapped = ONE_ETH.wrapping_mul(amount);
+        assert!(wrapped < ONE_ETH);
+        assert!(matches!(sales._buy(ID, amount, wrapped), Err(SaleError::Overflow(_))));
+        assert!(matches!(sales._buy(ID, amount, U256::ZERO), Err(SaleError::Overflow(_))));
+        assert_eq!(sales.purchase_count.get(), U256::ZERO);
+    }
+}
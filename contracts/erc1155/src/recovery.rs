@@ -0,0 +1,322 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct AccountRecovery {
+        mapping(address => address[]) guardians;
+        mapping(address => uint256) threshold;
+        mapping(address => uint256) delay;
+        mapping(address => address) successor;
+        mapping(address => uint256) round;
+        mapping(address => uint256) ready_at;
+        // holder => round => guardian => the successor that guardian currently backs.
+        mapping(address => mapping(uint256 => mapping(address => address))) votes;
+        // holder => round => candidate successor => guardians backing it.
+        mapping(address => mapping(uint256 => mapping(address => uint256))) approvals;
+    }
+}
+
+sol! {
+    event RecoveryConfigured(address indexed holder, address[] guardians, uint256 threshold, uint256 delay);
+    event RecoveryApproved(address indexed holder, address indexed guardian, address successor, uint256 approvals);
+    event RecoveryScheduled(address indexed holder, address indexed successor, uint256 readyAt);
+    event RecoveryUnscheduled(address indexed holder, address indexed successor);
+    event RecoveryCancelled(address indexed holder);
+    event RecoveryExecuted(address indexed holder, address indexed successor, uint256[] ids);
+
+    error RecoveryInvalidConfig(uint256 guardians, uint256 threshold);
+    error RecoveryNotGuardian(address holder, address account);
+    error RecoveryInvalidSuccessor(address successor);
+    error RecoveryNotReady(address holder, uint256 readyAt);
+    error RecoveryUnauthorized(address holder, address account);
+    error RecoveryNothingToMove(address holder);
+}
+
+pub enum RecoveryError {
+    InvalidConfig(RecoveryInvalidConfig),
+    NotGuardian(RecoveryNotGuardian),
+    InvalidSuccessor(RecoveryInvalidSuccessor),
+    NotReady(RecoveryNotReady),
+    Unauthorized(RecoveryUnauthorized),
+    NothingToMove(RecoveryNothingToMove),
+}
+
+impl From<RecoveryError> for Vec<u8> {
+    fn from(error: RecoveryError) -> Self {
+        match error {
+            RecoveryError::InvalidConfig(e) => revert::encode(e),
+            RecoveryError::NotGuardian(e) => revert::encode(e),
+            RecoveryError::InvalidSuccessor(e) => revert::encode(e),
+            RecoveryError::NotReady(e) => revert::encode(e),
+            RecoveryError::Unauthorized(e) => revert::encode(e),
+            RecoveryError::NothingToMove(e) => revert::encode(e),
+        }
+    }
+}
+
+impl AccountRecovery {
+    pub fn guardians(&self, holder: Address) -> Vec<Address> {
+        let list = self.guardians.get(holder);
+        (0..list.len()).filter_map(|i| list.get(i)).collect()
+    }
+
+    /// Returns `(threshold, delay)` for `holder`.
+    pub fn config(&self, holder: Address) -> (U256, U256) {
+        (self.threshold.get(holder), self.delay.get(holder))
+    }
+
+    /// Returns `(successor, approvals, ready_at)` of the scheduled recovery; all zero
+    /// until a successor reaches the threshold.
+    pub fn status(&self, holder: Address) -> (Address, U256, U256) {
+        let successor = self.successor.get(holder);
+        (successor, self.approvals(holder, successor), self.ready_at.get(holder))
+    }
+
+    /// Guardians currently backing `successor` for `holder` in the open round.
+    pub fn approvals(&self, holder: Address, successor: Address) -> U256 {
+        if successor.is_zero() {
+            return U256::ZERO;
+        }
+        let round = self.round.get(holder);
+        self.approvals.get(holder).get(round).get(successor)
+    }
+
+    /// Replaces the caller's guardian set. Any pending recovery is discarded.
+    pub fn configure(&mut self, guardians: Vec<Address>, threshold: U256, delay: U256) -> Result<(), RecoveryError> {
+        let holder = self.vm().msg_sender();
+        let count = U256::from(guardians.len());
+        let has_invalid = guardians
+            .iter()
+            .enumerate()
+            .any(|(i, g)| g.is_zero() || *g == holder || guardians[..i].contains(g));
+        if has_invalid || threshold.is_zero() || threshold > count {
+            return Err(RecoveryError::InvalidConfig(RecoveryInvalidConfig {
+                guardians: count,
+                threshold,
+            }));
+        }
+
+        {
+            let mut list = self.guardians.setter(holder);
+            list.erase();
+            for guardian in guardians.iter() {
+                list.push(*guardian);
+            }
+        }
+        self.threshold.insert(holder, threshold);
+        self.delay.insert(holder, delay);
+        self._reset(holder);
+
+        log(self.vm(), RecoveryConfigured {
+            holder,
+            guardians,
+            threshold,
+            delay,
+        });
+        Ok(())
+    }
+
+    /// Records the caller's vote to hand `holder`'s balances to `successor`. Votes are
+    /// counted per candidate, so a guardian backing someone else only moves its own
+    /// vote. The first candidate to reach the threshold starts the holder's timelock;
+    /// it is unscheduled if votes move away from it before execution.
+    pub fn approve(&mut self, holder: Address, successor: Address) -> Result<(), RecoveryError> {
+        let guardian = self.vm().msg_sender();
+        if !self.guardians(holder).contains(&guardian) {
+            return Err(RecoveryError::NotGuardian(RecoveryNotGuardian { holder, account: guardian }));
+        }
+        if successor.is_zero() || successor == holder {
+            return Err(RecoveryError::InvalidSuccessor(RecoveryInvalidSuccessor { successor }));
+        }
+
+        let round = self.round.get(holder);
+        let previous = self.votes.get(holder).get(round).get(guardian);
+        if previous == successor {
+            return Ok(());
+        }
+        {
+            let mut round_votes = self.votes.setter(holder);
+            round_votes.setter(round).insert(guardian, successor);
+        }
+        if !previous.is_zero() {
+            let count = self._approvals_mut(holder, round, previous, false);
+            if previous == self.successor.get(holder) && count < self.threshold.get(holder) {
+                self.successor.delete(holder);
+                self.ready_at.delete(holder);
+                log(self.vm(), RecoveryUnscheduled { holder, successor: previous });
+            }
+        }
+        let approvals = self._approvals_mut(holder, round, successor, true);
+
+        log(self.vm(), RecoveryApproved {
+            holder,
+            guardian,
+            successor,
+            approvals,
+        });
+
+        if approvals >= self.threshold.get(holder) && self.successor.get(holder).is_zero() {
+            let ready_at = U256::from(self.vm().block_timestamp()) + self.delay.get(holder);
+            self.successor.insert(holder, successor);
+            self.ready_at.insert(holder, ready_at);
+            log(self.vm(), RecoveryScheduled {
+                holder,
+                successor,
+                readyAt: ready_at,
+            });
+        }
+        Ok(())
+    }
+
+    /// The holder can abort a recovery at any point before it executes.
+    pub fn cancel(&mut self) {
+        let holder = self.vm().msg_sender();
+        self._reset(holder);
+        log(self.vm(), RecoveryCancelled { holder });
+    }
+
+    /// Checks that `holder`'s recovery is past its timelock and returns the successor.
+    /// Only the successor or one of the holder's guardians may execute, and only with
+    /// something to move. Executing closes the round: the caller must move every id in
+    /// this one call, and a later recovery needs fresh votes.
+    pub fn _execute(&mut self, holder: Address, ids: &[U256]) -> Result<Address, RecoveryError> {
+        let ready_at = self.ready_at.get(holder);
+        if ready_at.is_zero() || U256::from(self.vm().block_timestamp()) < ready_at {
+            return Err(RecoveryError::NotReady(RecoveryNotReady { holder, readyAt: ready_at }));
+        }
+
+        let successor = self.successor.get(holder);
+        let caller = self.vm().msg_sender();
+        if caller != successor && !self.guardians(holder).contains(&caller) {
+            return Err(RecoveryError::Unauthorized(RecoveryUnauthorized { holder, account: caller }));
+        }
+        if ids.is_empty() {
+            return Err(RecoveryError::NothingToMove(RecoveryNothingToMove { holder }));
+        }
+        self._reset(holder);
+        log(self.vm(), RecoveryExecuted {
+            holder,
+            successor,
+            ids: ids.to_vec(),
+        });
+        Ok(successor)
+    }
+
+    /// Adds or removes one vote for `candidate` and returns its new count.
+    fn _approvals_mut(&mut self, holder: Address, round: U256, candidate: Address, add: bool) -> U256 {
+        let mut holder_approvals = self.approvals.setter(holder);
+        let mut round_approvals = holder_approvals.setter(round);
+        let count = round_approvals.get(candidate);
+        let count = if add { count + U256::from(1) } else { count - U256::from(1) };
+        round_approvals.insert(candidate, count);
+        count
+    }
+
+    /// Opens a fresh round; votes from earlier rounds are never read again.
+    fn _reset(&mut self, holder: Address) {
+        let round = self.round.get(holder);
+        self.round.insert(holder, round + U256::from(1));
+        self.successor.delete(holder);
+        self.ready_at.delete(holder);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{
+        alloy_primitives::{address, uint},
+        testing::*,
+    };
+
+    const HOLDER: Address = address!("a11ce00000000000000000000000000000000001");
+    const GUARDIAN_A: Address = address!("9a00000000000000000000000000000000000001");
+    const GUARDIAN_B: Address = address!("9b00000000000000000000000000000000000002");
+    const GUARDIAN_C: Address = address!("9c00000000000000000000000000000000000003");
+    const SUCCESSOR: Address = address!("5ecc000000000000000000000000000000000001");
+    const ROGUE: Address = address!("bad0000000000000000000000000000000000001");
+    const IDS: [U256; 1] = [uint!(1_U256)];
+
+    fn setup() -> (TestVM, AccountRecovery) {
+        let vm = TestVM::default();
+        vm.set_block_timestamp(1_000);
+        vm.set_sender(HOLDER);
+        let mut recovery = AccountRecovery::from(&vm);
+        let guardians = vec![GUARDIAN_A, GUARDIAN_B, GUARDIAN_C];
+        assert!(recovery.configure(guardians, U256::from(2), U256::from(100)).is_ok());
+        (vm, recovery)
+    }
+
+    fn approve(vm: &TestVM, recovery: &mut AccountRecovery, guardian: Address, successor: Address) {
+        vm.set_sender(guardian);
+        assert!(recovery.approve(HOLDER, successor).is_ok());
+    }
+
+    #[test]
+    fn a_dissenting_guardian_does_not_wipe_other_votes() {
+        let (vm, mut recovery) = setup();
+        approve(&vm, &mut recovery, GUARDIAN_A, SUCCESSOR);
+        approve(&vm, &mut recovery, GUARDIAN_C, ROGUE);
+        assert_eq!(recovery.approvals(HOLDER, SUCCESSOR), U256::from(1));
+        assert_eq!(recovery.approvals(HOLDER, ROGUE), U256::from(1));
+
+        approve(&vm, &mut recovery, GUARDIAN_B, SUCCESSOR);
+        assert_eq!(recovery.status(HOLDER), (SUCCESSOR, U256::from(2), U256::from(1_100)));
+
+        // A vote for someone else after scheduling leaves the schedule alone.
+        approve(&vm, &mut recovery, GUARDIAN_C, GUARDIAN_A);
+        assert_eq!(recovery.status(HOLDER).0, SUCCESSOR);
+    }
+
+    #[test]
+    fn moving_a_vote_away_unschedules() {
+        let (vm, mut recovery) = setup();
+        approve(&vm, &mut recovery, GUARDIAN_A, SUCCESSOR);
+        approve(&vm, &mut recovery, GUARDIAN_B, SUCCESSOR);
+        approve(&vm, &mut recovery, GUARDIAN_B, ROGUE);
+        assert_eq!(recovery.status(HOLDER), (Address::ZERO, U256::ZERO, U256::ZERO));
+        assert_eq!(recovery.approvals(HOLDER, SUCCESSOR), U256::from(1));
+
+        approve(&vm, &mut recovery, GUARDIAN_C, SUCCESSOR);
+        assert_eq!(recovery.status(HOLDER).0, SUCCESSOR);
+    }
+
+    #[test]
+    fn execution_closes_the_round() {
+        let (vm, mut recovery) = setup();
+        approve(&vm, &mut recovery, GUARDIAN_A, SUCCESSOR);
+        approve(&vm, &mut recovery, GUARDIAN_B, SUCCESSOR);
+        assert!(matches!(recovery._execute(HOLDER, &IDS), Err(RecoveryError::NotReady(_))));
+
+        vm.set_block_timestamp(1_100);
+        vm.set_sender(SUCCESSOR);
+        assert_eq!(recovery._execute(HOLDER, &IDS).ok(), Some(SUCCESSOR));
+        assert!(matches!(recovery._execute(HOLDER, &IDS), Err(RecoveryError::NotReady(_))));
+        assert_eq!(recovery.approvals(HOLDER, SUCCESSOR), U256::ZERO);
+    }
+
+    #[test]
+    fn third_parties_cannot_close_a_ready_recovery() {
+        let (vm, mut recovery) = setup();
+        approve(&vm, &mut recovery, GUARDIAN_A, SUCCESSOR);
+        approve(&vm, &mut recovery, GUARDIAN_B, SUCCESSOR);
+        vm.set_block_timestamp(1_100);
+
+        vm.set_sender(ROGUE);
+        let result = recovery._execute(HOLDER, &IDS);
+        assert!(matches!(result, Err(RecoveryError::Unauthorized(e)) if e.account == ROGUE));
+        // Nor can a guardian burn the round by moving nothing.
+        vm.set_sender(GUARDIAN_C);
+        assert!(matches!(recovery._execute(HOLDER, &[]), Err(RecoveryError::NothingToMove(_))));
+        assert_eq!(recovery.status(HOLDER).0, SUCCESSOR);
+
+        assert_eq!(recovery._execute(HOLDER, &IDS).ok(), Some(SUCCESSOR));
+    }
+}
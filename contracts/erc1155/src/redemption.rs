@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct Redemption {
+        mapping(uint256 => address) token;
+        mapping(uint256 => uint256) rate;
+        mapping(uint256 => uint256) pool;
+        mapping(uint256 => uint256) units;
+    }
+}
+
+sol! {
+    event RedemptionConfigured(uint256 indexed id, address token, uint256 rate);
+    event RedemptionFunded(uint256 indexed id, uint256 amount, uint256 units);
+    event Redeemed(address indexed account, uint256 indexed id, uint256 amount, uint256 payout);
+
+    error RedemptionNotConfigured(uint256 id);
+    error RedemptionInsufficientPool(uint256 id, uint256 needed, uint256 available);
+    error RedemptionTransferFailed(address token, address to, uint256 amount);
+    error RedemptionOverflow(uint256 id, uint256 pool, uint256 amount);
+}
+
+pub enum RedemptionError {
+    NotConfigured(RedemptionNotConfigured),
+    InsufficientPool(RedemptionInsufficientPool),
+    TransferFailed(RedemptionTransferFailed),
+    Overflow(RedemptionOverflow),
+}
+
+impl From<RedemptionError> for Vec<u8> {
+    fn from(error: RedemptionError) -> Self {
+        match error {
+            RedemptionError::NotConfigured(e) => revert::encode(e),
+            RedemptionError::InsufficientPool(e) => revert::encode(e),
+            RedemptionError::TransferFailed(e) => revert::encode(e),
+            RedemptionError::Overflow(e) => revert::encode(e),
+        }
+    }
+}
+
+impl Redemption {
+    /// Returns `(token, rate, pool, units)` for `id`. A zero rate means pro-rata payouts.
+    pub fn config(&self, id: U256) -> (Address, U256, U256, U256) {
+        (
+            self.token.get(id),
+            self.rate.get(id),
+            self.pool.get(id),
+            self.units.get(id),
+        )
+    }
+
+    /// Sets the payout token for `id`. With a non-zero `rate` each burned unit pays
+    /// `rate` tokens; with zero it pays `pool * amount / units`.
+    pub fn configure(&mut self, id: U256, token: Address, rate: U256) {
+        self.token.insert(id, token);
+        self.rate.insert(id, rate);
+        log(self.vm(), RedemptionConfigured { id, token, rate });
+    }
+
+    /// Books tokens the caller has already moved into the contract. `units` only
+    /// matters for pro-rata ids and is the number of burnable units the pool backs.
+    pub fn _fund(&mut self, id: U256, amount: U256, units: U256) -> Result<Address, RedemptionError> {
+        let token = self.token.get(id);
+        if token.is_zero() {
+            return Err(RedemptionError::NotConfigured(RedemptionNotConfigured { id }));
+        }
+        self.pool.insert(id, self.pool.get(id) + amount);
+        self.units.insert(id, self.units.get(id) + units);
+        log(self.vm(), RedemptionFunded { id, amount, units });
+        Ok(token)
+    }
+
+    pub fn quote(&self, id: U256, amount: U256) -> Result<U256, RedemptionError> {
+        let pool = self.pool.get(id);
+        let rate = self.rate.get(id);
+        if self.token.get(id).is_zero() {
+            return Err(RedemptionError::NotConfigured(RedemptionNotConfigured { id }));
+        }
+
+        if !rate.is_zero() {
+            let payout = amount.checked_mul(rate).unwrap_or(U256::MAX);
+            if payout > pool {
+                return Err(RedemptionError::InsufficientPool(RedemptionInsufficientPool {
+                    id,
+                    needed: payout,
+                    available: pool,
+                }));
+            }
+            return Ok(payout);
+        }
+
+        let units = self.units.get(id);
+        if units.is_zero() || amount > units {
+            return Err(RedemptionError::InsufficientPool(RedemptionInsufficientPool {
+                id,
+                needed: amount,
+                available: units,
+            }));
+        }
+        pool
+            .checked_mul(amount)
+            .map(|product| product / units)
+            .ok_or(RedemptionError::Overflow(RedemptionOverflow { id, pool, amount }))
+    }
+
+    /// Debits the pool for a burn of `amount` and returns `(token, payout)`.
+    pub fn _redeem(&mut self, id: U256, amount: U256) -> Result<(Address, U256), RedemptionError> {
+        let payout = self.quote(id, amount)?;
+        self.pool.insert(id, self.pool.get(id) - payout);
+        if self.rate.get(id).is_zero() {
+            self.units.insert(id, self.units.get(id) - amount);
+        }
+        log(self.vm(), Redeemed {
+            account: self.vm().msg_sender(),
+            id,
+            amount,
+            payout,
+        });
+        Ok((self.token.get(id), payout))
+    }
+}
@@ -0,0 +1,47 @@
+//! Storage-based reentrancy lock. The crate is built with the SDK's `reentrant` feature,
+//! so receiver callbacks, listeners and ETH payouts can call straight back in.
+
+use alloc::vec::Vec;
+use stylus_sdk::{alloy_sol_types::sol, prelude::*};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct ReentrancyGuard {
+        bool entered;
+    }
+}
+
+sol! {
+    error ReentrancyGuardReentrantCall();
+}
+
+pub enum ReentrancyError {
+    ReentrantCall(ReentrancyGuardReentrantCall),
+}
+
+impl From<ReentrancyError> for Vec<u8> {
+    fn from(error: ReentrancyError) -> Self {
+        match error {
+            ReentrancyError::ReentrantCall(e) => revert::encode(e),
+        }
+    }
+}
+
+impl ReentrancyGuard {
+    pub fn entered(&self) -> bool {
+        self.entered.get()
+    }
+
+    pub fn _enter(&mut self) -> Result<(), ReentrancyError> {
+        if self.entered.get() {
+            return Err(ReentrancyError::ReentrantCall(ReentrancyGuardReentrantCall {}));
+        }
+        self.entered.set(true);
+        Ok(())
+    }
+
+    pub fn _exit(&mut self) {
+        self.entered.set(false);
+    }
+}
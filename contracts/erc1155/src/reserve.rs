@@ -0,0 +1,129 @@
+//! Per-id supply caps with earmarked allocations. Reserved units count against the cap
+//! as if minted, so ordinary mints can only use what is neither minted nor promised.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct SupplyReserve {
+        mapping(uint256 => uint256) max_supply;
+        mapping(uint256 => uint256) reserved;
+        mapping(uint256 => mapping(address => uint256)) allocations;
+    }
+}
+
+sol! {
+    event MaxSupplySet(uint256 indexed id, uint256 maxSupply);
+    event SupplyReserved(uint256 indexed id, address indexed beneficiary, uint256 amount);
+    event ReservedClaimed(uint256 indexed id, address indexed beneficiary, uint256 amount);
+
+    error SupplyCapExceeded(uint256 id, uint256 requested, uint256 available);
+    error SupplyCapBelowCommitted(uint256 id, uint256 maxSupply, uint256 committed);
+    error ReservationExceeded(address beneficiary, uint256 id, uint256 requested, uint256 allocated);
+}
+
+pub enum ReserveError {
+    CapExceeded(SupplyCapExceeded),
+    CapBelowCommitted(SupplyCapBelowCommitted),
+    ReservationExceeded(ReservationExceeded),
+}
+
+impl From<ReserveError> for Vec<u8> {
+    fn from(error: ReserveError) -> Self {
+        match error {
+            ReserveError::CapExceeded(e) => revert::encode(e),
+            ReserveError::CapBelowCommitted(e) => revert::encode(e),
+            ReserveError::ReservationExceeded(e) => revert::encode(e),
+        }
+    }
+}
+
+impl SupplyReserve {
+    /// Zero means uncapped.
+    pub fn max_supply(&self, id: U256) -> U256 {
+        self.max_supply.get(id)
+    }
+
+    /// Units of `id` promised but not yet claimed.
+    pub fn reserved(&self, id: U256) -> U256 {
+        self.reserved.get(id)
+    }
+
+    pub fn allocation(&self, id: U256, beneficiary: Address) -> U256 {
+        self.allocations.get(id).get(beneficiary)
+    }
+
+    /// The cap can't drop below what is already minted plus reserved.
+    pub fn set_max_supply(&mut self, id: U256, max_supply: U256, supply: U256) -> Result<(), ReserveError> {
+        let committed = supply + self.reserved.get(id);
+        if !max_supply.is_zero() && max_supply < committed {
+            return Err(ReserveError::CapBelowCommitted(SupplyCapBelowCommitted {
+                id,
+                maxSupply: max_supply,
+                committed,
+            }));
+        }
+        self.max_supply.insert(id, max_supply);
+        log(self.vm(), MaxSupplySet { id, maxSupply: max_supply });
+        Ok(())
+    }
+
+    /// Units of `id` that can still be minted on top of `supply` without touching a
+    /// reservation; `U256::MAX` when uncapped.
+    pub fn available(&self, id: U256, supply: U256) -> U256 {
+        let cap = self.max_supply.get(id);
+        if cap.is_zero() {
+            return U256::MAX;
+        }
+        cap.saturating_sub(supply + self.reserved.get(id))
+    }
+
+    /// Checks that minting `amount` on top of `supply` leaves every reservation intact.
+    pub fn check_mint(&self, id: U256, amount: U256, supply: U256) -> Result<(), ReserveError> {
+        let available = self.available(id, supply);
+        if amount > available {
+            return Err(ReserveError::CapExceeded(SupplyCapExceeded {
+                id,
+                requested: amount,
+                available,
+            }));
+        }
+        Ok(())
+    }
+
+    pub fn reserve(&mut self, id: U256, amount: U256, beneficiary: Address, supply: U256) -> Result<(), ReserveError> {
+        self.check_mint(id, amount, supply)?;
+        self.reserved.insert(id, self.reserved.get(id) + amount);
+        let mut allocations = self.allocations.setter(id);
+        let allocated = allocations.get(beneficiary);
+        allocations.insert(beneficiary, allocated + amount);
+        log(self.vm(), SupplyReserved { id, beneficiary, amount });
+        Ok(())
+    }
+
+    /// Consumes `amount` of the caller's allocation; the caller mints it.
+    pub fn _claim(&mut self, id: U256, amount: U256) -> Result<(), ReserveError> {
+        let beneficiary = self.vm().msg_sender();
+        let mut allocations = self.allocations.setter(id);
+        let allocated = allocations.get(beneficiary);
+        if amount > allocated {
+            return Err(ReserveError::ReservationExceeded(ReservationExceeded {
+                beneficiary,
+                id,
+                requested: amount,
+                allocated,
+            }));
+        }
+        allocations.insert(beneficiary, allocated - amount);
+        self.reserved.insert(id, self.reserved.get(id) - amount);
+        log(self.vm(), ReservedClaimed { id, beneficiary, amount });
+        Ok(())
+    }
+}
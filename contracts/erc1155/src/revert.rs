@@ -0,0 +1,50 @@
+//! How custom errors reach the caller. By default they are ABI-encoded custom errors.
+//! With the `revert-strings` feature every error is rendered as `Error(string)` instead,
+//! e.g. `ERC1155InsufficientBalance(0x…, 1, 5, 7)`, for tooling that can't decode custom
+//! errors. The formatter is only compiled in with the feature.
+
+use alloc::vec::Vec;
+use stylus_sdk::alloy_sol_types::SolError;
+
+#[cfg(not(feature = "revert-strings"))]
+pub fn encode<E: SolError>(error: E) -> Vec<u8> {
+    error.abi_encode()
+}
+
+#[cfg(feature = "revert-strings")]
+pub fn encode<E: SolError>(error: E) -> Vec<u8> {
+    use stylus_sdk::alloy_sol_types::Revert;
+
+    Revert::from(describe(&error)).abi_encode()
+}
+
+/// `Name(arg, …)` with addresses checksummed, integers in decimal and other words in hex.
+/// Every error in the crate has only static parameters, so each takes one word.
+#[cfg(feature = "revert-strings")]
+fn describe<E: SolError>(error: &E) -> alloc::string::String {
+    use alloc::{format, string::String};
+    use stylus_sdk::alloy_primitives::{Address, B256, U256};
+
+    let signature = E::SIGNATURE;
+    let (name, params) = signature.split_once('(').unwrap_or((signature, ")"));
+    let params = params.trim_end_matches(')');
+    let data = error.abi_encode();
+
+    let mut message = String::from(name);
+    message.push('(');
+    for (i, (kind, word)) in params.split(',').filter(|p| !p.is_empty()).zip(data[4..].chunks(32)).enumerate() {
+        if i > 0 {
+            message.push_str(", ");
+        }
+        let word = B256::from_slice(word);
+        let rendered = match kind {
+            "address" => format!("{}", Address::from_word(word)),
+            "bool" => format!("{}", !word.is_zero()),
+            k if k.starts_with("uint") => format!("{}", U256::from_be_bytes(word.0)),
+            _ => format!("{}", word),
+        };
+        message.push_str(&rendered);
+    }
+    message.push(')');
+    message
+}
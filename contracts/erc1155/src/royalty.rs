@@ -0,0 +1,341 @@
+//! ERC-2981 royalties with an optional fallback to the Royalty Registry
+//! (royaltyregistry.xyz) for ids that have no local configuration.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{fixed_bytes, uint, Address, FixedBytes, U256},
+    alloy_sol_types::sol,
+    call::Call,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::{
+    interfaces::{IRoyaltyInfo, IRoyaltyRegistry},
+    revert,
+};
+
+/// Royalty fractions are expressed in basis points of the sale price.
+pub const FEE_DENOMINATOR: U256 = uint!(10_000_U256);
+
+/// `type(IERC2981).interfaceId`
+pub const IERC2981_INTERFACE_ID: FixedBytes<4> = fixed_bytes!("2a55205a");
+
+sol_storage! {
+    pub struct Royalties {
+        address default_receiver;
+        uint256 default_fraction;
+        mapping(uint256 => address) token_receiver;
+        mapping(uint256 => uint256) token_fraction;
+        address registry;
+        mapping(uint256 => address) source_collection;
+        mapping(uint256 => uint256) source_token_id;
+    }
+}
+
+sol! {
+    event DefaultRoyaltySet(address receiver, uint256 feeNumerator);
+    event DefaultRoyaltyDeleted();
+    event TokenRoyaltySet(uint256 indexed id, address receiver, uint256 feeNumerator);
+    event TokenRoyaltyReset(uint256 indexed id);
+    event RoyaltyRegistrySet(address registry);
+    event RoyaltySourceSet(uint256 indexed id, address collection, uint256 tokenId);
+
+    error ERC2981InvalidDefaultRoyalty(uint256 numerator, uint256 denominator);
+    error ERC2981InvalidDefaultRoyaltyReceiver(address receiver);
+    error ERC2981InvalidTokenRoyalty(uint256 tokenId, uint256 numerator, uint256 denominator);
+    error ERC2981InvalidTokenRoyaltyReceiver(uint256 tokenId, address receiver);
+    error RoyaltyPaymentFailed(address receiver, uint256 amount);
+    error RoyaltyApportionOverflow();
+}
+
+pub enum RoyaltyError {
+    InvalidDefaultRoyalty(ERC2981InvalidDefaultRoyalty),
+    InvalidDefaultRoyaltyReceiver(ERC2981InvalidDefaultRoyaltyReceiver),
+    InvalidTokenRoyalty(ERC2981InvalidTokenRoyalty),
+    InvalidTokenRoyaltyReceiver(ERC2981InvalidTokenRoyaltyReceiver),
+    PaymentFailed(RoyaltyPaymentFailed),
+    ApportionOverflow(RoyaltyApportionOverflow),
+}
+
+impl From<RoyaltyError> for Vec<u8> {
+    fn from(error: RoyaltyError) -> Self {
+        match error {
+            RoyaltyError::InvalidDefaultRoyalty(e) => revert::encode(e),
+            RoyaltyError::InvalidDefaultRoyaltyReceiver(e) => revert::encode(e),
+            RoyaltyError::InvalidTokenRoyalty(e) => revert::encode(e),
+            RoyaltyError::InvalidTokenRoyaltyReceiver(e) => revert::encode(e),
+            RoyaltyError::PaymentFailed(e) => revert::encode(e),
+            RoyaltyError::ApportionOverflow(e) => revert::encode(e),
+        }
+    }
+}
+
+/// `sale_price * fee_numerator / FEE_DENOMINATOR`, rounded down. Split so that no
+/// sale price a settlement path can hold overflows it.
+pub fn royalty_amount(sale_price: U256, fee_numerator: U256) -> U256 {
+    sale_price / FEE_DENOMINATOR * fee_numerator + sale_price % FEE_DENOMINATOR * fee_numerator / FEE_DENOMINATOR
+}
+
+/// Splits `total` pro rata to `weights`; the last share absorbs the rounding remainder
+/// so the parts always sum to `total`. All-zero weights put everything in the last share.
+/// Reverts rather than wrapping when the weights or `total * weight` overflow.
+///
+/// Weights are unit counts, not prices: a bundle padded with many units of a cheap,
+/// royalty-free id pulls most of the ETH onto that id and away from the royalties owed
+/// on the rest. Sellers of mixed bundles who care about royalties should list ids
+/// separately.
+pub fn apportion(total: U256, weights: &[U256]) -> Result<Vec<U256>, RoyaltyError> {
+    let overflow = || RoyaltyError::ApportionOverflow(RoyaltyApportionOverflow {});
+    let sum = weights
+        .iter()
+        .try_fold(U256::ZERO, |acc, w| acc.checked_add(*w))
+        .ok_or_else(overflow)?;
+    let mut parts = Vec::with_capacity(weights.len());
+    for w in weights {
+        let part = if sum.is_zero() {
+            U256::ZERO
+        } else {
+            total.checked_mul(*w).ok_or_else(overflow)? / sum
+        };
+        parts.push(part);
+    }
+    // Each part is at most `total * w / sum`, so the parts never sum past `total`.
+    let assigned = parts.iter().fold(U256::ZERO, |acc, p| acc + *p);
+    if let Some(last) = parts.last_mut() {
+        *last += total - assigned;
+    }
+    Ok(parts)
+}
+
+impl Royalties {
+    /// Per-token override first, then the default, then the registry fallback.
+    pub fn royalty_info(&self, id: U256, sale_price: U256) -> (Address, U256) {
+        let (receiver, fraction) = match self.token_receiver.get(id) {
+            receiver if !receiver.is_zero() => (receiver, self.token_fraction.get(id)),
+            _ => (self.default_receiver.get(), self.default_fraction.get()),
+        };
+        if receiver.is_zero() {
+            return self._registry_lookup(id, sale_price);
+        }
+        (receiver, royalty_amount(sale_price, fraction))
+    }
+
+    /// What settlement pays out: `royalty_info`, capped at the sale price so a
+    /// misbehaving registry target can't take more than the trade is worth.
+    pub fn settlement(&self, id: U256, sale_price: U256) -> (Address, U256) {
+        let (receiver, amount) = self.royalty_info(id, sale_price);
+        if receiver.is_zero() {
+            return (receiver, U256::ZERO);
+        }
+        (receiver, amount.min(sale_price))
+    }
+
+    pub fn registry(&self) -> Address {
+        self.registry.get()
+    }
+
+    /// Returns `(collection, token_id)` queried in the registry for `id`.
+    pub fn source(&self, id: U256) -> (Address, U256) {
+        (self.source_collection.get(id), self.source_token_id.get(id))
+    }
+
+    pub fn set_default_royalty(&mut self, receiver: Address, fee_numerator: U256) -> Result<(), RoyaltyError> {
+        if fee_numerator > FEE_DENOMINATOR {
+            return Err(RoyaltyError::InvalidDefaultRoyalty(ERC2981InvalidDefaultRoyalty {
+                numerator: fee_numerator,
+                denominator: FEE_DENOMINATOR,
+            }));
+        }
+        if receiver.is_zero() {
+            return Err(RoyaltyError::InvalidDefaultRoyaltyReceiver(ERC2981InvalidDefaultRoyaltyReceiver { receiver }));
+        }
+        self.default_receiver.set(receiver);
+        self.default_fraction.set(fee_numerator);
+        log(self.vm(), DefaultRoyaltySet { receiver, feeNumerator: fee_numerator });
+        Ok(())
+    }
+
+    pub fn set_token_royalty(&mut self, id: U256, receiver: Address, fee_numerator: U256) -> Result<(), RoyaltyError> {
+        if fee_numerator > FEE_DENOMINATOR {
+            return Err(RoyaltyError::InvalidTokenRoyalty(ERC2981InvalidTokenRoyalty {
+                tokenId: id,
+                numerator: fee_numerator,
+                denominator: FEE_DENOMINATOR,
+            }));
+        }
+        if receiver.is_zero() {
+            return Err(RoyaltyError::InvalidTokenRoyaltyReceiver(ERC2981InvalidTokenRoyaltyReceiver {
+                tokenId: id,
+                receiver,
+            }));
+        }
+        self.token_receiver.insert(id, receiver);
+        self.token_fraction.insert(id, fee_numerator);
+        log(self.vm(), TokenRoyaltySet {
+            id,
+            receiver,
+            feeNumerator: fee_numerator,
+        });
+        Ok(())
+    }
+
+    /// Ids without an override fall through to the registry, if any.
+    pub fn delete_default_royalty(&mut self) {
+        self.default_receiver.erase();
+        self.default_fraction.erase();
+        log(self.vm(), DefaultRoyaltyDeleted {});
+    }
+
+    /// Drops `id`'s override so it uses the default again.
+    pub fn reset_token_royalty(&mut self, id: U256) {
+        self.token_receiver.delete(id);
+        self.token_fraction.delete(id);
+        log(self.vm(), TokenRoyaltyReset { id });
+    }
+
+    /// A zero `registry` disables the fallback.
+    pub fn set_registry(&mut self, registry: Address) {
+        self.registry.set(registry);
+        log(self.vm(), RoyaltyRegistrySet { registry });
+    }
+
+    /// Points `id` at the collection and token it was wrapped from. Unset ids are looked
+    /// up as themselves under this contract's address.
+    pub fn set_source(&mut self, id: U256, collection: Address, token_id: U256) {
+        self.source_collection.insert(id, collection);
+        self.source_token_id.insert(id, token_id);
+        log(self.vm(), RoyaltySourceSet {
+            id,
+            collection,
+            tokenId: token_id,
+        });
+    }
+
+    /// Asks the registry where `collection`'s royalties live and queries ERC-2981 there.
+    /// Any failure, or a lookup that resolves back to this contract, means no royalty.
+    fn _registry_lookup(&self, id: U256, sale_price: U256) -> (Address, U256) {
+        let registry = self.registry.get();
+        if registry.is_zero() {
+            return (Address::ZERO, U256::ZERO);
+        }
+        let (collection, token_id) = match self.source_collection.get(id) {
+            collection if !collection.is_zero() => (collection, self.source_token_id.get(id)),
+            _ => (self.vm().contract_address(), id),
+        };
+
+        let lookup = IRoyaltyRegistry::new(registry)
+            .get_royalty_lookup_address(Call::new(), collection)
+            .unwrap_or_default();
+        if lookup.is_zero() || lookup == self.vm().contract_address() {
+            return (Address::ZERO, U256::ZERO);
+        }
+        IRoyaltyInfo::new(lookup)
+            .royalty_info(Call::new(), token_id, sale_price)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{alloy_primitives::address, testing::*};
+
+    const RECEIVER: Address = address!("4ec0000000000000000000000000000000000001");
+
+    #[test]
+    fn royalty_amount_rounds_down() {
+        assert_eq!(royalty_amount(U256::from(10_000), U256::from(250)), U256::from(250));
+        assert_eq!(royalty_amount(U256::from(39), U256::from(250)), U256::ZERO);
+        assert_eq!(royalty_amount(U256::from(399), U256::from(250)), U256::from(9));
+        assert_eq!(royalty_amount(U256::ZERO, U256::from(250)), U256::ZERO);
+        assert_eq!(royalty_amount(U256::from(1_000), U256::ZERO), U256::ZERO);
+        assert_eq!(royalty_amount(U256::from(1_000), FEE_DENOMINATOR), U256::from(1_000));
+        assert_eq!(royalty_amount(U256::MAX, FEE_DENOMINATOR), U256::MAX);
+    }
+
+    #[test]
+    fn split_trades_never_pay_more_royalty() {
+        let vm = TestVM::default();
+        let mut royalties = Royalties::from(&vm);
+        assert!(royalties.set_default_royalty(RECEIVER, U256::from(250)).is_ok());
+        let whole = royalties.settlement(U256::from(1), U256::from(1_000)).1;
+        let split = (0..10).fold(U256::ZERO, |acc, _| acc + royalties.settlement(U256::from(1), U256::from(100)).1);
+        assert_eq!(whole, U256::from(25));
+        assert_eq!(split, U256::from(20));
+        // A trade too small to owe a whole wei pays nothing rather than reverting.
+        assert_eq!(royalties.settlement(U256::from(1), U256::from(39)), (RECEIVER, U256::ZERO));
+    }
+
+    #[test]
+    fn apportion_gives_remainder_to_last_share() {
+        let parts = apportion(U256::from(100), &[U256::from(1), U256::from(1), U256::from(1)]).ok();
+        assert_eq!(parts, Some(vec![U256::from(33), U256::from(33), U256::from(34)]));
+
+        let parts = apportion(U256::from(10), &[U256::from(3), U256::from(7)]).ok();
+        assert_eq!(parts, Some(vec![U256::from(3), U256::from(7)]));
+    }
+
+    #[test]
+    fn apportion_reverts_instead_of_wrapping() {
+        let result = apportion(U256::from(10), &[U256::MAX, U256::from(1)]);
+        assert!(matches!(result, Err(RoyaltyError::ApportionOverflow(_))));
+        let result = apportion(U256::MAX, &[U256::from(2), U256::from(1)]);
+        assert!(matches!(result, Err(RoyaltyError::ApportionOverflow(_))));
+    }
+
+    #[test]
+    fn apportion_zero_cases() {
+        assert_eq!(apportion(U256::from(100), &[]).ok(), Some(vec![]));
+        assert_eq!(
+            apportion(U256::from(100), &[U256::ZERO, U256::ZERO]).ok(),
+            Some(vec![U256::ZERO, U256::from(100)])
+        );
+        assert_eq!(
+            apportion(U256::ZERO, &[U256::from(1), U256::from(2)]).ok(),
+            Some(vec![U256::ZERO, U256::ZERO])
+        );
+    }
+
+    #[test]
+    fn settlement_is_zero_without_configuration() {
+        let vm = TestVM::default();
+        let royalties = Royalties::from(&vm);
+        assert_eq!(royalties.settlement(U256::from(1), U256::from(1_000)), (Address::ZERO, U256::ZERO));
+    }
+
+    #[test]
+    fn settlement_uses_default_then_override() {
+        let vm = TestVM::default();
+        let mut royalties = Royalties::from(&vm);
+        assert!(royalties.set_default_royalty(RECEIVER, U256::from(500)).is_ok());
+        assert_eq!(royalties.settlement(U256::from(1), U256::from(1_000)), (RECEIVER, U256::from(50)));
+        assert_eq!(royalties.settlement(U256::from(1), U256::ZERO), (RECEIVER, U256::ZERO));
+
+        assert!(royalties.set_token_royalty(U256::from(1), RECEIVER, U256::ZERO).is_ok());
+        assert_eq!(royalties.settlement(U256::from(1), U256::from(1_000)), (RECEIVER, U256::ZERO));
+
+        royalties.reset_token_royalty(U256::from(1));
+        royalties.delete_default_royalty();
+        assert_eq!(royalties.settlement(U256::from(1), U256::from(1_000)), (Address::ZERO, U256::ZERO));
+    }
+
+    #[test]
+    fn invalid_royalties_are_rejected() {
+        let vm = TestVM::default();
+        let mut royalties = Royalties::from(&vm);
+        let over = FEE_DENOMINATOR + U256::from(1);
+        assert!(matches!(
+            royalties.set_default_royalty(RECEIVER, over),
+            Err(RoyaltyError::InvalidDefaultRoyalty(_))
+        ));
+        assert!(matches!(
+            royalties.set_default_royalty(Address::ZERO, U256::from(1)),
+            Err(RoyaltyError::InvalidDefaultRoyaltyReceiver(_))
+        ));
+        assert!(matches!(
+            royalties.set_token_royalty(U256::from(1), RECEIVER, over),
+            Err(RoyaltyError::InvalidTokenRoyalty(_))
+        ));
+    }
+}
@@ -0,0 +1,32 @@
+//! Storage-key derivation for `eth_getProof` and light-client balance checks.
+//!
+//! The roots below assume `Erc1155` is the first field of the entrypoint struct, so its
+//! `balances` mapping sits at slot 0 and `operator_approvals` at slot 1. Stylus maps use
+//! Solidity's layout: `slot(key) = keccak256(pad32(key) ++ slot)`.
+
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    crypto::keccak,
+};
+
+pub const BALANCES_SLOT: U256 = U256::ZERO;
+pub const OPERATOR_APPROVALS_SLOT: U256 = U256::from_limbs([1, 0, 0, 0]);
+
+pub fn mapping_slot(key: B256, slot: B256) -> B256 {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(key.as_slice());
+    preimage[32..].copy_from_slice(slot.as_slice());
+    keccak(preimage)
+}
+
+/// Slot of `balances[id][account]`.
+pub fn balance_slot(account: Address, id: U256) -> B256 {
+    let inner = mapping_slot(B256::from(id), B256::from(BALANCES_SLOT));
+    mapping_slot(account.into_word(), inner)
+}
+
+/// Slot of `operator_approvals[owner][operator]`.
+pub fn approval_slot(owner: Address, operator: Address) -> B256 {
+    let inner = mapping_slot(owner.into_word(), B256::from(OPERATOR_APPROVALS_SLOT));
+    mapping_slot(operator.into_word(), inner)
+}
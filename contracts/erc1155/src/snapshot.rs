@@ -0,0 +1,187 @@
+//! Balance and supply snapshots, as in OpenZeppelin's `ERC20Snapshot`, per token id.
+//! `_snapshot` opens a new snapshot id; the first balance change of an `(id, account)`
+//! or an id's supply after that records the value it had, so reads at any snapshot are a
+//! binary search over the values recorded since.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    storage::{StorageU256, StorageVec},
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct Erc1155Snapshot {
+        uint256 current_id;
+        mapping(uint256 => mapping(address => uint256[])) balance_snapshot_ids;
+        mapping(uint256 => mapping(address => uint256[])) balance_snapshot_values;
+        mapping(uint256 => uint256[]) supply_snapshot_ids;
+        mapping(uint256 => uint256[]) supply_snapshot_values;
+    }
+}
+
+sol! {
+    event Snapshot(uint256 id);
+
+    error SnapshotNonexistentId(uint256 snapshotId);
+}
+
+pub enum SnapshotError {
+    NonexistentId(SnapshotNonexistentId),
+}
+
+impl From<SnapshotError> for Vec<u8> {
+    fn from(error: SnapshotError) -> Self {
+        match error {
+            SnapshotError::NonexistentId(e) => revert::encode(e),
+        }
+    }
+}
+
+impl Erc1155Snapshot {
+    pub fn current_id(&self) -> U256 {
+        self.current_id.get()
+    }
+
+    /// `balance` is the account's live balance, returned when nothing changed since.
+    pub fn balance_of_at(&self, account: Address, id: U256, snapshot_id: U256, balance: U256) -> Result<U256, SnapshotError> {
+        let snapshot_ids = self.balance_snapshot_ids.get(id);
+        let snapshot_values = self.balance_snapshot_values.get(id);
+        let found = self._value_at(snapshot_id, &snapshot_ids.get(account), &snapshot_values.get(account))?;
+        Ok(found.unwrap_or(balance))
+    }
+
+    /// `supply` is the id's live total supply, returned when nothing changed since.
+    pub fn total_supply_at(&self, id: U256, snapshot_id: U256, supply: U256) -> Result<U256, SnapshotError> {
+        let found = self._value_at(snapshot_id, &self.supply_snapshot_ids.get(id), &self.supply_snapshot_values.get(id))?;
+        Ok(found.unwrap_or(supply))
+    }
+
+    pub fn _snapshot(&mut self) -> U256 {
+        let id = self.current_id.get() + U256::from(1);
+        self.current_id.set(id);
+        log(self.vm(), Snapshot { id });
+        id
+    }
+
+    /// Records `balance`, the value before the change about to happen, if it is the first
+    /// change to `(id, account)` since the current snapshot.
+    pub fn _update_account(&mut self, id: U256, account: Address, balance: U256) {
+        let current = self.current_id.get();
+        if current.is_zero() || last(&self.balance_snapshot_ids.get(id).get(account)) >= current {
+            return;
+        }
+        let mut id_ids = self.balance_snapshot_ids.setter(id);
+        id_ids.setter(account).push(current);
+        let mut id_values = self.balance_snapshot_values.setter(id);
+        id_values.setter(account).push(balance);
+    }
+
+    /// As [`Self::_update_account`], for the id's total supply.
+    pub fn _update_supply(&mut self, id: U256, supply: U256) {
+        let current = self.current_id.get();
+        if current.is_zero() || last(&self.supply_snapshot_ids.get(id)) >= current {
+            return;
+        }
+        self.supply_snapshot_ids.setter(id).push(current);
+        self.supply_snapshot_values.setter(id).push(supply);
+    }
+
+    /// The value recorded for the first change after `snapshot_id`, or `None` if the
+    /// value hasn't changed since.
+    fn _value_at(
+        &self,
+        snapshot_id: U256,
+        ids: &StorageVec<StorageU256>,
+        values: &StorageVec<StorageU256>,
+    ) -> Result<Option<U256>, SnapshotError> {
+        if snapshot_id.is_zero() || snapshot_id > self.current_id.get() {
+            return Err(SnapshotError::NonexistentId(SnapshotNonexistentId { snapshotId: snapshot_id }));
+        }
+        // Lower bound: the first recorded snapshot id >= `snapshot_id`.
+        let (mut low, mut high) = (0, ids.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if ids.get(mid).unwrap_or_default() < snapshot_id {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(values.get(low))
+    }
+}
+
+fn last(ids: &StorageVec<StorageU256>) -> U256 {
+    ids.len().checked_sub(1).and_then(|i| ids.get(i)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::{
+        alloy_primitives::{address, uint},
+        testing::*,
+    };
+
+    const ALICE: Address = address!("a11ce00000000000000000000000000000000001");
+    const ID: U256 = uint!(1_U256);
+
+    #[test]
+    fn reads_outside_taken_snapshots_revert() {
+        let vm = TestVM::default();
+        let mut snapshots = Erc1155Snapshot::from(&vm);
+        assert!(snapshots.balance_of_at(ALICE, ID, U256::from(1), U256::ZERO).is_err());
+        assert_eq!(snapshots._snapshot(), U256::from(1));
+        assert!(snapshots.balance_of_at(ALICE, ID, U256::ZERO, U256::ZERO).is_err());
+        assert!(snapshots.total_supply_at(ID, U256::from(2), U256::ZERO).is_err());
+    }
+
+    #[test]
+    fn unchanged_values_read_live() {
+        let vm = TestVM::default();
+        let mut snapshots = Erc1155Snapshot::from(&vm);
+        snapshots._snapshot();
+        let at = snapshots.balance_of_at(ALICE, ID, U256::from(1), U256::from(7));
+        assert_eq!(at.ok(), Some(U256::from(7)));
+    }
+
+    #[test]
+    fn first_change_after_each_snapshot_is_recorded() {
+        let vm = TestVM::default();
+        let mut snapshots = Erc1155Snapshot::from(&vm);
+        // Changes before any snapshot are not recorded.
+        snapshots._update_account(ID, ALICE, U256::from(1));
+
+        snapshots._snapshot(); // 1: balance 10
+        snapshots._update_account(ID, ALICE, U256::from(10));
+        snapshots._update_account(ID, ALICE, U256::from(15));
+        snapshots._snapshot(); // 2: balance 20
+        snapshots._snapshot(); // 3: balance 20
+        snapshots._update_account(ID, ALICE, U256::from(20));
+        snapshots._snapshot(); // 4: balance 5, unchanged since
+
+        let at = |snapshot_id: u64| snapshots.balance_of_at(ALICE, ID, U256::from(snapshot_id), U256::from(5)).ok();
+        assert_eq!(at(1), Some(U256::from(10)));
+        assert_eq!(at(2), Some(U256::from(20)));
+        assert_eq!(at(3), Some(U256::from(20)));
+        assert_eq!(at(4), Some(U256::from(5)));
+    }
+
+    #[test]
+    fn supply_is_recorded_per_id() {
+        let vm = TestVM::default();
+        let mut snapshots = Erc1155Snapshot::from(&vm);
+        snapshots._snapshot();
+        snapshots._update_supply(ID, U256::from(100));
+        snapshots._update_supply(ID, U256::from(150));
+
+        assert_eq!(snapshots.total_supply_at(ID, U256::from(1), U256::from(200)).ok(), Some(U256::from(100)));
+        let other = ID + U256::from(1);
+        assert_eq!(snapshots.total_supply_at(other, U256::from(1), U256::from(3)).ok(), Some(U256::from(3)));
+    }
+}
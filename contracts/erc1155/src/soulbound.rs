@@ -0,0 +1,57 @@
+//! Per-id transfer lock for soulbound tokens such as achievement badges. Locked ids
+//! can still be minted and burned; unlocked ids are unaffected.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::U256,
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct Soulbound {
+        mapping(uint256 => bool) bound;
+    }
+}
+
+sol! {
+    event TransferLockSet(uint256 indexed id, bool locked);
+
+    error ERC1155TransferLocked(uint256 id);
+}
+
+pub enum SoulboundError {
+    TransferLocked(ERC1155TransferLocked),
+}
+
+impl From<SoulboundError> for Vec<u8> {
+    fn from(error: SoulboundError) -> Self {
+        match error {
+            SoulboundError::TransferLocked(e) => revert::encode(e),
+        }
+    }
+}
+
+impl Soulbound {
+    pub fn is_locked(&self, id: U256) -> bool {
+        self.bound.get(id)
+    }
+
+    pub fn set_locked(&mut self, id: U256, locked: bool) {
+        self.bound.insert(id, locked);
+        log(self.vm(), TransferLockSet { id, locked });
+    }
+
+    /// Locked ids can only be minted, burned or moved by guardian recovery.
+    pub fn check_transferable(&self, ids: &[U256]) -> Result<(), SoulboundError> {
+        for &id in ids {
+            if self.is_locked(id) {
+                return Err(SoulboundError::TransferLocked(ERC1155TransferLocked { id }));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,321 @@
+//! Merkle-Patricia proof verification for balances held by a deployment of this
+//! contract on another chain. Proofs are the `accountProof` / `storageProof[0].proof`
+//! arrays returned by `eth_getProof` for the slot from [`crate::slots::balance_slot`].
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    crypto::keccak,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::revert;
+
+sol_storage! {
+    pub struct RemoteStateRoots {
+        mapping(uint256 => mapping(uint256 => bytes32)) roots;
+    }
+}
+
+sol! {
+    event RemoteStateRootSet(uint256 indexed chainId, uint256 indexed blockNumber, bytes32 stateRoot);
+
+    error StorageProofInvalid();
+    error StorageProofAccountMissing(address account);
+    error UntrustedStateRoot(uint256 chainId, uint256 blockNumber);
+}
+
+pub enum StorageProofError {
+    Invalid(StorageProofInvalid),
+    AccountMissing(StorageProofAccountMissing),
+    UntrustedRoot(UntrustedStateRoot),
+}
+
+impl From<StorageProofError> for Vec<u8> {
+    fn from(error: StorageProofError) -> Self {
+        match error {
+            StorageProofError::Invalid(e) => revert::encode(e),
+            StorageProofError::AccountMissing(e) => revert::encode(e),
+            StorageProofError::UntrustedRoot(e) => revert::encode(e),
+        }
+    }
+}
+
+fn invalid() -> StorageProofError {
+    StorageProofError::Invalid(StorageProofInvalid {})
+}
+
+impl RemoteStateRoots {
+    pub fn root(&self, chain_id: U256, block_number: U256) -> B256 {
+        self.roots.get(chain_id).get(block_number)
+    }
+
+    pub fn set_root(&mut self, chain_id: U256, block_number: U256, state_root: B256) {
+        self.roots.setter(chain_id).insert(block_number, state_root);
+        log(self.vm(), RemoteStateRootSet {
+            chainId: chain_id,
+            blockNumber: block_number,
+            stateRoot: state_root,
+        });
+    }
+
+    pub fn trusted_root(&self, chain_id: U256, block_number: U256) -> Result<B256, StorageProofError> {
+        let root = self.root(chain_id, block_number);
+        if root.is_zero() {
+            return Err(StorageProofError::UntrustedRoot(UntrustedStateRoot {
+                chainId: chain_id,
+                blockNumber: block_number,
+            }));
+        }
+        Ok(root)
+    }
+}
+
+/// Proves the value of `slot` in `contract`'s storage under `state_root`. A slot that is
+/// provably absent from the storage trie reads as zero.
+pub fn verify_storage_slot(
+    state_root: B256,
+    contract: Address,
+    slot: B256,
+    account_proof: &[Bytes],
+    storage_proof: &[Bytes],
+) -> Result<U256, StorageProofError> {
+    let account_rlp = trie_get(state_root, keccak(contract.as_slice()), account_proof)?.ok_or(
+        StorageProofError::AccountMissing(StorageProofAccountMissing { account: contract }),
+    )?;
+    // account = rlp([nonce, balance, storageRoot, codeHash])
+    let account = rlp_list(account_rlp).ok_or_else(invalid)?;
+    let storage_root = account
+        .get(2)
+        .filter(|item| !item.is_list && item.payload.len() == 32)
+        .ok_or_else(invalid)?;
+
+    let Some(value_rlp) = trie_get(B256::from_slice(storage_root.payload), keccak(slot.as_slice()), storage_proof)? else {
+        return Ok(U256::ZERO);
+    };
+    let value = rlp_item(value_rlp).filter(|item| !item.is_list && item.payload.len() <= 32);
+    value
+        .map(|item| U256::from_be_slice(item.payload))
+        .ok_or_else(invalid)
+}
+
+struct RlpItem<'a> {
+    is_list: bool,
+    payload: &'a [u8],
+    raw: &'a [u8],
+}
+
+fn rlp_item(data: &[u8]) -> Option<RlpItem<'_>> {
+    let prefix = *data.first()?;
+    let (is_list, offset, len) = match prefix {
+        0x00..=0x7f => return Some(RlpItem { is_list: false, payload: &data[..1], raw: &data[..1] }),
+        0x80..=0xb7 => (false, 1, (prefix - 0x80) as usize),
+        0xb8..=0xbf => {
+            let len_of_len = (prefix - 0xb7) as usize;
+            (false, 1 + len_of_len, be_usize(data.get(1..1 + len_of_len)?)?)
+        }
+        0xc0..=0xf7 => (true, 1, (prefix - 0xc0) as usize),
+        0xf8..=0xff => {
+            let len_of_len = (prefix - 0xf7) as usize;
+            (true, 1 + len_of_len, be_usize(data.get(1..1 + len_of_len)?)?)
+        }
+    };
+    let end = offset.checked_add(len)?;
+    Some(RlpItem {
+        is_list,
+        payload: data.get(offset..end)?,
+        raw: data.get(..end)?,
+    })
+}
+
+fn rlp_list(data: &[u8]) -> Option<Vec<RlpItem<'_>>> {
+    let outer = rlp_item(data)?;
+    if !outer.is_list {
+        return None;
+    }
+    let mut items = Vec::new();
+    let mut rest = outer.payload;
+    while !rest.is_empty() {
+        let item = rlp_item(rest)?;
+        rest = &rest[item.raw.len()..];
+        items.push(item);
+    }
+    Some(items)
+}
+
+fn be_usize(bytes: &[u8]) -> Option<usize> {
+    if bytes.is_empty() || bytes.len() > 8 || bytes[0] == 0 {
+        return None;
+    }
+    Some(bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize))
+}
+
+/// Decodes a hex-prefix encoded path into `(nibbles, is_leaf)`.
+fn hex_prefix(encoded: &[u8]) -> Option<(Vec<u8>, bool)> {
+    let first = *encoded.first()?;
+    let flag = first >> 4;
+    let mut nibbles = Vec::with_capacity(encoded.len() * 2);
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in &encoded[1..] {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+    Some((nibbles, flag & 2 == 2))
+}
+
+/// Walks the trie from `root` along `key`, returning the leaf value (still RLP-encoded) or
+/// `None` if the proof shows the key is absent.
+fn trie_get<'a>(root: B256, key: B256, proof: &'a [Bytes]) -> Result<Option<&'a [u8]>, StorageProofError> {
+    let path: Vec<u8> = key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect();
+    let mut depth = 0;
+    let mut next_proof = 0;
+    let mut expected_hash = Some(root);
+    let mut inline: &'a [u8] = &[];
+
+    loop {
+        let node: &'a [u8] = match expected_hash {
+            Some(hash) => {
+                let node = proof.get(next_proof).ok_or_else(invalid)?;
+                next_proof += 1;
+                if keccak(node.as_slice()) != hash {
+                    return Err(invalid());
+                }
+                node.as_slice()
+            }
+            None => inline,
+        };
+
+        let items = rlp_list(node).ok_or_else(invalid)?;
+        let child = match items.len() {
+            17 => {
+                if depth == path.len() {
+                    return Ok(Some(items[16].payload).filter(|v| !v.is_empty()));
+                }
+                let child = &items[path[depth] as usize];
+                depth += 1;
+                child
+            }
+            2 => {
+                let (segment, is_leaf) = hex_prefix(items[0].payload).ok_or_else(invalid)?;
+                let remaining = &path[depth..];
+                if is_leaf {
+                    return Ok((remaining == segment.as_slice()).then_some(items[1].payload));
+                }
+                if !remaining.starts_with(&segment) {
+                    return Ok(None);
+                }
+                depth += segment.len();
+                &items[1]
+            }
+            _ => return Err(invalid()),
+        };
+
+        if child.is_list {
+            expected_hash = None;
+            inline = child.raw;
+        } else if child.payload.is_empty() {
+            return Ok(None);
+        } else if child.payload.len() == 32 {
+            expected_hash = Some(B256::from_slice(child.payload));
+        } else {
+            return Err(invalid());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::alloy_primitives::{address, b256, hex, uint};
+
+    // eth_getProof output for a two-account state trie. CONTRACT holds slot 0x1234 = 1000
+    // and slot 0x5678 = 7; the other account's leaf only shapes the root branch.
+    const STATE_ROOT: B256 = b256!("3b77c959dcf8121e37ac26579ccc3ee9d1d9931ff9bc94ccd4e91e45104d4d80");
+    const CONTRACT: Address = address!("c0ffee0000000000000000000000000000001155");
+    const SLOT: U256 = uint!(0x1234_U256);
+    const ABSENT_SLOT: U256 = uint!(0x9000_U256);
+    const ACCOUNT_BRANCH: [u8; 83] = hex!("f8518080808080a0c98ccf78e448e3dc31e31a6f128cc3b5aebdb5a57e156ebb1f945358331c9f8480a094b0563bef058059d1e6bcb58291b2e13ba3e9bab35dfb0c507c0817c65b5c7a808080808080808080");
+    const ACCOUNT_LEAF: [u8; 107] = hex!("f869a0303906a38065872fe83a04d952765070aefaa635f7b9f53620afc54741fcd12bb846f8440180a09f5e9d4d8ce9ed6884a3cd8f6e7e4885a1380e658fb258329f6bf142a940da8ba02dc081a8d6d4714c79b5abd2e9b08c3a33b4ef1dcf946ef8b8cf6c495014f47b");
+    const STORAGE_BRANCH: [u8; 83] = hex!("f851808080808080a019b77a7ee210808f4641c1e8b96900ba97b6d84263437f5bff07ca941d3c3c6b80808080808080a0822943807ed1649c6ec5fb8337fd52f5eafa86306179a21393876655481d1e238080");
+    const STORAGE_LEAF: [u8; 38] = hex!("e5a03321d900f3fd366734e2d071e30949ded20c27fd638f1a059390091c643b62c5838203e8");
+
+    fn proof(nodes: &[&[u8]]) -> Vec<Bytes> {
+        nodes.iter().map(|node| Bytes::from(node.to_vec())).collect()
+    }
+
+    fn account_proof() -> Vec<Bytes> {
+        proof(&[&ACCOUNT_BRANCH, &ACCOUNT_LEAF])
+    }
+
+    #[test]
+    fn proves_a_stored_slot() {
+        let storage_proof = proof(&[&STORAGE_BRANCH, &STORAGE_LEAF]);
+        let value = verify_storage_slot(STATE_ROOT, CONTRACT, B256::from(SLOT), &account_proof(), &storage_proof);
+        assert_eq!(value.ok(), Some(U256::from(1000)));
+    }
+
+    #[test]
+    fn proves_an_absent_slot_as_zero() {
+        let storage_proof = proof(&[&STORAGE_BRANCH]);
+        let value = verify_storage_slot(STATE_ROOT, CONTRACT, B256::from(ABSENT_SLOT), &account_proof(), &storage_proof);
+        assert_eq!(value.ok(), Some(U256::ZERO));
+    }
+
+    #[test]
+    fn missing_accounts_are_reported() {
+        let stranger = address!("0000000000000000000000000000000000000001");
+        let result = verify_storage_slot(STATE_ROOT, stranger, B256::from(SLOT), &proof(&[&ACCOUNT_BRANCH]), &[]);
+        assert!(matches!(result, Err(StorageProofError::AccountMissing(e)) if e.account == stranger));
+    }
+
+    #[test]
+    fn rejects_a_wrong_root() {
+        let storage_proof = proof(&[&STORAGE_BRANCH, &STORAGE_LEAF]);
+        let root = b256!("3b77c959dcf8121e37ac26579ccc3ee9d1d9931ff9bc94ccd4e91e45104d4d81");
+        let result = verify_storage_slot(root, CONTRACT, B256::from(SLOT), &account_proof(), &storage_proof);
+        assert!(matches!(result, Err(StorageProofError::Invalid(_))));
+    }
+
+    #[test]
+    fn rejects_tampered_and_truncated_proofs() {
+        // A leaf for another value no longer hashes to the branch's reference.
+        let mut leaf = STORAGE_LEAF;
+        leaf[37] = 0xe9;
+        let result = verify_storage_slot(STATE_ROOT, CONTRACT, B256::from(SLOT), &account_proof(), &proof(&[&STORAGE_BRANCH, &leaf]));
+        assert!(matches!(result, Err(StorageProofError::Invalid(_))));
+
+        // The proof stops at a hashed reference.
+        let result = verify_storage_slot(STATE_ROOT, CONTRACT, B256::from(SLOT), &account_proof(), &proof(&[&STORAGE_BRANCH]));
+        assert!(matches!(result, Err(StorageProofError::Invalid(_))));
+    }
+
+    #[test]
+    fn rejects_malformed_nodes() {
+        let key = keccak(B256::from(SLOT).as_slice());
+        // Declares a 0x51-byte payload but ends early.
+        let truncated = &STORAGE_BRANCH[..60];
+        assert!(trie_get(keccak(truncated), key, &proof(&[truncated])).is_err());
+        // A well-formed list that is neither a branch nor an extension/leaf.
+        let three_items = hex!("c3010203");
+        assert!(trie_get(keccak(three_items), key, &proof(&[&three_items])).is_err());
+        // Not a list at all.
+        let string = hex!("83010203");
+        assert!(trie_get(keccak(string), key, &proof(&[&string])).is_err());
+    }
+
+    #[test]
+    fn decodes_rlp_lengths() {
+        let long = [&[0xb8, 0x38][..], &[0xaa; 0x38]].concat();
+        let item = rlp_item(&long).map(|item| (item.is_list, item.payload.len(), item.raw.len()));
+        assert_eq!(item, Some((false, 0x38, 0x3a)));
+        // Lengths with a leading zero byte are non-canonical.
+        assert!(rlp_item(&[0xb9, 0x00, 0x38]).is_none());
+        assert!(rlp_item(&[0x82, 0x01]).is_none());
+        assert_eq!(rlp_item(&[0x7f]).map(|item| item.payload.to_vec()), Some(vec![0x7f]));
+    }
+}
@@ -0,0 +1,152 @@
+//! Escrowless OTC swaps: the maker signs a [`SwapOrder`] and any taker (or the named
+//! one) settles both legs in a single call.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{b256, Address, B256, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::{
+    ecdsa,
+    eip712::{self, address_word},
+    erc1155::ERC1155InvalidArrayLength,
+    nonces::{InvalidAccountNonce, NonceError, SignatureNonces},
+    revert,
+    webauthn::Passkeys,
+};
+
+/// `keccak256("SwapOrder(address maker,address taker,uint256[] giveIds,uint256[] giveAmounts,uint256[] wantIds,uint256[] wantAmounts,uint256 wantEth,uint256 nonce,uint256 expiry)")`
+pub const SWAP_ORDER_TYPEHASH: B256 =
+    b256!("53ef57463bac93ea7f3ba3cfe1b6bd06f39d2ae4ffa404f0eb626d9c48549f23");
+
+sol! {
+    /// `taker == address(0)` lets anyone fill. The taker pays `wantIds`/`wantAmounts`
+    /// and `wantEth` (either side may be empty) and receives `giveIds`/`giveAmounts`.
+    struct SwapOrder {
+        address maker;
+        address taker;
+        uint256[] giveIds;
+        uint256[] giveAmounts;
+        uint256[] wantIds;
+        uint256[] wantAmounts;
+        uint256 wantEth;
+        uint256 nonce;
+        uint256 expiry;
+    }
+
+    event SwapFilled(address indexed maker, address indexed taker, uint256 indexed nonce);
+
+    error SwapExpired(uint256 expiry);
+    error SwapInvalidSigner(address signer, address maker);
+    error SwapInvalidTaker(address taker);
+    error SwapIncorrectPayment(uint256 expected, uint256 received);
+    error SwapPaymentFailed(address to, uint256 amount);
+}
+
+pub enum SwapError {
+    Expired(SwapExpired),
+    InvalidSigner(SwapInvalidSigner),
+    InvalidTaker(SwapInvalidTaker),
+    IncorrectPayment(SwapIncorrectPayment),
+    PaymentFailed(SwapPaymentFailed),
+    InvalidArrayLength(ERC1155InvalidArrayLength),
+    InvalidNonce(InvalidAccountNonce),
+}
+
+impl From<SwapError> for Vec<u8> {
+    fn from(error: SwapError) -> Self {
+        match error {
+            SwapError::Expired(e) => revert::encode(e),
+            SwapError::InvalidSigner(e) => revert::encode(e),
+            SwapError::InvalidTaker(e) => revert::encode(e),
+            SwapError::IncorrectPayment(e) => revert::encode(e),
+            SwapError::PaymentFailed(e) => revert::encode(e),
+            SwapError::InvalidArrayLength(e) => revert::encode(e),
+            SwapError::InvalidNonce(e) => revert::encode(e),
+        }
+    }
+}
+
+impl From<NonceError> for SwapError {
+    fn from(error: NonceError) -> Self {
+        match error {
+            NonceError::InvalidAccountNonce(e) => SwapError::InvalidNonce(e),
+        }
+    }
+}
+
+/// Checks `order` against `taker` and the attached `value`, verifies the maker's
+/// signature (ECDSA or passkey) and consumes the order's nonce. The caller moves the assets.
+pub fn verify(
+    nonces: &mut SignatureNonces,
+    passkeys: &Passkeys,
+    order: &SwapOrder,
+    taker: Address,
+    value: U256,
+    signature: &[u8],
+) -> Result<(), SwapError> {
+    check_lengths(&order.giveIds, &order.giveAmounts)?;
+    check_lengths(&order.wantIds, &order.wantAmounts)?;
+    if U256::from(nonces.vm().block_timestamp()) > order.expiry {
+        return Err(SwapError::Expired(SwapExpired { expiry: order.expiry }));
+    }
+    if !order.taker.is_zero() && order.taker != taker {
+        return Err(SwapError::InvalidTaker(SwapInvalidTaker { taker }));
+    }
+    if value != order.wantEth {
+        return Err(SwapError::IncorrectPayment(SwapIncorrectPayment {
+            expected: order.wantEth,
+            received: value,
+        }));
+    }
+
+    let digest = eip712::hash_typed_data(nonces.vm(), struct_hash(order));
+    let signer = if passkeys.verify(order.maker, digest, signature) {
+        order.maker
+    } else {
+        ecdsa::recover_signature(digest, signature).unwrap_or_default()
+    };
+    if signer != order.maker {
+        return Err(SwapError::InvalidSigner(SwapInvalidSigner { signer, maker: order.maker }));
+    }
+    nonces._use_unordered(order.maker, order.nonce)?;
+
+    log(nonces.vm(), SwapFilled {
+        maker: order.maker,
+        taker,
+        nonce: order.nonce,
+    });
+    Ok(())
+}
+
+fn struct_hash(order: &SwapOrder) -> B256 {
+    let words = |values: &[U256]| -> B256 {
+        let words: Vec<B256> = values.iter().map(|v| B256::from(*v)).collect();
+        eip712::hash_words(&words)
+    };
+    eip712::hash_words(&[
+        SWAP_ORDER_TYPEHASH,
+        address_word(order.maker),
+        address_word(order.taker),
+        words(&order.giveIds),
+        words(&order.giveAmounts),
+        words(&order.wantIds),
+        words(&order.wantAmounts),
+        B256::from(order.wantEth),
+        B256::from(order.nonce),
+        B256::from(order.expiry),
+    ])
+}
+
+fn check_lengths(ids: &[U256], amounts: &[U256]) -> Result<(), SwapError> {
+    if ids.len() != amounts.len() {
+        return Err(SwapError::InvalidArrayLength(ERC1155InvalidArrayLength {
+            idsLength: U256::from(ids.len()),
+            valuesLength: U256::from(amounts.len()),
+        }));
+    }
+    Ok(())
+}
@@ -0,0 +1,197 @@
+//! Community treasury held by this contract. Units deposited here are booked per id,
+//! separately from marketplace escrow, and only leave through transfers the governor
+//! queued at least `delay` seconds earlier.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+    stylus_core::log,
+};
+
+use crate::{erc1155::ERC1155InvalidArrayLength, revert};
+
+sol_storage! {
+    pub struct Treasury {
+        address governor;
+        uint256 delay;
+        mapping(uint256 => uint256) held;
+        uint256 proposal_count;
+        mapping(uint256 => TreasuryProposal) proposals;
+    }
+
+    pub struct TreasuryProposal {
+        address to;
+        uint256[] ids;
+        uint256[] amounts;
+        uint256 eta;
+        bool executed;
+        bool cancelled;
+    }
+}
+
+sol! {
+    event TreasuryGovernorSet(address indexed governor);
+    event TreasuryDelaySet(uint256 delay);
+    event TreasuryDeposit(address indexed from, uint256 indexed id, uint256 amount);
+    event TreasuryTransferQueued(uint256 indexed proposalId, address indexed to, uint256[] ids, uint256[] amounts, uint256 eta);
+    event TreasuryTransferCancelled(uint256 indexed proposalId);
+    event TreasuryTransferExecuted(uint256 indexed proposalId);
+
+    error TreasuryUnauthorized(address account);
+    error TreasuryUnknownProposal(uint256 proposalId);
+    error TreasuryNotReady(uint256 proposalId, uint256 eta);
+    error TreasuryInsufficientHoldings(uint256 id, uint256 requested, uint256 held);
+}
+
+pub enum TreasuryError {
+    Unauthorized(TreasuryUnauthorized),
+    UnknownProposal(TreasuryUnknownProposal),
+    NotReady(TreasuryNotReady),
+    InsufficientHoldings(TreasuryInsufficientHoldings),
+    InvalidArrayLength(ERC1155InvalidArrayLength),
+}
+
+impl From<TreasuryError> for Vec<u8> {
+    fn from(error: TreasuryError) -> Self {
+        match error {
+            TreasuryError::Unauthorized(e) => revert::encode(e),
+            TreasuryError::UnknownProposal(e) => revert::encode(e),
+            TreasuryError::NotReady(e) => revert::encode(e),
+            TreasuryError::InsufficientHoldings(e) => revert::encode(e),
+            TreasuryError::InvalidArrayLength(e) => revert::encode(e),
+        }
+    }
+}
+
+impl Treasury {
+    pub fn governor(&self) -> Address {
+        self.governor.get()
+    }
+
+    pub fn delay(&self) -> U256 {
+        self.delay.get()
+    }
+
+    pub fn held(&self, id: U256) -> U256 {
+        self.held.get(id)
+    }
+
+    /// Returns `(to, ids, amounts, eta, executed, cancelled)`.
+    pub fn proposal(&self, proposal_id: U256) -> (Address, Vec<U256>, Vec<U256>, U256, bool, bool) {
+        let proposal = self.proposals.get(proposal_id);
+        (
+            proposal.to.get(),
+            (0..proposal.ids.len()).filter_map(|i| proposal.ids.get(i)).collect(),
+            (0..proposal.amounts.len()).filter_map(|i| proposal.amounts.get(i)).collect(),
+            proposal.eta.get(),
+            proposal.executed.get(),
+            proposal.cancelled.get(),
+        )
+    }
+
+    /// Hands the treasury to `governor`. Once set, only the governor itself can replace it.
+    pub fn _set_governor(&mut self, governor: Address) {
+        self.governor.set(governor);
+        log(self.vm(), TreasuryGovernorSet { governor });
+    }
+
+    pub fn set_delay(&mut self, delay: U256) -> Result<(), TreasuryError> {
+        self.only_governor()?;
+        self.delay.set(delay);
+        log(self.vm(), TreasuryDelaySet { delay });
+        Ok(())
+    }
+
+    pub fn only_governor(&self) -> Result<(), TreasuryError> {
+        let account = self.vm().msg_sender();
+        if account.is_zero() || account != self.governor.get() {
+            return Err(TreasuryError::Unauthorized(TreasuryUnauthorized { account }));
+        }
+        Ok(())
+    }
+
+    /// Books units the caller has already moved into the contract.
+    pub fn _deposit(&mut self, id: U256, amount: U256) {
+        self.held.insert(id, self.held.get(id) + amount);
+        log(self.vm(), TreasuryDeposit {
+            from: self.vm().msg_sender(),
+            id,
+            amount,
+        });
+    }
+
+    pub fn queue(&mut self, to: Address, ids: Vec<U256>, amounts: Vec<U256>) -> Result<U256, TreasuryError> {
+        self.only_governor()?;
+        if ids.len() != amounts.len() {
+            return Err(TreasuryError::InvalidArrayLength(ERC1155InvalidArrayLength {
+                idsLength: U256::from(ids.len()),
+                valuesLength: U256::from(amounts.len()),
+            }));
+        }
+
+        let proposal_id = self.proposal_count.get() + U256::from(1);
+        self.proposal_count.set(proposal_id);
+        let eta = U256::from(self.vm().block_timestamp()) + self.delay.get();
+
+        let mut proposal = self.proposals.setter(proposal_id);
+        proposal.to.set(to);
+        for (id, amount) in ids.iter().zip(amounts.iter()) {
+            proposal.ids.push(*id);
+            proposal.amounts.push(*amount);
+        }
+        proposal.eta.set(eta);
+
+        log(self.vm(), TreasuryTransferQueued {
+            proposalId: proposal_id,
+            to,
+            ids,
+            amounts,
+            eta,
+        });
+        Ok(proposal_id)
+    }
+
+    pub fn cancel(&mut self, proposal_id: U256) -> Result<(), TreasuryError> {
+        self.only_governor()?;
+        self._open_proposal(proposal_id)?;
+        self.proposals.setter(proposal_id).cancelled.set(true);
+        log(self.vm(), TreasuryTransferCancelled { proposalId: proposal_id });
+        Ok(())
+    }
+
+    /// Marks a matured proposal executed, debits the holdings and returns
+    /// `(to, ids, amounts)` for the caller to transfer out.
+    pub fn _execute(&mut self, proposal_id: U256) -> Result<(Address, Vec<U256>, Vec<U256>), TreasuryError> {
+        self._open_proposal(proposal_id)?;
+        let (to, ids, amounts, eta, _, _) = self.proposal(proposal_id);
+        if U256::from(self.vm().block_timestamp()) < eta {
+            return Err(TreasuryError::NotReady(TreasuryNotReady { proposalId: proposal_id, eta }));
+        }
+
+        for (&id, &amount) in ids.iter().zip(amounts.iter()) {
+            let held = self.held.get(id);
+            if amount > held {
+                return Err(TreasuryError::InsufficientHoldings(TreasuryInsufficientHoldings {
+                    id,
+                    requested: amount,
+                    held,
+                }));
+            }
+            self.held.insert(id, held - amount);
+        }
+        self.proposals.setter(proposal_id).executed.set(true);
+
+        log(self.vm(), TreasuryTransferExecuted { proposalId: proposal_id });
+        Ok((to, ids, amounts))
+    }
+
+    fn _open_proposal(&self, proposal_id: U256) -> Result<(), TreasuryError> {
+        let proposal = self.proposals.get(proposal_id);
+        if proposal.eta.get().is_zero() || proposal.executed.get() || proposal.cancelled.get() {
+            return Err(TreasuryError::UnknownProposal(TreasuryUnknownProposal { proposalId: proposal_id }));
+        }
+        Ok(())
+    }
+}
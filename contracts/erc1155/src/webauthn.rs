@@ -0,0 +1,265 @@
+//! WebAuthn (passkey) signatures over secp256r1, so smart accounts whose signer is a
+//! passkey can sign permits and orders. An account registers its P-256 public key once;
+//! signatures longer than 65 bytes are then read as an ABI-encoded [`WebAuthnAuth`].
+//!
+//! Verification goes through the RIP-7212 `P256VERIFY` precompile. Chains without it
+//! return empty output, which is indistinguishable from a bad signature, so builds with
+//! the `p256-fallback` feature re-check failures in software.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{address, uint, Address, B256, U256},
+    alloy_sol_types::{sol, SolValue},
+    call::{static_call, Call},
+    prelude::*,
+    stylus_core::log,
+};
+
+pub const SHA256: Address = address!("0000000000000000000000000000000000000002");
+pub const P256VERIFY: Address = address!("0000000000000000000000000000000000000100");
+
+/// secp256r1 `n / 2`. As with secp256k1, high-`s` signatures are rejected.
+const P256_HALF_ORDER: U256 = uint!(0x7FFFFFFF800000007FFFFFFFFFFFFFFFDE737D56D38BCF4279DCE5617E3192A8_U256);
+
+/// User-present flag in the authenticator data.
+const FLAG_UP: u8 = 0x01;
+
+const TYPE_GET: &[u8] = br#""type":"webauthn.get""#;
+const CHALLENGE_PREFIX: &[u8] = br#""challenge":""#;
+
+sol_storage! {
+    pub struct Passkeys {
+        mapping(address => bytes32) x;
+        mapping(address => bytes32) y;
+    }
+}
+
+sol! {
+    event PasskeySet(address indexed account, bytes32 x, bytes32 y);
+
+    /// `challengeIndex` and `typeIndex` point at `"challenge":"…"` and `"type":"…"` in
+    /// `clientDataJSON`, so the JSON never has to be parsed on-chain.
+    struct WebAuthnAuth {
+        bytes authenticatorData;
+        string clientDataJSON;
+        uint256 challengeIndex;
+        uint256 typeIndex;
+        bytes32 r;
+        bytes32 s;
+    }
+}
+
+impl Passkeys {
+    pub fn passkey(&self, account: Address) -> (B256, B256) {
+        (self.x.get(account), self.y.get(account))
+    }
+
+    /// Registers the caller's P-256 public key. Zero coordinates remove it.
+    pub fn set_passkey(&mut self, x: B256, y: B256) {
+        let account = self.vm().msg_sender();
+        self.x.insert(account, x);
+        self.y.insert(account, y);
+        log(self.vm(), PasskeySet { account, x, y });
+    }
+
+    /// Whether `signature` is a WebAuthn assertion over `challenge` by `account`'s
+    /// registered passkey. ECDSA-sized signatures are never passkey signatures.
+    pub fn verify(&self, account: Address, challenge: B256, signature: &[u8]) -> bool {
+        if signature.len() <= 65 {
+            return false;
+        }
+        let (x, y) = self.passkey(account);
+        if x.is_zero() && y.is_zero() {
+            return false;
+        }
+        let Ok(auth) = WebAuthnAuth::abi_decode(signature, true) else {
+            return false;
+        };
+        verify_assertion(challenge, &auth, x, y)
+    }
+}
+
+fn verify_assertion(challenge: B256, auth: &WebAuthnAuth, x: B256, y: B256) -> bool {
+    if !check_assertion_data(challenge, auth) {
+        return false;
+    }
+    let client_data = auth.clientDataJSON.as_bytes();
+    let authenticator_data = auth.authenticatorData.as_ref();
+    let Some(client_data_hash) = sha256(client_data) else {
+        return false;
+    };
+    let mut message = authenticator_data.to_vec();
+    message.extend_from_slice(client_data_hash.as_slice());
+    let Some(hash) = sha256(&message) else {
+        return false;
+    };
+    p256_verify(hash, auth.r, auth.s, x, y)
+}
+
+/// The checks on an assertion that don't involve the signature itself: the user was
+/// present, `clientDataJSON` is a `webauthn.get` over `challenge`, and `s` is low.
+fn check_assertion_data(challenge: B256, auth: &WebAuthnAuth) -> bool {
+    let client_data = auth.clientDataJSON.as_bytes();
+    let authenticator_data = auth.authenticatorData.as_ref();
+    if authenticator_data.len() < 37 || authenticator_data[32] & FLAG_UP == 0 {
+        return false;
+    }
+    if !contains_at(client_data, auth.typeIndex, TYPE_GET) {
+        return false;
+    }
+
+    let mut expected = CHALLENGE_PREFIX.to_vec();
+    expected.extend_from_slice(&base64url(challenge.as_slice()));
+    expected.push(b'"');
+    if !contains_at(client_data, auth.challengeIndex, &expected) {
+        return false;
+    }
+    U256::from_be_bytes(auth.s.0) <= P256_HALF_ORDER
+}
+
+/// Verifies a raw secp256r1 signature over a prehashed message.
+pub fn p256_verify(hash: B256, r: B256, s: B256, x: B256, y: B256) -> bool {
+    let mut input = [0u8; 160];
+    for (i, word) in [hash, r, s, x, y].iter().enumerate() {
+        input[i * 32..(i + 1) * 32].copy_from_slice(word.as_slice());
+    }
+    match static_call(Call::new(), P256VERIFY, &input) {
+        Ok(output) if output.len() == 32 => output[31] == 1,
+        _ => software_verify(hash, r, s, x, y),
+    }
+}
+
+#[cfg(feature = "p256-fallback")]
+fn software_verify(hash: B256, r: B256, s: B256, x: B256, y: B256) -> bool {
+    use p256::{
+        ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey},
+        EncodedPoint,
+    };
+
+    let point = EncodedPoint::from_affine_coordinates(&x.0.into(), &y.0.into(), false);
+    let Ok(key) = VerifyingKey::from_encoded_point(&point) else {
+        return false;
+    };
+    let Ok(signature) = Signature::from_scalars(r.0, s.0) else {
+        return false;
+    };
+    key.verify_prehash(hash.as_slice(), &signature).is_ok()
+}
+
+#[cfg(not(feature = "p256-fallback"))]
+fn software_verify(_hash: B256, _r: B256, _s: B256, _x: B256, _y: B256) -> bool {
+    false
+}
+
+fn sha256(data: &[u8]) -> Option<B256> {
+    let output = static_call(Call::new(), SHA256, data).ok()?;
+    (output.len() == 32).then(|| B256::from_slice(&output))
+}
+
+fn contains_at(haystack: &[u8], index: U256, needle: &[u8]) -> bool {
+    let Ok(start) = usize::try_from(index) else {
+        return false;
+    };
+    start
+        .checked_add(needle.len())
+        .and_then(|end| haystack.get(start..end))
+        .is_some_and(|found| found == needle)
+}
+
+/// Unpadded base64url, as WebAuthn encodes the challenge.
+fn base64url(data: &[u8]) -> Vec<u8> {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut out = Vec::with_capacity((data.len() * 4).div_ceil(3));
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |acc, (i, b)| acc | ((*b as u32) << (16 - 8 * i)));
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[((bits >> (18 - 6 * i)) & 0x3f) as usize]);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::alloy_primitives::{b256, hex};
+
+    // A browser-shaped assertion over CHALLENGE for rpId `example.com`.
+    const CHALLENGE: B256 = b256!("f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0");
+    const CLIENT_DATA: &str = r#"{"type":"webauthn.get","challenge":"8PDw8PDw8PDw8PDw8PDw8PDw8PDw8PDw8PDw8PDw8PA","origin":"https://example.com","crossOrigin":false}"#;
+    const AUTHENTICATOR_DATA: [u8; 37] = hex!("a379a6f6eeafb9a55e378c118034e2751e682fab9f2d30ab13d2125586ce19470500000000");
+    const R: B256 = b256!("8f0ae8b1367dfacfed24a12c2419863a31f50ecbe4322244c063dcd90576eab4");
+    const S: B256 = b256!("4e532ed1246045202bc198fb372c9da4b1c3780112f3172a9e549cd013f86c86");
+
+    fn assertion() -> WebAuthnAuth {
+        WebAuthnAuth {
+            authenticatorData: AUTHENTICATOR_DATA.to_vec().into(),
+            clientDataJSON: CLIENT_DATA.into(),
+            challengeIndex: U256::from(23),
+            typeIndex: U256::from(1),
+            r: R,
+            s: S,
+        }
+    }
+
+    #[test]
+    fn base64url_is_unpadded() {
+        assert_eq!(base64url(CHALLENGE.as_slice()), b"8PDw8PDw8PDw8PDw8PDw8PDw8PDw8PDw8PDw8PDw8PA");
+        assert_eq!(base64url(b"\xfb\xff"), b"-_8");
+        assert_eq!(base64url(b""), b"");
+    }
+
+    #[test]
+    fn accepts_a_well_formed_assertion() {
+        assert!(check_assertion_data(CHALLENGE, &assertion()));
+    }
+
+    #[test]
+    fn rejects_misplaced_or_mismatched_client_data() {
+        let mut auth = assertion();
+        auth.challengeIndex = U256::from(24);
+        assert!(!check_assertion_data(CHALLENGE, &auth));
+
+        let mut auth = assertion();
+        auth.typeIndex = U256::MAX;
+        assert!(!check_assertion_data(CHALLENGE, &auth));
+
+        let mut auth = assertion();
+        auth.clientDataJSON = CLIENT_DATA.replace("webauthn.get", "webauthn.create");
+        assert!(!check_assertion_data(CHALLENGE, &auth));
+
+        assert!(!check_assertion_data(B256::ZERO, &assertion()));
+    }
+
+    #[test]
+    fn rejects_short_or_absent_user_authenticator_data() {
+        let mut auth = assertion();
+        auth.authenticatorData = AUTHENTICATOR_DATA[..36].to_vec().into();
+        assert!(!check_assertion_data(CHALLENGE, &auth));
+
+        let mut data = AUTHENTICATOR_DATA;
+        data[32] = 0x04;
+        let mut auth = assertion();
+        auth.authenticatorData = data.to_vec().into();
+        assert!(!check_assertion_data(CHALLENGE, &auth));
+    }
+
+    #[test]
+    fn rejects_high_s() {
+        let mut auth = assertion();
+        auth.s = B256::from(P256_HALF_ORDER + U256::from(1));
+        assert!(!check_assertion_data(CHALLENGE, &auth));
+    }
+
+    #[cfg(feature = "p256-fallback")]
+    #[test]
+    fn verifies_p256_signatures_in_software() {
+        // sha256(authenticatorData || sha256(clientDataJSON))
+        let hash = b256!("706eb5c634499f39d6310a76f650f83bc055029b85c950e5894b7cd834f18ce0");
+        let x = b256!("471c3e758c4904285bba7e53118ed0f524adeb0757d25bd2f8e7b0d76dfa714c");
+        let y = b256!("dd520f7aca8a8b917acc37f51de8f0c9bbe3ad858382e702dc25a12d09f7a858");
+        assert!(software_verify(hash, R, S, x, y));
+        assert!(!software_verify(hash, S, R, x, y));
+        assert!(!software_verify(B256::ZERO, R, S, x, y));
+    }
+}
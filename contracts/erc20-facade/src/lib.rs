@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: MIT
+//! The ERC-20 face of one id's wrapped balances in an `erc1155-stylus` contract (the
+//! hub). The hub clones this contract once per wrappable id and initializes the clone
+//! with the id. Balances and allowances live in the hub, which runs its transfer checks
+//! on every move; this contract only speaks the ERC-20 ABI and emits its events.
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    call::Call,
+    evm, msg,
+    prelude::*,
+    storage::{StorageAddress, StorageU256},
+};
+
+sol_interface! {
+    interface IWrapperHub {
+        function wrappedTotalSupply(uint256 id) external view returns (uint256);
+        function wrappedBalanceOf(uint256 id, address account) external view returns (uint256);
+        function wrappedAllowance(uint256 id, address owner, address spender) external view returns (uint256);
+        function facadeTransfer(address from, address to, uint256 amount) external returns (bool);
+        function facadeTransferFrom(address spender, address from, address to, uint256 amount) external returns (bool);
+        function facadeApprove(address owner, address spender, uint256 amount) external returns (bool);
+    }
+}
+
+sol! {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
+    error FacadeAlreadyInitialized(address hub);
+    error FacadeNotHub(address caller);
+}
+
+#[derive(SolidityError)]
+pub enum FacadeError {
+    AlreadyInitialized(FacadeAlreadyInitialized),
+    NotHub(FacadeNotHub),
+}
+
+#[entrypoint]
+#[storage]
+pub struct Erc20Facade {
+    hub: StorageAddress,
+    id: StorageU256,
+}
+
+#[public]
+impl Erc20Facade {
+    /// Called by the hub right after cloning, in the same transaction.
+    pub fn initialize(&mut self, id: U256) -> Result<(), FacadeError> {
+        let hub = self.hub.get();
+        if !hub.is_zero() {
+            return Err(FacadeError::AlreadyInitialized(FacadeAlreadyInitialized { hub }));
+        }
+        self.hub.set(msg::sender());
+        self.id.set(id);
+        Ok(())
+    }
+
+    pub fn hub(&self) -> Address {
+        self.hub.get()
+    }
+
+    /// The hub's token id this facade wraps.
+    pub fn id(&self) -> U256 {
+        self.id.get()
+    }
+
+    pub fn name(&self) -> String {
+        "Wrapped ERC-1155".into()
+    }
+
+    pub fn symbol(&self) -> String {
+        "W1155".into()
+    }
+
+    /// Units of an id are indivisible.
+    pub fn decimals(&self) -> u8 {
+        0
+    }
+
+    pub fn total_supply(&self) -> Result<U256, Vec<u8>> {
+        Ok(self.hub_contract().wrapped_total_supply(Call::new(), self.id.get())?)
+    }
+
+    pub fn balance_of(&self, account: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.hub_contract().wrapped_balance_of(Call::new(), self.id.get(), account)?)
+    }
+
+    pub fn allowance(&self, owner: Address, spender: Address) -> Result<U256, Vec<u8>> {
+        Ok(self.hub_contract().wrapped_allowance(Call::new(), self.id.get(), owner, spender)?)
+    }
+
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let from = msg::sender();
+        self.hub_contract().facade_transfer(Call::new_in(self), from, to, value)?;
+        evm::log(Transfer { from, to, value });
+        Ok(true)
+    }
+
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let spender = msg::sender();
+        self.hub_contract().facade_transfer_from(Call::new_in(self), spender, from, to, value)?;
+        evm::log(Transfer { from, to, value });
+        Ok(true)
+    }
+
+    pub fn approve(&mut self, spender: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let owner = msg::sender();
+        self.hub_contract().facade_approve(Call::new_in(self), owner, spender, value)?;
+        evm::log(Approval { owner, spender, value });
+        Ok(true)
+    }
+
+    /// Hub-only: logs the `Transfer` of a wrap (from zero) or unwrap (to zero).
+    pub fn notify_transfer(&mut self, from: Address, to: Address, value: U256) -> Result<(), FacadeError> {
+        let caller = msg::sender();
+        if caller != self.hub.get() {
+            return Err(FacadeError::NotHub(FacadeNotHub { caller }));
+        }
+        evm::log(Transfer { from, to, value });
+        Ok(())
+    }
+}
+
+impl Erc20Facade {
+    fn hub_contract(&self) -> IWrapperHub {
+        IWrapperHub::new(self.hub.get())
+    }
+}
@@ -0,0 +1,5 @@
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+
+fn main() {
+    // Empty main for binary compilation
+}
@@ -0,0 +1,61 @@
+#!/bin/bash
+# Regenerates contracts/erc1155/src/error_selectors.rs from every `error` declared in a
+# `sol!` block under contracts/erc1155/src. Requires `cast` (Foundry).
+# Run after adding or changing a custom error: bash scripts/gen-error-selectors.sh
+
+set -euo pipefail
+
+SRC_DIR="contracts/erc1155/src"
+OUT="$SRC_DIR/error_selectors.rs"
+
+signatures=$(grep -h -o '^\s*error [A-Za-z0-9_]*(.*);' "$SRC_DIR"/*.rs \
+  | sed -E 's/^\s*error //; s/;$//' \
+  | awk -F'(' '{
+      name = $1; params = substr($0, length(name) + 2); sub(/\)$/, "", params);
+      n = split(params, parts, ",");
+      types = "";
+      for (i = 1; i <= n; i++) {
+        gsub(/^ +| +$/, "", parts[i]);
+        if (parts[i] == "") continue;
+        split(parts[i], words, " ");
+        types = types (types == "" ? "" : ",") words[1];
+      }
+      print name "(" types ")";
+    }' \
+  | LC_ALL=C sort -u)
+
+{
+  echo "//! Generated by \`scripts/gen-error-selectors.sh\`; do not edit by hand."
+  echo "//!"
+  echo "//! Every custom error the contract can revert with, keyed by name, so SDKs and"
+  echo "//! monitoring can decode reverts without the full ABI."
+  echo ""
+  echo "use stylus_sdk::alloy_primitives::{fixed_bytes, FixedBytes};"
+  echo ""
+  echo "/// \`(name, signature, selector)\` for each custom error, sorted by name."
+  echo "pub const ERROR_SELECTORS: &[(&str, &str, FixedBytes<4>)] = &["
+  while IFS= read -r sig; do
+    name="${sig%%(*}"
+    selector=$(cast sig "$sig")
+    echo "    (\"$name\", \"$sig\", fixed_bytes!(\"${selector#0x}\")),"
+  done <<< "$signatures"
+  echo "];"
+  echo ""
+  echo "/// Selector of the error called \`name\`, if the contract declares one."
+  echo "pub fn selector_of(name: &str) -> Option<FixedBytes<4>> {"
+  echo "    ERROR_SELECTORS"
+  echo "        .iter()"
+  echo "        .find(|(n, _, _)| *n == name)"
+  echo "        .map(|(_, _, selector)| *selector)"
+  echo "}"
+  echo ""
+  echo "/// Name of the error whose selector is \`selector\`, for decoding revert data."
+  echo "pub fn name_of(selector: FixedBytes<4>) -> Option<&'static str> {"
+  echo "    ERROR_SELECTORS"
+  echo "        .iter()"
+  echo "        .find(|(_, _, s)| *s == selector)"
+  echo "        .map(|(name, _, _)| *name)"
+  echo "}"
+} > "$OUT"
+
+echo "Wrote $(echo "$signatures" | wc -l | tr -d ' ') selectors to $OUT"